@@ -0,0 +1,367 @@
+//! Comparison of two extracted crate snapshots.
+//!
+//! This works on [`CrateTypeInfo`] values directly and doesn't talk to the
+//! daemon, so it can compare a snapshot saved by an earlier CI run against
+//! the current one.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use bronzite_client::diff::diff;
+//!
+//! let mut old: CrateTypeInfo = serde_json::from_str(&baseline_json)?;
+//! let mut new = client.get_crate_info("my_crate")?;
+//!
+//! // Only compare the public API
+//! old.retain_public();
+//! new.retain_public();
+//!
+//! let changes = diff(&old, &new);
+//! if !changes.is_empty() {
+//!     println!("removed types: {:?}", changes.removed_types);
+//! }
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+
+use bronzite_types::{CrateTypeInfo, FieldInfo, TraitDetails, TypeDetails};
+
+/// Differences between two snapshots of a crate's API.
+///
+/// Types and traits are identified by path and fields by name (or index, for
+/// tuple fields). Inherent methods are identified by path, which names the
+/// impl's self type (`Wrapper::<u8>::get`), so same-named methods from
+/// different impl blocks stay apart; trait methods by name. All lists are
+/// sorted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApiDiff {
+    pub added_types: Vec<String>,
+    pub removed_types: Vec<String>,
+    pub changed_types: Vec<TypeChange>,
+    pub added_traits: Vec<String>,
+    pub removed_traits: Vec<String>,
+    pub changed_traits: Vec<TraitChange>,
+}
+
+impl ApiDiff {
+    /// Whether the two snapshots have the same API.
+    pub fn is_empty(&self) -> bool {
+        self.added_types.is_empty()
+            && self.removed_types.is_empty()
+            && self.changed_types.is_empty()
+            && self.added_traits.is_empty()
+            && self.removed_traits.is_empty()
+            && self.changed_traits.is_empty()
+    }
+}
+
+/// Changes to a type present in both snapshots.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TypeChange {
+    pub path: String,
+    /// Fields are keyed by name; fields of enum variants as `Variant.field`
+    pub added_fields: Vec<String>,
+    pub removed_fields: Vec<String>,
+    pub changed_fields: Vec<FieldChange>,
+    pub added_variants: Vec<String>,
+    pub removed_variants: Vec<String>,
+    /// Inherent methods, keyed by path
+    pub added_methods: Vec<String>,
+    pub removed_methods: Vec<String>,
+    pub changed_methods: Vec<SignatureChange>,
+}
+
+/// Changes to a trait present in both snapshots.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TraitChange {
+    pub path: String,
+    pub added_methods: Vec<String>,
+    pub removed_methods: Vec<String>,
+    pub changed_methods: Vec<SignatureChange>,
+}
+
+/// A field whose type changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    pub name: String,
+    pub old_ty: String,
+    pub new_ty: String,
+}
+
+/// A method whose signature changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureChange {
+    /// Path for inherent methods, name for trait methods
+    pub name: String,
+    pub old_signature: String,
+    pub new_signature: String,
+}
+
+/// Compare two snapshots of a crate.
+pub fn diff(old: &CrateTypeInfo, new: &CrateTypeInfo) -> ApiDiff {
+    let (added_types, removed_types, changed_types) =
+        compare_maps(&old.types, &new.types, type_change);
+    let (added_traits, removed_traits, changed_traits) =
+        compare_maps(&old.traits, &new.traits, trait_change);
+
+    ApiDiff {
+        added_types,
+        removed_types,
+        changed_types,
+        added_traits,
+        removed_traits,
+        changed_traits,
+    }
+}
+
+/// Split two maps into added keys, removed keys and the changes `compare`
+/// finds for keys in both.
+fn compare_maps<V, C>(
+    old: &HashMap<String, V>,
+    new: &HashMap<String, V>,
+    compare: impl Fn(&str, &V, &V) -> Option<C>,
+) -> (Vec<String>, Vec<String>, Vec<C>) {
+    let old: BTreeMap<&String, &V> = old.iter().collect();
+    let new: BTreeMap<&String, &V> = new.iter().collect();
+
+    let added = new
+        .keys()
+        .filter(|key| !old.contains_key(*key))
+        .map(|key| key.to_string())
+        .collect();
+    let removed = old
+        .keys()
+        .filter(|key| !new.contains_key(*key))
+        .map(|key| key.to_string())
+        .collect();
+    let changed = old
+        .iter()
+        .filter_map(|(key, old_value)| compare(key, old_value, new.get(key)?))
+        .collect();
+    (added, removed, changed)
+}
+
+fn type_change(path: &str, old: &TypeDetails, new: &TypeDetails) -> Option<TypeChange> {
+    let (added_fields, removed_fields, changed_fields) = compare_maps(
+        &type_fields(old),
+        &type_fields(new),
+        |name, old_ty, new_ty| {
+            (old_ty != new_ty).then(|| FieldChange {
+                name: name.to_string(),
+                old_ty: old_ty.clone(),
+                new_ty: new_ty.clone(),
+            })
+        },
+    );
+
+    let variants = |t: &TypeDetails| -> HashMap<String, ()> {
+        t.variants
+            .iter()
+            .flatten()
+            .map(|v| (v.name.clone(), ()))
+            .collect()
+    };
+    let (added_variants, removed_variants, _) =
+        compare_maps(&variants(old), &variants(new), |_, _, _| None::<()>);
+
+    let methods = |t: &TypeDetails| -> HashMap<String, String> {
+        t.inherent_methods
+            .iter()
+            .map(|m| (m.path.clone(), m.signature.clone()))
+            .collect()
+    };
+    let (added_methods, removed_methods, changed_methods) =
+        compare_maps(&methods(old), &methods(new), signature_change);
+
+    let change = TypeChange {
+        path: path.to_string(),
+        added_fields,
+        removed_fields,
+        changed_fields,
+        added_variants,
+        removed_variants,
+        added_methods,
+        removed_methods,
+        changed_methods,
+    };
+    let unchanged = TypeChange {
+        path: path.to_string(),
+        ..Default::default()
+    };
+    (change != unchanged).then_some(change)
+}
+
+fn trait_change(path: &str, old: &TraitDetails, new: &TraitDetails) -> Option<TraitChange> {
+    let methods = |t: &TraitDetails| -> HashMap<String, String> {
+        t.methods
+            .iter()
+            .map(|m| (m.name.clone(), m.signature.clone()))
+            .collect()
+    };
+    let (added_methods, removed_methods, changed_methods) =
+        compare_maps(&methods(old), &methods(new), signature_change);
+
+    let change = TraitChange {
+        path: path.to_string(),
+        added_methods,
+        removed_methods,
+        changed_methods,
+    };
+    let unchanged = TraitChange {
+        path: path.to_string(),
+        ..Default::default()
+    };
+    (change != unchanged).then_some(change)
+}
+
+fn signature_change(name: &str, old: &String, new: &String) -> Option<SignatureChange> {
+    (old != new).then(|| SignatureChange {
+        name: name.to_string(),
+        old_signature: old.clone(),
+        new_signature: new.clone(),
+    })
+}
+
+/// The type of every field of a struct, union or enum, keyed by field name
+/// (`Variant.field` for enum variants).
+fn type_fields(t: &TypeDetails) -> HashMap<String, String> {
+    let field_name = |f: &FieldInfo| f.name.clone().unwrap_or_else(|| f.index.to_string());
+
+    let mut fields: HashMap<String, String> = t
+        .fields
+        .iter()
+        .flatten()
+        .map(|f| (field_name(f), f.ty.clone()))
+        .collect();
+    for variant in t.variants.iter().flatten() {
+        for f in &variant.fields {
+            fields.insert(format!("{}.{}", variant.name, field_name(f)), f.ty.clone());
+        }
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bronzite_types::{MethodSummary, TypeKind, Visibility};
+
+    fn field(name: &str, ty: &str) -> FieldInfo {
+        FieldInfo {
+            name: Some(name.to_string()),
+            index: 0,
+            ty: ty.to_string(),
+            resolved_ty: None,
+            visibility: Visibility::Public,
+            docs: None,
+            attributes: Vec::new(),
+            offset: None,
+            size: None,
+            span: None,
+            defined_in_crate: None,
+        }
+    }
+
+    fn method(path: &str, signature: &str) -> MethodSummary {
+        MethodSummary {
+            name: path.rsplit("::").next().unwrap().to_string(),
+            path: path.to_string(),
+            signature: signature.to_string(),
+            visibility: Visibility::Public,
+            is_method: true,
+            is_unsafe: false,
+            is_const: false,
+            is_async: false,
+            attributes: Vec::new(),
+        }
+    }
+
+    fn snapshot(types: Vec<(&str, Vec<FieldInfo>, Vec<MethodSummary>)>) -> CrateTypeInfo {
+        let mut info = CrateTypeInfo::default();
+        for (path, fields, inherent_methods) in types {
+            let details = TypeDetails {
+                name: path.to_string(),
+                path: path.to_string(),
+                kind: TypeKind::Struct,
+                visibility: Visibility::Public,
+                is_local: true,
+                is_fundamental: false,
+                generics: Vec::new(),
+                variances: Vec::new(),
+                where_clause: None,
+                docs: None,
+                attributes: Vec::new(),
+                fields: Some(fields),
+                variants: None,
+                trait_impls: Vec::new(),
+                indirect_trait_impls: Vec::new(),
+                inherent_methods,
+                layout: None,
+                source: None,
+                span: None,
+                stable_id: String::new(),
+            };
+            info.types.insert(path.to_string(), details);
+        }
+        info
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = snapshot(vec![
+            (
+                "User",
+                vec![field("id", "u32"), field("name", "String")],
+                vec![method("User::id", "fn(&User) -> u32")],
+            ),
+            ("Session", Vec::new(), Vec::new()),
+        ]);
+        let new = snapshot(vec![
+            (
+                "User",
+                vec![field("id", "u64"), field("email", "String")],
+                vec![method("User::id", "fn(&User) -> u64")],
+            ),
+            ("Token", Vec::new(), Vec::new()),
+        ]);
+
+        assert!(diff(&old, &old).is_empty());
+
+        let changes = diff(&old, &new);
+        assert_eq!(changes.added_types, ["Token"]);
+        assert_eq!(changes.removed_types, ["Session"]);
+        assert_eq!(changes.changed_types.len(), 1);
+
+        let user = &changes.changed_types[0];
+        assert_eq!(user.path, "User");
+        assert_eq!(user.added_fields, ["email"]);
+        assert_eq!(user.removed_fields, ["name"]);
+        assert_eq!(user.changed_fields[0].name, "id");
+        assert_eq!(user.changed_fields[0].new_ty, "u64");
+        assert_eq!(user.changed_methods[0].name, "User::id");
+        assert!(user.added_methods.is_empty() && user.removed_methods.is_empty());
+    }
+
+    #[test]
+    fn test_diff_methods_of_separate_impls() {
+        // `impl Wrapper<u8> { fn get }` and `impl Wrapper<u16> { fn get }`
+        let old = snapshot(vec![(
+            "Wrapper",
+            Vec::new(),
+            vec![
+                method("Wrapper::<u8>::get", "fn(&Wrapper<u8>) -> u8"),
+                method("Wrapper::<u16>::get", "fn(&Wrapper<u16>) -> u16"),
+            ],
+        )]);
+        let new = snapshot(vec![(
+            "Wrapper",
+            Vec::new(),
+            vec![method("Wrapper::<u8>::get", "fn(&Wrapper<u8>) -> u8")],
+        )]);
+
+        let changes = diff(&old, &new);
+        let wrapper = &changes.changed_types[0];
+        assert_eq!(wrapper.removed_methods, ["Wrapper::<u16>::get"]);
+        assert!(wrapper.added_methods.is_empty() && wrapper.changed_methods.is_empty());
+    }
+}
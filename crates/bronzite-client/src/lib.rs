@@ -44,7 +44,7 @@ use std::process::{Command, Stdio};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
-use bronzite_types::{Query, QueryData, QueryResult, Request, Response};
+use bronzite_types::{QueryResult, Request, Response};
 
 #[cfg(unix)]
 use std::os::unix::net::UnixStream;
@@ -75,6 +75,59 @@ pub enum Error {
 
     #[error("Timeout waiting for daemon to start")]
     DaemonStartTimeout,
+
+    #[error(
+        "Crate '{requested}' not found (available: {}); note that crate names use '_' where the package name has '-'",
+        available.join(", ")
+    )]
+    CrateNotFound {
+        requested: String,
+        available: Vec<String>,
+    },
+
+    #[error("Invalid pattern '{pattern}': {reason}")]
+    InvalidPattern { pattern: String, reason: String },
+
+    #[error("Bronzite daemon is unavailable. {hint}")]
+    DaemonUnavailable {
+        hint: String,
+        #[source]
+        source: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Check a path pattern before sending it, since the daemon answers a
+    /// malformed one with no matches rather than an error.
+    pub(crate) fn check_pattern(pattern: &str) -> Result<()> {
+        bronzite_types::validate_pattern(pattern).map_err(|reason| Error::InvalidPattern {
+            pattern: pattern.to_string(),
+            reason,
+        })
+    }
+
+    /// Check a regex before sending it, so a typo doesn't cost a round trip
+    /// (or a compile) to the daemon.
+    #[cfg(feature = "regex")]
+    pub(crate) fn check_regex(regex: &str) -> Result<()> {
+        bronzite_types::validate_regex(regex).map_err(|reason| Error::InvalidPattern {
+            pattern: regex.to_string(),
+            reason,
+        })
+    }
+
+    /// Wrap an error from starting or connecting to the daemon with what to
+    /// check to fix it.
+    pub(crate) fn daemon_unavailable(source: Error) -> Self {
+        Error::DaemonUnavailable {
+            hint: format!(
+                "Make sure bronzite is installed (cargo install bronzite) \
+                 and so is its toolchain (rustup toolchain install {}).",
+                bronzite_types::TOOLCHAIN
+            ),
+            source: Box::new(source),
+        }
+    }
 }
 
 /// Result type for Bronzite operations.
@@ -93,6 +146,42 @@ pub struct BronziteClient {
     stream: UnixStream,
     #[cfg(windows)]
     stream: std::net::TcpStream,
+    skip_bodies: bool,
+    include_private: bool,
+    fresh: bool,
+    warnings: Vec<String>,
+}
+
+/// Version information reported by a running daemon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaemonVersion {
+    /// The daemon's crate version
+    pub daemon_version: String,
+    /// The protocol version the daemon speaks
+    pub protocol_version: u32,
+    /// The rustc toolchain the daemon compiles crates with
+    pub toolchain: String,
+}
+
+/// How a crate is compiled, see [`BronziteClient::get_crate_meta`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrateMeta {
+    /// The crate's edition, e.g. `2021`
+    pub edition: String,
+    /// The version of rustc analyzing the crate, e.g. `1.91.0-nightly (05f5a58e8 2025-08-19)`
+    pub rustc_version: String,
+}
+
+/// A type with everything the daemon knows about it, as returned by
+/// [`BronziteClient::get_type_full`].
+#[derive(Debug, Clone)]
+pub struct TypeFull {
+    pub details: bronzite_types::TypeDetails,
+    pub fields: Vec<bronzite_types::FieldInfo>,
+    pub trait_impls: Vec<bronzite_types::TraitImplDetails>,
+    pub inherent_impls: Vec<bronzite_types::InherentImplDetails>,
+    /// `None` when no layout could be computed, e.g. for generic types
+    pub layout: Option<bronzite_types::LayoutInfo>,
 }
 
 impl BronziteClient {
@@ -116,7 +205,13 @@ impl BronziteClient {
         }
 
         let stream = UnixStream::connect(&socket_path)?;
-        Ok(Self { stream })
+        Ok(Self {
+            stream,
+            skip_bodies: false,
+            include_private: true,
+            fresh: false,
+            warnings: Vec::new(),
+        })
     }
 
     /// Connect to the Bronzite daemon at a specific address (Windows).
@@ -132,17 +227,96 @@ impl BronziteClient {
         let port = 10000 + (hasher.finish() % 50000) as u16;
 
         let stream = std::net::TcpStream::connect(("127.0.0.1", port))?;
-        Ok(Self { stream })
+        Ok(Self {
+            stream,
+            skip_bodies: false,
+            include_private: true,
+            fresh: false,
+            warnings: Vec::new(),
+        })
+    }
+
+    /// Tell the daemon that this client never reads method bodies.
+    ///
+    /// Extraction then leaves `body_source`, `body_tokens` and default trait
+    /// method bodies as `None`, which is noticeably faster for large crates.
+    pub fn set_skip_bodies(&mut self, skip_bodies: bool) {
+        self.skip_bodies = skip_bodies;
+    }
+
+    /// Choose whether responses include non-`pub` items, fields and methods.
+    ///
+    /// On by default, as derive-style macros expanding inside the reflected
+    /// crate usually need private fields. Turn it off to see only the public
+    /// API surface.
+    pub fn set_include_private(&mut self, include_private: bool) {
+        self.include_private = include_private;
+    }
+
+    /// Make the daemon recompile the crate before answering each query,
+    /// rather than serving cached results.
+    ///
+    /// This guarantees results match the sources on disk, even right after
+    /// an edit, but every query pays for a compile. To refresh once and then
+    /// query the cache, see [`Crate::reflect_fresh`](crate::Crate::reflect_fresh).
+    pub fn set_fresh(&mut self, fresh: bool) {
+        self.fresh = fresh;
+    }
+
+    /// Take the extraction warnings collected from query responses since
+    /// the last call, e.g. for items that failed to extract and are missing
+    /// from a listing. [`get_crate_info`](Self::get_crate_info) returns its
+    /// warnings in the crate info instead.
+    pub fn take_warnings(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.warnings)
     }
 
     /// Send a query to the daemon and wait for a response.
     pub fn query(&mut self, crate_name: &str, query: Query) -> Result<QueryData> {
+        let id = self.send_request(crate_name, query)?;
+
+        // Read the response
+        let mut reader = BufReader::new(&self.stream);
+        let mut response_line = String::new();
+        reader.read_line(&mut response_line)?;
+
+        parse_response(&response_line, id)
+    }
+
+    /// Send any query to the daemon and return its raw response data.
+    ///
+    /// The typed methods on this client cover the queries bronzite ships
+    /// with; this is for queries they don't wrap yet, e.g. when trying out a
+    /// new query on a patched daemon. Unlike the typed methods, this doesn't
+    /// collect warnings for [`take_warnings`](Self::take_warnings); they stay
+    /// in the returned data.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use bronzite_client::{Query, QueryData};
+    ///
+    /// let data = client.query_raw("my_crate", Query::GetTypeAliases { pattern: "*".into() })?;
+    /// if let QueryData::TypeAliases { aliases, .. } = data {
+    ///     println!("{} aliases", aliases.len());
+    /// }
+    /// ```
+    pub fn query_raw(&mut self, crate_name: &str, query: Query) -> Result<QueryData> {
+        self.query(crate_name, query)
+    }
+
+    /// Send a request for `query` without waiting for the response, returning
+    /// the request id.
+    fn send_request(&mut self, crate_name: &str, query: Query) -> Result<u64> {
         let id = REQUEST_ID.fetch_add(1, Ordering::SeqCst);
 
         let request = Request {
             id,
             crate_name: crate_name.to_string(),
             query,
+            skip_bodies: self.skip_bodies,
+            include_private: self.include_private,
+            fresh: self.fresh,
         };
 
         // Send the request as a JSON line
@@ -150,26 +324,50 @@ impl BronziteClient {
         request_json.push('\n');
         self.stream.write_all(request_json.as_bytes())?;
         self.stream.flush()?;
+        Ok(id)
+    }
 
-        // Read the response
-        let mut reader = BufReader::new(&self.stream);
-        let mut response_line = String::new();
-        reader.read_line(&mut response_line)?;
-
-        let response: Response = serde_json::from_str(&response_line)?;
-
-        // Verify the response ID matches
-        if response.id != id {
-            return Err(Error::ResponseMismatch {
-                expected: id,
-                got: response.id,
-            });
-        }
+    /// Find types matching a pattern and pass them to `callback` one at a
+    /// time as the daemon sends them, instead of collecting the whole list.
+    /// Returns how many types were found.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut structs = 0;
+    /// client.stream_types("my_crate", "**", |summary| {
+    ///     if summary.kind == TypeKind::Struct {
+    ///         structs += 1;
+    ///     }
+    /// })?;
+    /// ```
+    pub fn stream_types(
+        &mut self,
+        crate_name: &str,
+        pattern: &str,
+        mut callback: impl FnMut(bronzite_types::TypeSummary),
+    ) -> Result<usize> {
+        Error::check_pattern(pattern)?;
+        let query = Query::StreamTypes {
+            pattern: pattern.to_string(),
+            options: MatchOptions::default(),
+        };
+        let id = self.send_request(crate_name, query)?;
 
-        // Extract the result
-        match response.result {
-            QueryResult::Success { data } => Ok(data),
-            QueryResult::Error { message } => Err(Error::DaemonError(message)),
+        // One reader for the whole stream, as it may buffer several responses
+        let mut reader = BufReader::new(&self.stream);
+        loop {
+            let mut response_line = String::new();
+            reader.read_line(&mut response_line)?;
+
+            match parse_response(&response_line, id)? {
+                QueryData::TypeItem(summary) => callback(summary),
+                QueryData::StreamEnd { count, warnings } => {
+                    self.warnings.extend(warnings);
+                    return Ok(count);
+                }
+                _ => return Err(Error::UnexpectedResponse),
+            }
         }
     }
 
@@ -182,6 +380,35 @@ impl BronziteClient {
         }
     }
 
+    /// Get the version information the daemon reports.
+    ///
+    /// A daemon whose `protocol_version` differs from
+    /// [`bronzite_types::PROTOCOL_VERSION`] was built from an incompatible
+    /// version of bronzite and should be restarted.
+    pub fn version(&mut self) -> Result<DaemonVersion> {
+        match self.query("", Query::Version)? {
+            QueryData::Version {
+                daemon_version,
+                protocol_version,
+                toolchain,
+            } => Ok(DaemonVersion {
+                daemon_version,
+                protocol_version,
+                toolchain,
+            }),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// List the crates the daemon knows about: the workspace members and
+    /// any crate it has cached.
+    pub fn list_crates(&mut self) -> Result<Vec<String>> {
+        match self.query("", Query::ListCachedCrates)? {
+            QueryData::Crates { crates } => Ok(crates),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
     /// Request the daemon to shut down.
     pub fn shutdown(&mut self) -> Result<()> {
         match self.query("", Query::Shutdown) {
@@ -194,7 +421,37 @@ impl BronziteClient {
     /// List all items in a crate.
     pub fn list_items(&mut self, crate_name: &str) -> Result<Vec<bronzite_types::ItemInfo>> {
         match self.query(crate_name, Query::ListItems)? {
-            QueryData::Items { items } => Ok(items),
+            QueryData::Items { items, warnings } => {
+                self.warnings.extend(warnings);
+                Ok(items)
+            }
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Get the edition a crate is compiled with and the rustc version that
+    /// analyzes it.
+    pub fn get_crate_meta(&mut self, crate_name: &str) -> Result<CrateMeta> {
+        match self.query(crate_name, Query::GetCrateMeta)? {
+            QueryData::CrateMeta {
+                edition,
+                rustc_version,
+            } => Ok(CrateMeta {
+                edition,
+                rustc_version,
+            }),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Get everything extracted from a crate in a single query.
+    ///
+    /// Like other queries, this leaves out non-public items when
+    /// [`set_include_private`](Self::set_include_private) is off. The result
+    /// can be saved and later compared with [`diff`](crate::diff::diff).
+    pub fn get_crate_info(&mut self, crate_name: &str) -> Result<bronzite_types::CrateTypeInfo> {
+        match self.query(crate_name, Query::GetCrateInfo)? {
+            QueryData::CrateInfo(info) => Ok(*info),
             _ => Err(Error::UnexpectedResponse),
         }
     }
@@ -252,6 +509,156 @@ impl BronziteClient {
         }
     }
 
+    /// Check if a generic type instantiated with `type_args` implements a
+    /// trait, e.g. `check_impl_for("my_crate", "Wrapper", &["String"], "Clone")`.
+    ///
+    /// Unlike [`check_impl`](Self::check_impl), this asks the trait solver,
+    /// so conditional impls only count when their where clauses hold for
+    /// the given arguments.
+    pub fn check_impl_for(
+        &mut self,
+        crate_name: &str,
+        type_path: &str,
+        type_args: &[&str],
+        trait_path: &str,
+    ) -> Result<(bool, Option<bronzite_types::TraitImplDetails>)> {
+        let query = Query::CheckImplFor {
+            type_path: type_path.to_string(),
+            type_args: type_args.iter().map(|arg| arg.to_string()).collect(),
+            trait_path: trait_path.to_string(),
+        };
+
+        match self.query(crate_name, query)? {
+            QueryData::ImplCheck {
+                implements,
+                impl_info,
+            } => Ok((implements, impl_info)),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Check if a generic type instantiated with `args` is `Sized`, e.g.
+    /// `check_sized("my_crate", "Wrapper", &["str"])`.
+    pub fn check_sized(
+        &mut self,
+        crate_name: &str,
+        type_path: &str,
+        args: &[&str],
+    ) -> Result<bool> {
+        let query = Query::CheckSized {
+            type_path: type_path.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+        };
+
+        match self.query(crate_name, query)? {
+            QueryData::SizedCheck { is_sized } => Ok(is_sized),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Get the source text a span from an earlier query covers.
+    ///
+    /// The daemon doesn't keep the crate's sources, so this recompiles the
+    /// crate the first time a given span is asked for.
+    pub fn span_text(
+        &mut self,
+        crate_name: &str,
+        span: &bronzite_types::SpanInfo,
+    ) -> Result<String> {
+        let query = Query::GetSpanText { span: span.clone() };
+
+        match self.query(crate_name, query)? {
+            QueryData::SpanText { text } => Ok(text),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Check if a value of type `from` can be converted into `into` via
+    /// `Into`, e.g. `check_convertible("my_crate", "&str", "MyName")`.
+    ///
+    /// Types are written as Rust source. The impl details are returned when
+    /// the crate itself provides the `From` impl.
+    pub fn check_convertible(
+        &mut self,
+        crate_name: &str,
+        from: &str,
+        into: &str,
+    ) -> Result<(bool, Option<bronzite_types::TraitImplDetails>)> {
+        let query = Query::CheckConvertible {
+            from: from.to_string(),
+            into: into.to_string(),
+        };
+
+        match self.query(crate_name, query)? {
+            QueryData::ConvertibleCheck {
+                convertible,
+                impl_info,
+            } => Ok((convertible, impl_info)),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Get the signature of a trait method as a type's impl provides it, with
+    /// `Self` and associated types resolved, e.g. `fn(&User) -> u64` for
+    /// `impl_method_signature("my_crate", "User", "HasId", "id")`.
+    ///
+    /// Default methods the impl doesn't override are resolved too.
+    pub fn impl_method_signature(
+        &mut self,
+        crate_name: &str,
+        type_path: &str,
+        trait_path: &str,
+        method_name: &str,
+    ) -> Result<bronzite_types::FunctionSignature> {
+        let query = Query::GetImplMethodSignature {
+            type_path: type_path.to_string(),
+            trait_path: trait_path.to_string(),
+            method_name: method_name.to_string(),
+        };
+
+        match self.query(crate_name, query)? {
+            QueryData::MethodSignature(signature) => Ok(signature),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Find the inherent and trait impl methods named `method_name` on a
+    /// type. More than one candidate means a call may be ambiguous.
+    pub fn resolve_method(
+        &mut self,
+        crate_name: &str,
+        type_path: &str,
+        method_name: &str,
+    ) -> Result<Vec<bronzite_types::MethodCandidate>> {
+        let query = Query::ResolveMethod {
+            type_path: type_path.to_string(),
+            method_name: method_name.to_string(),
+        };
+
+        match self.query(crate_name, query)? {
+            QueryData::MethodCandidates { candidates } => Ok(candidates),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Break a type string (e.g. a field type) down into its structure:
+    /// tuple elements, array element and length, reference targets, and
+    /// so on.
+    pub fn describe_type(
+        &mut self,
+        crate_name: &str,
+        ty: &str,
+    ) -> Result<bronzite_types::TypeStructure> {
+        let query = Query::DescribeType {
+            type_path: ty.to_string(),
+        };
+
+        match self.query(crate_name, query)? {
+            QueryData::TypeStructure(structure) => Ok(structure),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
     /// Get all fields of a struct.
     pub fn get_fields(
         &mut self,
@@ -268,6 +675,28 @@ impl BronziteClient {
         }
     }
 
+    /// Get a field of an enum variant, e.g.
+    /// `get_variant_field("my_crate", "Shape", "Circle", "radius")`. Fields
+    /// of tuple variants are named by index (`"0"`).
+    pub fn get_variant_field(
+        &mut self,
+        crate_name: &str,
+        enum_path: &str,
+        variant: &str,
+        field: &str,
+    ) -> Result<bronzite_types::FieldInfo> {
+        let query = Query::GetVariantField {
+            enum_path: enum_path.to_string(),
+            variant: variant.to_string(),
+            field: field.to_string(),
+        };
+
+        match self.query(crate_name, query)? {
+            QueryData::Field(field) => Ok(field),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
     /// Get detailed information about a type.
     pub fn get_type(
         &mut self,
@@ -284,10 +713,117 @@ impl BronziteClient {
         }
     }
 
+    /// Get a type along with its fields, trait and inherent impls and layout
+    /// in a single query.
+    pub fn get_type_full(&mut self, crate_name: &str, type_path: &str) -> Result<TypeFull> {
+        let query = Query::GetTypeFull {
+            path: type_path.to_string(),
+        };
+
+        match self.query(crate_name, query)? {
+            QueryData::TypeFull {
+                details,
+                fields,
+                trait_impls,
+                inherent_impls,
+                layout,
+            } => Ok(TypeFull {
+                details,
+                fields,
+                trait_impls,
+                inherent_impls,
+                layout,
+            }),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Get a type by the `stable_id` reported in its [`TypeDetails`] or
+    /// [`ItemInfo`](bronzite_types::ItemInfo).
+    ///
+    /// [`TypeDetails`]: bronzite_types::TypeDetails
+    pub fn get_type_by_id(
+        &mut self,
+        crate_name: &str,
+        id: &str,
+    ) -> Result<bronzite_types::TypeDetails> {
+        let query = Query::GetTypeById { id: id.to_string() };
+
+        match self.query(crate_name, query)? {
+            QueryData::TypeInfo(info) => Ok(info),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Get detailed information about a generic type instantiated with
+    /// concrete arguments, e.g. `Wrapper` with `["u32"]`.
+    ///
+    /// Field types and layout describe the instantiation. Arguments are
+    /// written as Rust types; lifetimes can be omitted.
+    pub fn get_type_instantiated(
+        &mut self,
+        crate_name: &str,
+        type_path: &str,
+        args: &[&str],
+    ) -> Result<bronzite_types::TypeDetails> {
+        let query = Query::GetTypeInstantiated {
+            type_path: type_path.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+        };
+
+        match self.query(crate_name, query)? {
+            QueryData::TypeInfo(info) => Ok(info),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
     /// Get all traits defined in a crate.
     pub fn get_traits(&mut self, crate_name: &str) -> Result<Vec<bronzite_types::TraitInfo>> {
         match self.query(crate_name, Query::GetTraits)? {
-            QueryData::Traits { traits } => Ok(traits),
+            QueryData::Traits { traits, warnings } => {
+                self.warnings.extend(warnings);
+                Ok(traits)
+            }
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Get all traits with `supertrait` among their direct or indirect
+    /// supertraits.
+    pub fn traits_with_supertrait(
+        &mut self,
+        crate_name: &str,
+        supertrait: &str,
+    ) -> Result<Vec<bronzite_types::TraitInfo>> {
+        let query = Query::FindTraitsBySupertrait {
+            supertrait: supertrait.to_string(),
+        };
+
+        match self.query(crate_name, query)? {
+            QueryData::Traits { traits, warnings } => {
+                self.warnings.extend(warnings);
+                Ok(traits)
+            }
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Get the types with a field whose type mentions `type_path`, e.g.
+    /// `LegacyId` in `id: LegacyId` or `ids: Vec<LegacyId>`.
+    pub fn find_types_using_type(
+        &mut self,
+        crate_name: &str,
+        type_path: &str,
+    ) -> Result<Vec<bronzite_types::TypeUsage>> {
+        let query = Query::FindTypesUsingType {
+            type_path: type_path.to_string(),
+        };
+
+        match self.query(crate_name, query)? {
+            QueryData::TypeUsages { usages, warnings } => {
+                self.warnings.extend(warnings);
+                Ok(usages)
+            }
             _ => Err(Error::UnexpectedResponse),
         }
     }
@@ -308,18 +844,193 @@ impl BronziteClient {
         }
     }
 
+    /// Get a trait alias (`trait Shape = Area + Debug;`).
+    pub fn get_trait_alias(
+        &mut self,
+        crate_name: &str,
+        path: &str,
+    ) -> Result<bronzite_types::TraitAliasInfo> {
+        let query = Query::GetTraitAlias {
+            path: path.to_string(),
+        };
+
+        match self.query(crate_name, query)? {
+            QueryData::TraitAlias(alias) => Ok(alias),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
     /// Find types matching a pattern.
     pub fn find_types(
         &mut self,
         crate_name: &str,
         pattern: &str,
     ) -> Result<Vec<bronzite_types::TypeSummary>> {
+        self.find_types_with(crate_name, pattern, MatchOptions::default())
+    }
+
+    /// Find types matching a pattern, with relaxed matching such as
+    /// ignoring case.
+    pub fn find_types_with(
+        &mut self,
+        crate_name: &str,
+        pattern: &str,
+        options: MatchOptions,
+    ) -> Result<Vec<bronzite_types::TypeSummary>> {
+        Error::check_pattern(pattern)?;
         let query = Query::FindTypes {
             pattern: pattern.to_string(),
+            options,
+        };
+
+        match self.query(crate_name, query)? {
+            QueryData::Types { types, warnings } => {
+                self.warnings.extend(warnings);
+                Ok(types)
+            }
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Find types whose full path matches a regular expression.
+    ///
+    /// An invalid regex is reported as [`Error::InvalidPattern`] without
+    /// asking the daemon.
+    #[cfg(feature = "regex")]
+    pub fn find_types_regex(
+        &mut self,
+        crate_name: &str,
+        regex: &str,
+    ) -> Result<Vec<bronzite_types::TypeSummary>> {
+        Error::check_regex(regex)?;
+        let query = Query::FindTypesRegex {
+            regex: regex.to_string(),
+        };
+
+        match self.query(crate_name, query)? {
+            QueryData::Types { types, warnings } => {
+                self.warnings.extend(warnings);
+                Ok(types)
+            }
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Get the crate's module tree.
+    pub fn get_module_tree(&mut self, crate_name: &str) -> Result<bronzite_types::ModuleTree> {
+        match self.query(crate_name, Query::GetModuleTree)? {
+            QueryData::ModuleTree(tree) => Ok(tree),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Get detailed information about a free function.
+    pub fn get_function(
+        &mut self,
+        crate_name: &str,
+        path: &str,
+    ) -> Result<bronzite_types::FunctionDetails> {
+        let query = Query::GetFunction {
+            path: path.to_string(),
+        };
+
+        match self.query(crate_name, query)? {
+            QueryData::FunctionDetails(details) => Ok(details),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Get a `macro_rules!` or procedural macro defined in the crate.
+    pub fn get_macro(
+        &mut self,
+        crate_name: &str,
+        path: &str,
+    ) -> Result<bronzite_types::MacroDetails> {
+        let query = Query::GetMacro {
+            path: path.to_string(),
+        };
+
+        match self.query(crate_name, query)? {
+            QueryData::MacroDetails(details) => Ok(details),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Get a top-level `const` or `static` item, with a const's evaluated
+    /// value.
+    pub fn get_const(
+        &mut self,
+        crate_name: &str,
+        path: &str,
+    ) -> Result<bronzite_types::ConstDetails> {
+        let query = Query::GetConst {
+            path: path.to_string(),
+        };
+
+        match self.query(crate_name, query)? {
+            QueryData::ConstDetails(details) => Ok(details),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Find free functions matching a pattern.
+    pub fn find_functions(
+        &mut self,
+        crate_name: &str,
+        pattern: &str,
+    ) -> Result<Vec<bronzite_types::FunctionDetails>> {
+        self.find_functions_with(crate_name, pattern, MatchOptions::default())
+    }
+
+    /// Find free functions matching a pattern, with relaxed matching such
+    /// as ignoring case.
+    pub fn find_functions_with(
+        &mut self,
+        crate_name: &str,
+        pattern: &str,
+        options: MatchOptions,
+    ) -> Result<Vec<bronzite_types::FunctionDetails>> {
+        Error::check_pattern(pattern)?;
+        let query = Query::FindFunctions {
+            pattern: pattern.to_string(),
+            options,
+        };
+
+        match self.query(crate_name, query)? {
+            QueryData::Functions {
+                functions,
+                warnings,
+            } => {
+                self.warnings.extend(warnings);
+                Ok(functions)
+            }
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Find impl methods, across all types in the crate, whose path matches
+    /// a pattern (e.g. `*::handle_*`). Trait impl methods, whose paths look
+    /// like `<User as Greet>::hello`, are only searched with
+    /// `include_trait_impls`.
+    ///
+    /// Each summary carries the method's attributes, so methods tagged with
+    /// e.g. `#[command]` can be picked out of the result.
+    pub fn find_methods(
+        &mut self,
+        crate_name: &str,
+        pattern: &str,
+        include_trait_impls: bool,
+    ) -> Result<Vec<bronzite_types::MethodSummary>> {
+        let query = Query::FindMethods {
+            pattern: pattern.to_string(),
+            include_trait_impls,
         };
 
         match self.query(crate_name, query)? {
-            QueryData::Types { types } => Ok(types),
+            QueryData::Methods { methods, warnings } => {
+                self.warnings.extend(warnings);
+                Ok(methods)
+            }
             _ => Err(Error::UnexpectedResponse),
         }
     }
@@ -330,6 +1041,21 @@ impl BronziteClient {
         crate_name: &str,
         path: &str,
     ) -> Result<(String, String, Vec<String>)> {
+        let (original, resolved, chain, _) = self.resolve_alias_with_generics(crate_name, path)?;
+        Ok((original, resolved, chain))
+    }
+
+    /// Resolve a type alias, also returning the generic parameters it declares.
+    pub fn resolve_alias_with_generics(
+        &mut self,
+        crate_name: &str,
+        path: &str,
+    ) -> Result<(
+        String,
+        String,
+        Vec<String>,
+        Vec<bronzite_types::GenericParam>,
+    )> {
         let query = Query::ResolveAlias {
             path: path.to_string(),
         };
@@ -339,7 +1065,29 @@ impl BronziteClient {
                 original,
                 resolved,
                 chain,
-            } => Ok((original, resolved, chain)),
+                generics,
+            } => Ok((original, resolved, chain, generics)),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Get every type alias whose path matches a pattern, with the type it
+    /// resolves to.
+    pub fn get_type_aliases(
+        &mut self,
+        crate_name: &str,
+        pattern: &str,
+    ) -> Result<Vec<bronzite_types::TypeAliasInfo>> {
+        Error::check_pattern(pattern)?;
+        let query = Query::GetTypeAliases {
+            pattern: pattern.to_string(),
+        };
+
+        match self.query(crate_name, query)? {
+            QueryData::TypeAliases { aliases, warnings } => {
+                self.warnings.extend(warnings);
+                Ok(aliases)
+            }
             _ => Err(Error::UnexpectedResponse),
         }
     }
@@ -360,12 +1108,32 @@ impl BronziteClient {
         }
     }
 
-    /// Get memory layout information for a type.
+    /// Get every implementation of a trait in the crate, across all types.
+    ///
+    /// Unlike [`get_implementors`](Self::get_implementors), this returns the
+    /// impl blocks themselves, with their methods and associated items.
+    pub fn all_impls_of_trait(
+        &mut self,
+        crate_name: &str,
+        trait_path: &str,
+    ) -> Result<Vec<bronzite_types::TraitImplDetails>> {
+        let query = Query::GetAllImplsOfTrait {
+            trait_path: trait_path.to_string(),
+        };
+
+        match self.query(crate_name, query)? {
+            QueryData::TraitImpls { impls } => Ok(impls),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Get memory layout information for a type, or why it has no fixed
+    /// layout.
     pub fn get_layout(
         &mut self,
         crate_name: &str,
         type_path: &str,
-    ) -> Result<bronzite_types::LayoutInfo> {
+    ) -> Result<bronzite_types::TypeLayout> {
         let query = Query::GetLayout {
             type_path: type_path.to_string(),
         };
@@ -377,6 +1145,25 @@ impl BronziteClient {
     }
 }
 
+/// Parse a response line, checking it answers request `id`.
+fn parse_response(line: &str, id: u64) -> Result<QueryData> {
+    let response: Response = serde_json::from_str(line)?;
+
+    // Verify the response ID matches
+    if response.id != id {
+        return Err(Error::ResponseMismatch {
+            expected: id,
+            got: response.id,
+        });
+    }
+
+    // Extract the result
+    match response.result {
+        QueryResult::Success { data } => Ok(data),
+        QueryResult::Error { message } => Err(Error::DaemonError(message)),
+    }
+}
+
 /// Try to connect to an existing daemon, or return an error if not running.
 ///
 /// This is the recommended entry point for proc-macros, as it provides
@@ -390,6 +1177,33 @@ pub fn connect_for_workspace(workspace_root: &std::path::Path) -> Result<Bronzit
     BronziteClient::connect_for_workspace(workspace_root)
 }
 
+/// Find the root of the workspace being compiled.
+///
+/// Starts from `CARGO_MANIFEST_DIR`, which cargo sets while building a crate
+/// (and so while its proc-macros run), and walks up to the nearest manifest
+/// declaring a `[workspace]`. A crate outside any workspace is its own root.
+/// Returns `None` when `CARGO_MANIFEST_DIR` isn't set.
+pub fn find_workspace_root() -> Option<PathBuf> {
+    let manifest_dir = PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR")?);
+    Some(workspace_root_of(&manifest_dir))
+}
+
+fn workspace_root_of(manifest_dir: &std::path::Path) -> PathBuf {
+    let manifest_dir = std::fs::canonicalize(manifest_dir).unwrap_or(manifest_dir.to_path_buf());
+
+    manifest_dir
+        .ancestors()
+        .find(|dir| {
+            std::fs::read_to_string(dir.join("Cargo.toml")).is_ok_and(|manifest| {
+                manifest
+                    .lines()
+                    .any(|line| line.trim_start().starts_with("[workspace]"))
+            })
+        })
+        .unwrap_or(&manifest_dir)
+        .to_path_buf()
+}
+
 /// Check if the daemon is running and responding.
 pub fn is_daemon_running() -> bool {
     is_daemon_running_at(&bronzite_types::default_socket_path())
@@ -410,6 +1224,9 @@ pub fn is_daemon_running_at(socket_path: &PathBuf) -> bool {
                 id: 0,
                 crate_name: String::new(),
                 query: Query::Ping,
+                skip_bodies: false,
+                include_private: true,
+                fresh: false,
             };
 
             if let Ok(json) = serde_json::to_string(&request) {
@@ -478,10 +1295,35 @@ pub fn ensure_daemon_running_with_timeout(
     manifest_path: Option<&std::path::Path>,
     timeout: Duration,
 ) -> Result<()> {
-    let socket_path = bronzite_types::default_socket_path();
+    ensure_daemon_running_at(
+        &bronzite_types::default_socket_path(),
+        manifest_path,
+        timeout,
+    )
+}
+
+/// Ensure the daemon serving `workspace_root` is running, starting it if
+/// necessary.
+///
+/// Each workspace gets its own daemon, listening on
+/// [`bronzite_types::socket_path_for_workspace`], so crates from unrelated
+/// workspaces never share a cache. Connect to it with
+/// [`connect_for_workspace`].
+pub fn ensure_daemon_running_for_workspace(workspace_root: &std::path::Path) -> Result<()> {
+    ensure_daemon_running_at(
+        &bronzite_types::socket_path_for_workspace(workspace_root),
+        Some(workspace_root),
+        DEFAULT_DAEMON_TIMEOUT,
+    )
+}
 
+fn ensure_daemon_running_at(
+    socket_path: &PathBuf,
+    manifest_path: Option<&std::path::Path>,
+    timeout: Duration,
+) -> Result<()> {
     // Check if daemon is already running
-    if is_daemon_running_at(&socket_path) {
+    if is_daemon_running_at(socket_path) {
         return Ok(());
     }
 
@@ -494,6 +1336,7 @@ pub fn ensure_daemon_running_with_timeout(
     cmd.arg("--ensure-timeout")
         .arg(timeout.as_secs().to_string());
 
+    cmd.arg("--socket").arg(socket_path);
     if let Some(path) = manifest_path {
         cmd.arg("--manifest-path").arg(path);
     }
@@ -515,7 +1358,7 @@ pub fn ensure_daemon_running_with_timeout(
     }
 
     // Verify daemon is now running
-    if !is_daemon_running_at(&socket_path) {
+    if !is_daemon_running_at(socket_path) {
         return Err(Error::DaemonStartTimeout);
     }
 
@@ -582,6 +1425,80 @@ pub fn connect_or_start(manifest_path: Option<&std::path::Path>) -> Result<Bronz
     connect()
 }
 
+// ============================================================================
+// Body Analysis
+// ============================================================================
+
+/// Collect the functions and methods called in a body, in order of first
+/// appearance and without duplicates.
+///
+/// Function calls are reported by their path as written (e.g. `Vec::new`),
+/// method calls by the method name alone. This is best-effort: calls through
+/// closures, function pointers or trait objects, and calls inside macro
+/// invocations, can't be told apart from other expressions and are missed.
+pub fn called_paths(tokens: &[Token]) -> Vec<String> {
+    fn record(call: String, calls: &mut Vec<String>) {
+        if !calls.contains(&call) {
+            calls.push(call);
+        }
+    }
+
+    fn walk(token: &Token, calls: &mut Vec<String>) {
+        match token {
+            Token::FnCall { path, args } => {
+                record(path.join("::"), calls);
+                args.iter().for_each(|t| walk(t, calls));
+            }
+            Token::MethodCall {
+                receiver,
+                method,
+                args,
+            } => {
+                // The receiver is evaluated first
+                walk(receiver, calls);
+                record(method.clone(), calls);
+                args.iter().for_each(|t| walk(t, calls));
+            }
+            Token::Group { tokens, .. } => tokens.iter().for_each(|t| walk(t, calls)),
+            Token::Block { stmts } => stmts.iter().for_each(|t| walk(t, calls)),
+            Token::FieldAccess { base, .. } => walk(base, calls),
+            Token::BinOp { lhs, rhs, .. } => {
+                walk(lhs, calls);
+                walk(rhs, calls);
+            }
+            Token::UnaryOp { expr, .. } => walk(expr, calls),
+            Token::If {
+                cond,
+                then_branch,
+                else_branch,
+            } => {
+                walk(cond, calls);
+                then_branch.iter().for_each(|t| walk(t, calls));
+                else_branch.iter().flatten().for_each(|t| walk(t, calls));
+            }
+            Token::Match { expr, arms } => {
+                walk(expr, calls);
+                for arm in arms {
+                    arm.body.iter().for_each(|t| walk(t, calls));
+                }
+            }
+            Token::Let { init, .. } => init.iter().for_each(|t| walk(t, calls)),
+            Token::Return { expr } => expr.iter().for_each(|t| walk(t, calls)),
+            Token::Closure { body, .. } => walk(body, calls),
+            Token::Ident { .. }
+            | Token::Literal { .. }
+            | Token::Punct { .. }
+            | Token::Keyword { .. }
+            | Token::Path { .. }
+            | Token::Raw { .. } => {}
+        }
+    }
+
+    let mut calls = Vec::new();
+    tokens.iter().for_each(|t| walk(t, &mut calls));
+    calls
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -601,6 +1518,360 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_daemon_unavailable() {
+        use std::error::Error as _;
+
+        let err = Error::daemon_unavailable(Error::DaemonStartTimeout);
+        let message = err.to_string();
+        // The cause is left to the `source` chain rather than repeated
+        assert!(!message.contains("Timeout waiting for daemon to start"));
+        assert!(message.contains(&format!(
+            "rustup toolchain install {}",
+            bronzite_types::TOOLCHAIN
+        )));
+        assert_eq!(
+            err.source().map(|s| s.to_string()).as_deref(),
+            Some("Timeout waiting for daemon to start")
+        );
+    }
+
+    #[test]
+    fn test_workspace_root_of() {
+        let manifest_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+        let root = workspace_root_of(manifest_dir);
+        // bronzite-client lives two levels below the workspace manifest
+        let expected = std::fs::canonicalize(manifest_dir.join("../..")).unwrap();
+        assert_eq!(root, expected);
+        assert_eq!(workspace_root_of(&root), root);
+    }
+
+    #[test]
+    fn test_called_paths() {
+        let ident = |name: &str| Token::Ident {
+            name: name.to_string(),
+        };
+        let call = |path: &[&str], args: Vec<Token>| Token::FnCall {
+            path: path.iter().map(|s| s.to_string()).collect(),
+            args,
+        };
+
+        // let v = Vec::new(); if v.is_empty() { log(v.len()) } else { Vec::new() }
+        let tokens = vec![
+            Token::Let {
+                pattern: "v".to_string(),
+                ty: None,
+                init: Some(Box::new(call(&["Vec", "new"], Vec::new()))),
+            },
+            Token::If {
+                cond: Box::new(Token::MethodCall {
+                    receiver: Box::new(ident("v")),
+                    method: "is_empty".to_string(),
+                    args: Vec::new(),
+                }),
+                then_branch: vec![call(
+                    &["log"],
+                    vec![Token::MethodCall {
+                        receiver: Box::new(ident("v")),
+                        method: "len".to_string(),
+                        args: Vec::new(),
+                    }],
+                )],
+                else_branch: Some(vec![call(&["Vec", "new"], Vec::new())]),
+            },
+        ];
+
+        assert_eq!(
+            called_paths(&tokens),
+            ["Vec::new", "is_empty", "log", "len"]
+        );
+        assert!(called_paths(&[ident("x")]).is_empty());
+    }
+
+    #[test]
+    fn test_lookup_type_through_alias() {
+        use reflection::lookup_type_through_alias;
+
+        // `type Owner = Bar; type UserId = u64;`
+        let lookup = |path: &str| {
+            let mut calls: Vec<String> = Vec::new();
+            let found = lookup_type_through_alias(
+                &mut calls,
+                path,
+                |calls, path| {
+                    calls.push(format!("type {}", path));
+                    match path {
+                        "Bar" => Ok(path.to_string()),
+                        _ => Err(format!("Type '{}' not found", path)),
+                    }
+                },
+                |calls, path| {
+                    calls.push(format!("alias {}", path));
+                    match path {
+                        "Owner" => Ok("Bar".to_string()),
+                        "UserId" => Ok("u64".to_string()),
+                        _ => Err(format!("Type alias '{}' not found", path)),
+                    }
+                },
+            );
+            (found, calls)
+        };
+
+        // A type is found without looking for an alias
+        assert_eq!(
+            lookup("Bar"),
+            (Ok("Bar".to_string()), vec!["type Bar".into()])
+        );
+
+        let (found, calls) = lookup("Owner");
+        assert_eq!(found, Ok("Bar".to_string()));
+        assert_eq!(calls, ["type Owner", "alias Owner", "type Bar"]);
+
+        // Neither a type nor an alias: the type lookup's error is kept
+        assert_eq!(lookup("u32").0, Err("Type 'u32' not found".to_string()));
+        // An alias of a type that isn't in the crate
+        assert_eq!(lookup("UserId").0, Err("Type 'u64' not found".to_string()));
+    }
+
+    /// Serve requests on a fresh socket, answering each with `answer`,
+    /// until the client hangs up.
+    #[cfg(unix)]
+    fn stub_daemon(
+        name: &str,
+        answer: impl Fn(Query) -> QueryResult + Send + 'static,
+    ) -> (PathBuf, std::thread::JoinHandle<()>) {
+        let socket_path = std::env::temp_dir().join(format!(
+            "bronzite-{}-test-{}.sock",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&socket_path);
+        let listener = std::os::unix::net::UnixListener::bind(&socket_path).unwrap();
+
+        let daemon = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(&stream);
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 0 {
+                let request: Request = serde_json::from_str(&line).unwrap();
+                let response = Response {
+                    id: request.id,
+                    result: answer(request.query),
+                };
+                let json = serde_json::to_string(&response).unwrap();
+                (&stream)
+                    .write_all(format!("{}\n", json).as_bytes())
+                    .unwrap();
+                line.clear();
+            }
+        });
+        (socket_path, daemon)
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_take_warnings() {
+        // Answer with a listing that lost an item
+        let (socket_path, daemon) = stub_daemon("warnings", |_| QueryResult::Success {
+            data: QueryData::Items {
+                items: Vec::new(),
+                warnings: vec!["failed to extract `Broken`".to_string()],
+            },
+        });
+
+        let mut client = BronziteClient::connect_to(socket_path.clone()).unwrap();
+        assert!(client.list_items("demo").unwrap().is_empty());
+        assert_eq!(client.take_warnings(), ["failed to extract `Broken`"]);
+        assert!(client.take_warnings().is_empty());
+
+        // Loading the crate lists its items too
+        let client = std::sync::Arc::new(std::sync::Mutex::new(client));
+        let krate = reflection::Crate::with_client("demo", client).unwrap();
+        assert_eq!(krate.warnings(), ["failed to extract `Broken`"]);
+        assert!(krate.warnings().is_empty());
+
+        drop(krate);
+        daemon.join().unwrap();
+        let _ = std::fs::remove_file(&socket_path);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_effective_methods_of_foreign_trait() {
+        use bronzite_types::{
+            FunctionSignature, MethodDetails, TraitImplDetails, TypeDetails, TypeKind, Visibility,
+        };
+
+        // `impl std::fmt::Display for Label { fn fmt(..) }`
+        let fmt = MethodDetails {
+            name: "fmt".to_string(),
+            path: "<Label as std::fmt::Display>::fmt".to_string(),
+            signature: String::new(),
+            parsed_signature: FunctionSignature {
+                receiver: None,
+                params: Vec::new(),
+                return_ty: None,
+                generics: Vec::new(),
+                where_clause: None,
+                where_predicates: Vec::new(),
+            },
+            visibility: Visibility::Public,
+            is_method: true,
+            overrides_default: false,
+            has_body: true,
+            body_source: None,
+            body_tokens: None,
+            is_unsafe: false,
+            is_const: false,
+            is_async: false,
+            docs: None,
+            attributes: Vec::new(),
+            span: None,
+        };
+        let display = TraitImplDetails {
+            self_ty: "Label".to_string(),
+            self_ty_args: Vec::new(),
+            trait_path: "std::fmt::Display".to_string(),
+            trait_args: Vec::new(),
+            generics: Vec::new(),
+            where_clause: None,
+            is_negative: false,
+            is_unsafe: false,
+            methods: vec![fmt],
+            assoc_types: Vec::new(),
+            assoc_consts: Vec::new(),
+            source: None,
+            span: None,
+            docs: None,
+            attributes: Vec::new(),
+        };
+        let label = TypeDetails {
+            name: "Label".to_string(),
+            path: "Label".to_string(),
+            kind: TypeKind::Struct,
+            visibility: Visibility::Public,
+            is_local: true,
+            is_fundamental: false,
+            generics: Vec::new(),
+            variances: Vec::new(),
+            where_clause: None,
+            docs: None,
+            attributes: Vec::new(),
+            fields: Some(Vec::new()),
+            variants: None,
+            trait_impls: vec![display.trait_path.clone()],
+            indirect_trait_impls: Vec::new(),
+            inherent_methods: Vec::new(),
+            layout: None,
+            source: None,
+            span: None,
+            stable_id: String::new(),
+        };
+
+        let (socket_path, daemon) = stub_daemon("foreign-trait", move |query| match query {
+            Query::ListItems => QueryResult::Success {
+                data: QueryData::Items {
+                    items: Vec::new(),
+                    warnings: Vec::new(),
+                },
+            },
+            Query::GetType { .. } => QueryResult::Success {
+                data: QueryData::TypeInfo(label.clone()),
+            },
+            Query::GetTraitImpls { .. } => QueryResult::Success {
+                data: QueryData::TraitImpls {
+                    impls: vec![display.clone()],
+                },
+            },
+            // The daemon only knows the crate's own traits
+            Query::GetTrait { path } => QueryResult::Error {
+                message: format!("Trait '{}' not found", path),
+            },
+            other => panic!("unexpected query: {:?}", other),
+        });
+
+        let client = BronziteClient::connect_to(socket_path.clone()).unwrap();
+        let client = std::sync::Arc::new(std::sync::Mutex::new(client));
+        {
+            let krate = reflection::Crate::with_client("demo", client).unwrap();
+            let impls = krate.get_struct("Label").unwrap().trait_impls().unwrap();
+            let methods = impls[0].effective_methods().unwrap();
+            let names: Vec<(&str, bool)> = methods
+                .iter()
+                .map(|(m, own)| (m.name.as_str(), *own))
+                .collect();
+            assert_eq!(names, [("fmt", true)]);
+        }
+
+        // Every handle is gone, which closes the connection
+        daemon.join().unwrap();
+        let _ = std::fs::remove_file(&socket_path);
+    }
+
+    #[cfg(all(unix, feature = "regex"))]
+    #[test]
+    fn test_find_types_regex() {
+        use bronzite_types::{TypeKind, TypeSummary};
+
+        let (socket_path, daemon) = stub_daemon("regex", |query| match query {
+            Query::ListItems => QueryResult::Success {
+                data: QueryData::Items {
+                    items: Vec::new(),
+                    warnings: Vec::new(),
+                },
+            },
+            Query::FindTypesRegex { regex } => {
+                assert_eq!(regex, "^events::(Key|Mouse)Event$");
+                QueryResult::Success {
+                    data: QueryData::Types {
+                        types: vec![TypeSummary {
+                            name: "KeyEvent".to_string(),
+                            path: "events::KeyEvent".to_string(),
+                            kind: TypeKind::Struct,
+                            generics: Vec::new(),
+                            crate_name: None,
+                        }],
+                        warnings: Vec::new(),
+                    },
+                }
+            }
+            other => panic!("unexpected query: {:?}", other),
+        });
+
+        let mut client = BronziteClient::connect_to(socket_path.clone()).unwrap();
+        // Rejected before anything is sent, which the stub would panic on
+        assert!(matches!(
+            client.find_types_regex("demo", "events::(Key"),
+            Err(Error::InvalidPattern { .. })
+        ));
+        let types = client
+            .find_types_regex("demo", "^events::(Key|Mouse)Event$")
+            .unwrap();
+        assert_eq!(types[0].path, "events::KeyEvent");
+
+        {
+            let client = std::sync::Arc::new(std::sync::Mutex::new(client));
+            let krate = reflection::Crate::with_client("demo", client).unwrap();
+            assert!(matches!(
+                krate.items_regex("[unclosed"),
+                Err(Error::InvalidPattern { .. })
+            ));
+            let items = krate.items_regex("^events::(Key|Mouse)Event$").unwrap();
+            assert_eq!(items.len(), 1);
+        }
+
+        daemon.join().unwrap();
+        let _ = std::fs::remove_file(&socket_path);
+    }
+
+    #[test]
+    fn test_reflection_handles_are_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Crate>();
+        assert_send_sync::<StructDef>();
+        assert_send_sync::<TraitImpl>();
+    }
+
     #[test]
     fn test_find_daemon_binary() {
         // This should at least not panic
@@ -614,8 +1885,29 @@ mod tests {
 
 pub mod reflection;
 
+// ============================================================================
+// Snapshot Comparison
+// ============================================================================
+
+pub mod diff;
+
+pub use diff::{ApiDiff, diff};
+
 // Re-export the main types for convenient access
 pub use reflection::{
-    Crate, EnumDef, Field, Item, Method, StructDef, TraitDef, TraitImpl, TraitMethod, TypeAliasDef,
-    UnionDef,
+    ConstDef, Crate, EnumDef, Field, FullType, FunctionDef, Item, Method, ParamTypeRef, StructDef,
+    TraitAliasDef, TraitDef, TraitImpl, TraitMethod, TypeAliasDef, TypeOrigin, UnionDef, Variant,
 };
+
+// Re-export the body token types so `Method::body_tokens` can be matched on
+pub use bronzite_types::{Delimiter, LiteralKind, MatchArm, MatchOptions, Token, TypeStructure};
+
+// Re-exported so `Method::receiver_kind`, `Method::classify` and signature
+// return types can be matched on
+pub use bronzite_types::{MethodRole, ReceiverKind, ReturnType};
+
+// Re-exported so `StructDef::layout` can be matched on
+pub use bronzite_types::TypeLayout;
+
+// Re-exported for `BronziteClient::query_raw`
+pub use bronzite_types::{Query, QueryData};
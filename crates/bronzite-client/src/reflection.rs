@@ -31,11 +31,14 @@
 
 use crate::{BronziteClient, Error, Result};
 use bronzite_types::{
-    AssocConstInfo, AssocTypeInfo, FieldInfo as RawFieldInfo, FunctionSignature, GenericParam,
-    LayoutInfo, MethodDetails as RawMethodDetails, TraitDetails as RawTraitDetails,
-    TraitImplDetails as RawTraitImpl, TypeDetails, TypeSummary, Visibility,
+    AssocConstInfo, AssocTypeInfo, ConstDetails, FieldInfo as RawFieldInfo, FunctionDetails,
+    FunctionSignature, GenericParam, ItemInfo, LayoutInfo, MatchOptions,
+    MethodDetails as RawMethodDetails, MethodRole, ModuleTree, ReceiverKind, SpanInfo, Token,
+    TraitDetails as RawTraitDetails, TraitImplDetails as RawTraitImpl, TraitMethodInfo,
+    TypeDetails, TypeLayout, TypeStructure, TypeSummary, Visibility,
 };
-use std::sync::Arc;
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
 
 // ============================================================================
 // Core Reflection Entry Point
@@ -44,7 +47,7 @@ use std::sync::Arc;
 /// A reflected crate - the main entry point for type reflection.
 pub struct Crate {
     name: String,
-    client: Arc<BronziteClient>,
+    client: Arc<Mutex<BronziteClient>>,
 }
 
 impl Crate {
@@ -52,12 +55,99 @@ impl Crate {
     ///
     /// This will connect to the daemon (starting it if needed) and return
     /// a handle for querying types in the specified crate.
+    ///
+    /// The daemon is the one for the workspace of the crate being compiled
+    /// (see [`crate::find_workspace_root`]), so proc-macros in unrelated
+    /// workspaces don't share a daemon. Outside a cargo build, the default
+    /// socket is used.
+    ///
+    /// The crate is extracted right away, and if the daemon doesn't know it
+    /// this fails with [`Error::CrateNotFound`](crate::Error::CrateNotFound)
+    /// listing the crates it does know. If the daemon can't be started or
+    /// reached, this fails with
+    /// [`Error::DaemonUnavailable`](crate::Error::DaemonUnavailable), whose
+    /// message says how to install it.
     pub fn reflect(crate_name: impl Into<String>) -> Result<Self> {
-        crate::ensure_daemon_running(None)?;
-        let client = crate::connect()?;
-        Ok(Self {
+        let krate = Self {
+            name: crate_name.into(),
+            client: Arc::new(Mutex::new(connect_daemon()?)),
+        };
+        krate.load()?;
+        Ok(krate)
+    }
+
+    /// Reflect on a crate by name, recompiling it first.
+    ///
+    /// Like [`reflect`](Self::reflect), but the daemon drops whatever it has
+    /// cached for the crate and recompiles it, so the results reflect the
+    /// sources as they are now, even if an edit was just saved. Later
+    /// queries through the returned handle are served from the fresh cache.
+    /// Mostly useful in tests.
+    pub fn reflect_fresh(crate_name: impl Into<String>) -> Result<Self> {
+        let krate = Self {
+            name: crate_name.into(),
+            client: Arc::new(Mutex::new(connect_daemon()?)),
+        };
+        lock_client(&krate.client).set_fresh(true);
+        let loaded = krate.load();
+        lock_client(&krate.client).set_fresh(false);
+        loaded?;
+        Ok(krate)
+    }
+
+    /// Reflect on a crate through a client that is already connected.
+    ///
+    /// Long-lived tools that keep one connection open (an editor
+    /// integration, say) can hand out `Crate`s for several crates without
+    /// reconnecting. The client's settings, like
+    /// [`set_include_private`](BronziteClient::set_include_private), apply
+    /// to every query made through the returned handle. Handles lock the
+    /// client for each query, so it can be shared between threads.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let client = Arc::new(Mutex::new(bronzite_client::connect_for_workspace(&root)?));
+    /// let models = Crate::with_client("models", Arc::clone(&client))?;
+    /// let api = Crate::with_client("api", client)?;
+    /// ```
+    pub fn with_client(
+        crate_name: impl Into<String>,
+        client: Arc<Mutex<BronziteClient>>,
+    ) -> Result<Self> {
+        let krate = Self {
             name: crate_name.into(),
-            client: Arc::new(client),
+            client,
+        };
+        krate.load()?;
+        Ok(krate)
+    }
+
+    /// List the crates the daemon for the current workspace knows, to find
+    /// the name to pass to [`reflect`](Self::reflect).
+    pub fn list_crates() -> Result<Vec<String>> {
+        connect_daemon()?.list_crates()
+    }
+
+    /// Have the daemon extract the crate, reporting a crate it doesn't know
+    /// as [`Error::CrateNotFound`](crate::Error::CrateNotFound).
+    fn load(&self) -> Result<()> {
+        let mut client = lock_client(&self.client);
+        let error = match client.list_items(&self.name) {
+            Ok(_) => return Ok(()),
+            Err(error @ crate::Error::DaemonError(_)) => error,
+            Err(error) => return Err(error),
+        };
+
+        // Only blame the name if the daemon could tell which crates exist
+        let available = client.list_crates()?;
+        let requested = bronzite_types::normalize_crate_name(&self.name);
+        if available.is_empty() || available.contains(&requested) {
+            return Err(error);
+        }
+        Err(crate::Error::CrateNotFound {
+            requested: self.name.clone(),
+            available,
         })
     }
 
@@ -66,6 +156,58 @@ impl Crate {
         &self.name
     }
 
+    /// Get the edition the crate is compiled with, e.g. `"2021"`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Bare trait objects are an error from 2021 on
+    /// let object = if krate.edition()? == "2015" {
+    ///     quote! { Box<Handler> }
+    /// } else {
+    ///     quote! { Box<dyn Handler> }
+    /// };
+    /// ```
+    pub fn edition(&self) -> Result<String> {
+        Ok(lock_client(&self.client)
+            .get_crate_meta(&self.name)?
+            .edition)
+    }
+
+    /// Take the extraction warnings of the queries made through this crate
+    /// and the handles it returned since the last call, e.g. for items that
+    /// failed to extract and are missing from [`items`](Self::items).
+    ///
+    /// The warnings are collected on the client, so a client shared with
+    /// [`with_client`](Self::with_client) also hands out those of other
+    /// crates queried through it.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let handlers = krate.items("handlers::*")?;
+    /// for warning in krate.warnings() {
+    ///     eprintln!("warning: {}", warning);
+    /// }
+    /// ```
+    pub fn warnings(&self) -> Vec<String> {
+        lock_client(&self.client).take_warnings()
+    }
+
+    /// Get the source text of a span reported by another query.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let config = krate.get_struct("Config")?;
+    /// let span = config.details().and_then(|d| d.span.clone()).unwrap();
+    /// let text = krate.span_text(&span)?;
+    /// assert!(text.starts_with("pub struct Config"));
+    /// ```
+    pub fn span_text(&self, span: &SpanInfo) -> Result<String> {
+        lock_client(&self.client).span_text(&self.name, span)
+    }
+
     /// Get all items matching a pattern.
     ///
     /// Supports:
@@ -73,9 +215,57 @@ impl Crate {
     /// - Wildcard: `"foo::Bar*"`
     /// - Single-level glob: `"foo::*"` (matches `foo::Bar` but not `foo::bar::Baz`)
     /// - Recursive glob: `"foo::**"` (matches all descendants)
+    ///
+    /// Types come first, followed by free functions. Other uses of `**`, or
+    /// malformed paths like `"foo::"`, fail with
+    /// [`Error::InvalidPattern`](crate::Error::InvalidPattern).
     pub fn items(&self, pattern: &str) -> Result<Vec<Item>> {
-        let types = self.client_mut()?.find_types(&self.name, pattern)?;
+        self.items_with_options(pattern, MatchOptions::default())
+    }
+
+    /// Get all items matching a pattern, with relaxed matching.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let options = MatchOptions { case_insensitive: true };
+    /// // Finds `models::User` and `models::UserId`
+    /// let users = krate.items_with_options("models::user*", options)?;
+    /// ```
+    pub fn items_with_options(&self, pattern: &str, options: MatchOptions) -> Result<Vec<Item>> {
+        let (types, functions) = {
+            let mut client = lock_client(&self.client);
+            (
+                client.find_types_with(&self.name, pattern, options)?,
+                client.find_functions_with(&self.name, pattern, options)?,
+            )
+        };
+
+        let mut items = types
+            .into_iter()
+            .map(|summary| Item::from_summary(summary, &self.name, Arc::clone(&self.client)))
+            .collect::<Result<Vec<_>>>()?;
+        items.extend(functions.into_iter().map(|details| {
+            Item::Function(FunctionDef::from_details(
+                details,
+                &self.name,
+                Arc::clone(&self.client),
+            ))
+        }));
+        Ok(items)
+    }
 
+    /// Get all types whose full path matches a regular expression, for
+    /// searches globs can't express (alternation, character classes).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let events = krate.items_regex(r"^events::(Key|Mouse)\w*Event$")?;
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn items_regex(&self, regex: &str) -> Result<Vec<Item>> {
+        let types = lock_client(&self.client).find_types_regex(&self.name, regex)?;
         types
             .into_iter()
             .map(|summary| Item::from_summary(summary, &self.name, Arc::clone(&self.client)))
@@ -108,7 +298,7 @@ impl Crate {
 
     /// Get all traits matching a pattern.
     pub fn traits(&self, pattern: &str) -> Result<Vec<TraitDef>> {
-        let all_traits = self.client_mut()?.get_traits(&self.name)?;
+        let all_traits = lock_client(&self.client).get_traits(&self.name)?;
 
         let matching: Vec<_> = all_traits
             .into_iter()
@@ -121,47 +311,305 @@ impl Crate {
             .collect()
     }
 
+    /// Get all traits that have `supertrait` as a direct or indirect
+    /// supertrait.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Finds `trait Entity: Identified` where `trait Identified: Debug`
+    /// let debuggable = krate.traits_with_supertrait("Debug")?;
+    /// ```
+    pub fn traits_with_supertrait(&self, supertrait: &str) -> Result<Vec<TraitDef>> {
+        let traits = lock_client(&self.client).traits_with_supertrait(&self.name, supertrait)?;
+
+        traits
+            .into_iter()
+            .map(|info| TraitDef::from_info(info, &self.name, Arc::clone(&self.client)))
+            .collect()
+    }
+
+    /// Get the types with fields of type `type_path`, each with the names of
+    /// those fields. Fields count when the type appears anywhere in theirs,
+    /// so `ids: Vec<LegacyId>` uses `LegacyId`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for (item, fields) in krate.users_of_type("LegacyId")? {
+    ///     println!("{} uses LegacyId in {:?}", item.path(), fields);
+    /// }
+    /// ```
+    pub fn users_of_type(&self, type_path: &str) -> Result<Vec<(Item, Vec<String>)>> {
+        let usages = lock_client(&self.client).find_types_using_type(&self.name, type_path)?;
+
+        usages
+            .into_iter()
+            .map(|usage| {
+                let item = Item::from_summary(usage.owner, &self.name, Arc::clone(&self.client))?;
+                Ok((item, usage.fields))
+            })
+            .collect()
+    }
+
+    /// Get every item in the crate, including functions, consts, modules and
+    /// impls that [`items`](Self::items) doesn't yield.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for item in krate.all_items()? {
+    ///     println!("{:?} {}", item.kind, item.path);
+    /// }
+    /// ```
+    pub fn all_items(&self) -> Result<Vec<ItemInfo>> {
+        lock_client(&self.client).list_items(&self.name)
+    }
+
     /// Get a specific struct by path.
     pub fn get_struct(&self, path: &str) -> Result<StructDef> {
-        let details = self.client_mut()?.get_type(&self.name, path)?;
+        let details = lock_client(&self.client).get_type(&self.name, path)?;
+        StructDef::from_details(details, &self.name, Arc::clone(&self.client))
+    }
+
+    /// Get a struct, enum or union together with its fields, trait impls,
+    /// inherent methods and layout, fetched in a single query.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let user = krate.inspect("User")?;
+    /// println!("{} fields, {} trait impls", user.fields.len(), user.trait_impls.len());
+    /// if let Some(layout) = &user.layout {
+    ///     println!("size: {}", layout.size);
+    /// }
+    /// ```
+    pub fn inspect(&self, path: &str) -> Result<FullType> {
+        let full = lock_client(&self.client).get_type_full(&self.name, path)?;
+        FullType::from_raw(full, &self.name, Arc::clone(&self.client))
+    }
+
+    /// Get a generic struct instantiated with concrete type arguments.
+    ///
+    /// The returned struct's fields and layout describe that instantiation.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let wrapper = krate.get_struct_instantiated("Wrapper", &["u32"])?;
+    /// assert_eq!(wrapper.fields()?[0].ty, "u32");
+    /// ```
+    pub fn get_struct_instantiated(&self, path: &str, args: &[&str]) -> Result<StructDef> {
+        let details = lock_client(&self.client).get_type_instantiated(&self.name, path, args)?;
         StructDef::from_details(details, &self.name, Arc::clone(&self.client))
     }
 
+    /// Get the crate's modules as a tree, starting at the crate root.
+    ///
+    /// Each module lists the items it defines directly, which makes it easy
+    /// to mirror the module structure, e.g. for nested re-export modules.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// fn print(module: &ModuleTree, depth: usize) {
+    ///     println!("{}{} ({} items)", "  ".repeat(depth), module.name, module.items.len());
+    ///     for sub in &module.submodules {
+    ///         print(sub, depth + 1);
+    ///     }
+    /// }
+    /// print(&krate.module_tree()?, 0);
+    /// ```
+    pub fn module_tree(&self) -> Result<ModuleTree> {
+        lock_client(&self.client).get_module_tree(&self.name)
+    }
+
+    /// Get all free functions matching a pattern.
+    pub fn functions(&self, pattern: &str) -> Result<Vec<FunctionDef>> {
+        let functions = lock_client(&self.client).find_functions(&self.name, pattern)?;
+        Ok(functions
+            .into_iter()
+            .map(|details| FunctionDef::from_details(details, &self.name, Arc::clone(&self.client)))
+            .collect())
+    }
+
+    /// Get a specific free function by path.
+    pub fn get_function(&self, path: &str) -> Result<FunctionDef> {
+        let details = lock_client(&self.client).get_function(&self.name, path)?;
+        Ok(FunctionDef::from_details(
+            details,
+            &self.name,
+            Arc::clone(&self.client),
+        ))
+    }
+
+    /// Get a top-level `const` or `static` item by path, with its type and,
+    /// for a const, its evaluated value.
+    pub fn get_const(&self, path: &str) -> Result<ConstDef> {
+        let details = lock_client(&self.client).get_const(&self.name, path)?;
+        Ok(ConstDef::from_details(
+            details,
+            &self.name,
+            Arc::clone(&self.client),
+        ))
+    }
+
     /// Get a specific enum by path.
     pub fn get_enum(&self, path: &str) -> Result<EnumDef> {
-        let details = self.client_mut()?.get_type(&self.name, path)?;
+        let details = lock_client(&self.client).get_type(&self.name, path)?;
         EnumDef::from_details(details, &self.name, Arc::clone(&self.client))
     }
 
     /// Get a specific trait by path.
     pub fn get_trait(&self, path: &str) -> Result<TraitDef> {
-        let details = self.client_mut()?.get_trait(&self.name, path)?;
+        let details = lock_client(&self.client).get_trait(&self.name, path)?;
         TraitDef::from_trait_details(details, &self.name, Arc::clone(&self.client))
     }
 
     /// Get a specific type alias by path.
     pub fn get_type_alias(&self, path: &str) -> Result<TypeAliasDef> {
-        let (original, resolved, chain) = self.client_mut()?.resolve_alias(&self.name, path)?;
+        let (original, resolved, chain, generics) =
+            lock_client(&self.client).resolve_alias_with_generics(&self.name, path)?;
         Ok(TypeAliasDef {
             path: original,
             resolved_path: resolved,
             resolution_chain: chain,
+            generics,
+            crate_name: self.name.clone(),
+            client: Arc::clone(&self.client),
+        })
+    }
+
+    /// Get all type aliases matching a pattern.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Build a map from alias to the type it stands for
+    /// let type_map: HashMap<String, String> = krate
+    ///     .type_aliases("**")?
+    ///     .into_iter()
+    ///     .map(|alias| (alias.path, alias.resolved_path))
+    ///     .collect();
+    /// ```
+    pub fn type_aliases(&self, pattern: &str) -> Result<Vec<TypeAliasDef>> {
+        let aliases = lock_client(&self.client).get_type_aliases(&self.name, pattern)?;
+        Ok(aliases
+            .into_iter()
+            .map(|alias| TypeAliasDef {
+                path: alias.path,
+                resolved_path: alias.resolved_ty,
+                resolution_chain: alias.chain,
+                generics: alias.generics,
+                crate_name: self.name.clone(),
+                client: Arc::clone(&self.client),
+            })
+            .collect())
+    }
+
+    /// Get a specific trait alias by path.
+    pub fn get_trait_alias(&self, path: &str) -> Result<TraitAliasDef> {
+        let alias = lock_client(&self.client).get_trait_alias(&self.name, path)?;
+        Ok(TraitAliasDef {
+            name: alias.name,
+            path: alias.path,
+            bounds: alias.bounds,
+            generics: alias.generics,
+            docs: alias.docs,
+            visibility: alias.visibility,
             crate_name: self.name.clone(),
             client: Arc::clone(&self.client),
         })
     }
 
-    /// Helper to get mutable client access (Arc doesn't need Mutex for single-threaded use).
-    fn client_mut(&self) -> Result<&mut BronziteClient> {
-        // SAFETY: This is safe in proc-macro context where we're single-threaded.
-        // Arc is used for cheap cloning, not thread-safety here.
-        unsafe {
-            let ptr = Arc::as_ptr(&self.client) as *mut BronziteClient;
-            Ok(&mut *ptr)
+    /// Walk the types reachable from `root_path` through field types,
+    /// breadth first, following at most `max_depth` levels of fields.
+    ///
+    /// Each type is visited once, so self-referential types like
+    /// `struct Node { next: Option<Box<Node>> }` terminate. Types nested in
+    /// generic arguments (`Vec<Item>`) are followed too, while primitives and
+    /// types from other crates are skipped. The root comes first.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for item in krate.type_graph("Node", 8)? {
+    ///     println!("reachable: {}", item.path());
+    /// }
+    /// ```
+    pub fn type_graph(&self, root_path: &str, max_depth: usize) -> Result<Vec<Item>> {
+        let root = lock_client(&self.client).get_type(&self.name, root_path)?;
+        let mut visited: HashSet<String> = HashSet::from([root.path.clone()]);
+        let mut queue = VecDeque::from([(root, 0)]);
+        let mut items = Vec::new();
+
+        while let Some((details, depth)) = queue.pop_front() {
+            if depth < max_depth {
+                let field_types = details
+                    .fields
+                    .iter()
+                    .flatten()
+                    .chain(details.variants.iter().flatten().flat_map(|v| &v.fields))
+                    .map(|f| f.resolved_ty.as_deref().unwrap_or(&f.ty));
+
+                for path in field_types.flat_map(type_paths_in) {
+                    if !visited.insert(path.to_string()) {
+                        continue;
+                    }
+                    // Primitives and external types aren't found, which ends
+                    // the walk there
+                    if let Ok(next) = lock_client(&self.client).get_type(&self.name, path) {
+                        // A suffix path may name a type already reached by
+                        // its full path
+                        if next.path == path || visited.insert(next.path.clone()) {
+                            queue.push_back((next, depth + 1));
+                        }
+                    }
+                }
+            }
+
+            let summary = TypeSummary {
+                name: details.name,
+                path: details.path,
+                kind: details.kind,
+                generics: details.generics,
+                crate_name: None,
+            };
+            items.push(Item::from_summary(
+                summary,
+                &self.name,
+                Arc::clone(&self.client),
+            )?);
         }
+
+        Ok(items)
     }
 }
 
+/// Lock the client shared by a crate's handles for one query.
+///
+/// A query that panicked mid-way (e.g. in a [`stream_types`] callback)
+/// poisons the lock, but responses are matched to requests by id, so the
+/// client stays usable.
+///
+/// [`stream_types`]: BronziteClient::stream_types
+fn lock_client(client: &Mutex<BronziteClient>) -> MutexGuard<'_, BronziteClient> {
+    client
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Connect to the daemon for the workspace being compiled, starting it if
+/// needed (see [`Crate::reflect`]).
+fn connect_daemon() -> Result<BronziteClient> {
+    let connected = match crate::find_workspace_root() {
+        Some(root) => crate::ensure_daemon_running_for_workspace(&root)
+            .and_then(|()| crate::connect_for_workspace(&root)),
+        None => crate::ensure_daemon_running(None).and_then(|()| crate::connect()),
+    };
+    connected.map_err(crate::Error::daemon_unavailable)
+}
+
 // ============================================================================
 // Item Enum - Unified Type Representation
 // ============================================================================
@@ -185,6 +633,7 @@ impl Crate {
 ///         Item::Trait(t) => println!("Trait: {}", t.name),
 ///         Item::TypeAlias(a) => println!("Alias: {}", a.path),
 ///         Item::Union(u) => println!("Union: {}", u.name),
+///         Item::Function(f) => println!("Function: {}", f.name),
 ///     }
 /// }
 /// ```
@@ -200,6 +649,8 @@ pub enum Item {
     TypeAlias(TypeAliasDef),
     /// A union definition
     Union(UnionDef),
+    /// A free function
+    Function(FunctionDef),
 }
 
 impl Item {
@@ -211,6 +662,7 @@ impl Item {
             Item::Trait(t) => &t.name,
             Item::TypeAlias(a) => &a.path,
             Item::Union(u) => &u.name,
+            Item::Function(f) => &f.name,
         }
     }
 
@@ -222,14 +674,25 @@ impl Item {
             Item::Trait(t) => &t.path,
             Item::TypeAlias(a) => &a.path,
             Item::Union(u) => &u.path,
+            Item::Function(f) => &f.path,
         }
     }
 
     fn from_summary(
         summary: TypeSummary,
         crate_name: &str,
-        client: Arc<BronziteClient>,
+        client: Arc<Mutex<BronziteClient>>,
     ) -> Result<Self> {
+        // Types reached from another crate (e.g. implementors of a trait
+        // defined upstream) must be queried in the crate that defines them.
+        // Extraction reports underscored crate names, so compare loosely to
+        // keep the caller's spelling for the current crate.
+        let crate_name = match summary.crate_name {
+            Some(owner) if owner != crate_name.replace('-', "_") => owner,
+            _ => crate_name.to_string(),
+        };
+        let crate_name = crate_name.as_str();
+
         match summary.kind {
             bronzite_types::TypeKind::Struct => Ok(Item::Struct(StructDef {
                 name: summary.name,
@@ -256,11 +719,7 @@ impl Item {
             })),
             bronzite_types::TypeKind::Trait => {
                 // For traits, we need to fetch full details
-                let client_mut = unsafe {
-                    let ptr = Arc::as_ptr(&client) as *mut BronziteClient;
-                    &mut *ptr
-                };
-                let details = client_mut.get_trait(crate_name, &summary.path)?;
+                let details = lock_client(&client).get_trait(crate_name, &summary.path)?;
                 Ok(Item::Trait(TraitDef::from_trait_details(
                     details, crate_name, client,
                 )?))
@@ -270,6 +729,76 @@ impl Item {
     }
 }
 
+// ============================================================================
+// Full Type
+// ============================================================================
+
+/// A type with its fields, impls and layout, as returned by
+/// [`Crate::inspect`].
+#[derive(Debug, Clone)]
+pub struct FullType {
+    /// The type itself, as a struct, enum or union
+    pub item: Item,
+    /// Fields of a struct or union; empty for enums
+    pub fields: Vec<Field>,
+    pub trait_impls: Vec<TraitImpl>,
+    /// Methods from all inherent impl blocks
+    pub methods: Vec<Method>,
+    /// `None` when no layout could be computed, e.g. for generic types
+    pub layout: Option<LayoutInfo>,
+}
+
+impl FullType {
+    fn from_raw(
+        full: crate::TypeFull,
+        crate_name: &str,
+        client: Arc<Mutex<BronziteClient>>,
+    ) -> Result<Self> {
+        let fields = full
+            .fields
+            .into_iter()
+            .map(|f| Field::from_raw(f, crate_name, Arc::clone(&client)))
+            .collect();
+        let trait_impls = full
+            .trait_impls
+            .into_iter()
+            .map(|i| TraitImpl::from_raw(i, crate_name, Arc::clone(&client)))
+            .collect();
+        let methods = full
+            .inherent_impls
+            .into_iter()
+            .flat_map(|impl_block| impl_block.methods)
+            .map(|m| Method::from_raw(m, crate_name, Arc::clone(&client)))
+            .collect();
+
+        let details = full.details;
+        let item = match details.kind {
+            bronzite_types::TypeKind::Struct => {
+                Item::Struct(StructDef::from_details(details, crate_name, client)?)
+            }
+            bronzite_types::TypeKind::Enum => {
+                Item::Enum(EnumDef::from_details(details, crate_name, client)?)
+            }
+            bronzite_types::TypeKind::Union => Item::Union(UnionDef {
+                name: details.name,
+                path: details.path,
+                generics: details.generics,
+                crate_name: crate_name.to_string(),
+                client,
+            }),
+            _ => return Err(Error::UnexpectedResponse),
+        };
+
+        Ok(Self {
+            item,
+            fields,
+            trait_impls,
+            methods,
+            layout: full.layout,
+        })
+    }
+}
+
 // ============================================================================
 // Struct Definition
 // ============================================================================
@@ -314,7 +843,7 @@ pub struct StructDef {
     /// Generic parameters
     pub generics: Vec<GenericParam>,
     crate_name: String,
-    client: Arc<BronziteClient>,
+    client: Arc<Mutex<BronziteClient>>,
     cached_details: Option<Box<TypeDetails>>,
 }
 
@@ -322,7 +851,7 @@ impl StructDef {
     fn from_details(
         details: TypeDetails,
         crate_name: &str,
-        client: Arc<BronziteClient>,
+        client: Arc<Mutex<BronziteClient>>,
     ) -> Result<Self> {
         Ok(Self {
             name: details.name.clone(),
@@ -334,11 +863,37 @@ impl StructDef {
         })
     }
 
+    /// Whether this struct takes type or const parameters. See
+    /// [`TypeSummary::is_generic`].
+    pub fn is_generic(&self) -> bool {
+        bronzite_types::is_generic(&self.generics)
+    }
+
+    /// Whether this struct has any type parameters.
+    pub fn has_type_params(&self) -> bool {
+        bronzite_types::has_type_params(&self.generics)
+    }
+
+    /// Whether this struct has any lifetime parameters.
+    pub fn has_lifetimes(&self) -> bool {
+        bronzite_types::has_lifetimes(&self.generics)
+    }
+
+    /// Whether this struct has any const parameters.
+    pub fn has_const_params(&self) -> bool {
+        bronzite_types::has_const_params(&self.generics)
+    }
+
     /// Get all fields of this struct.
     ///
     /// Returns a vector of [`Field`] objects, each representing a field in the struct.
     /// Fields include metadata like name, type, visibility, size, and offset.
     ///
+    /// The fields come from the details this `StructDef` was created with,
+    /// not a new query, so they don't pick up later edits to the crate, even
+    /// after [`Crate::reflect_fresh`]. Look the struct up again for current
+    /// fields.
+    ///
     /// # Example
     ///
     /// ```ignore
@@ -354,15 +909,39 @@ impl StructDef {
     /// }
     /// ```
     pub fn fields(&self) -> Result<Vec<Field>> {
-        let fields = self
-            .client_mut()?
-            .get_fields(&self.crate_name, &self.path)?;
-        Ok(fields
+        Ok(self
+            .raw_fields()?
             .into_iter()
             .map(|f| Field::from_raw(f, &self.crate_name, Arc::clone(&self.client)))
             .collect())
     }
 
+    fn raw_fields(&self) -> Result<Vec<RawFieldInfo>> {
+        // Cached details may be an instantiation, whose field types differ
+        // from the generic definition
+        match self.details().and_then(|d| d.fields.clone()) {
+            Some(fields) => Ok(fields),
+            None => lock_client(&self.client).get_fields(&self.crate_name, &self.path),
+        }
+    }
+
+    /// Get only the `pub` fields of this struct.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Public fields need no getter
+    /// let user = krate.get_struct("User")?;
+    /// let public: Vec<_> = user.public_fields()?.into_iter().filter_map(|f| f.name).collect();
+    /// ```
+    pub fn public_fields(&self) -> Result<Vec<Field>> {
+        Ok(self
+            .fields()?
+            .into_iter()
+            .filter(Field::is_public)
+            .collect())
+    }
+
     /// Get all trait implementations for this struct.
     ///
     /// Returns a vector of [`TraitImpl`] objects containing information about each
@@ -380,15 +959,34 @@ impl StructDef {
     /// }
     /// ```
     pub fn trait_impls(&self) -> Result<Vec<TraitImpl>> {
-        let impls = self
-            .client_mut()?
-            .get_trait_impls(&self.crate_name, &self.path)?;
+        let impls = lock_client(&self.client).get_trait_impls(&self.crate_name, &self.path)?;
         Ok(impls
             .into_iter()
             .map(|i| TraitImpl::from_raw(i, &self.crate_name, Arc::clone(&self.client)))
             .collect())
     }
 
+    /// Get the trait implementations for one instantiation of this struct,
+    /// e.g. only `impl Serialize for Wrapper<u8>` and not the
+    /// `Wrapper<u16>` impl. Blanket impls over `Wrapper<T>` only match
+    /// `&["T"]`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let wrapper = krate.get_struct("Wrapper")?;
+    /// for impl_block in wrapper.trait_impls_for_args(&["u8"])? {
+    ///     println!("Wrapper<u8> implements {}", impl_block.trait_path);
+    /// }
+    /// ```
+    pub fn trait_impls_for_args(&self, args: &[&str]) -> Result<Vec<TraitImpl>> {
+        Ok(self
+            .trait_impls()?
+            .into_iter()
+            .filter(|i| i.raw.self_ty_args_match(args))
+            .collect())
+    }
+
     /// Check if this struct implements a specific trait.
     ///
     /// This is a convenient way to test for trait implementation without
@@ -411,11 +1009,46 @@ impl StructDef {
     /// ```
     pub fn implements(&self, trait_path: &str) -> Result<bool> {
         let (implements, _) =
-            self.client_mut()?
-                .check_impl(&self.crate_name, &self.path, trait_path)?;
+            lock_client(&self.client).check_impl(&self.crate_name, &self.path, trait_path)?;
+        Ok(implements)
+    }
+
+    /// Check if this struct, instantiated with concrete type arguments,
+    /// implements a trait. Conditional impls such as
+    /// `impl<T: Clone> Clone for Wrapper<T>` are decided for those arguments.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let wrapper = krate.get_struct("Wrapper")?;
+    /// assert!(wrapper.implements_with_args("Clone", &["String"])?);
+    /// assert!(!wrapper.implements_with_args("Clone", &["NotClone"])?);
+    /// ```
+    pub fn implements_with_args(&self, trait_path: &str, args: &[&str]) -> Result<bool> {
+        let (implements, _) = lock_client(&self.client).check_impl_for(
+            &self.crate_name,
+            &self.path,
+            args,
+            trait_path,
+        )?;
         Ok(implements)
     }
 
+    /// Check if this struct, instantiated with concrete type arguments, is
+    /// `Sized`. A struct whose last field is a `T: ?Sized` is only sized
+    /// when `T` is.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let wrapper = krate.get_struct("Wrapper")?;
+    /// assert!(wrapper.is_sized_with_args(&["u32"])?);
+    /// assert!(!wrapper.is_sized_with_args(&["str"])?);
+    /// ```
+    pub fn is_sized_with_args(&self, args: &[&str]) -> Result<bool> {
+        lock_client(&self.client).check_sized(&self.crate_name, &self.path, args)
+    }
+
     /// Get inherent methods (from `impl StructName { ... }` blocks).
     ///
     /// Returns methods defined in inherent impl blocks, not trait implementations.
@@ -437,9 +1070,7 @@ impl StructDef {
     /// }
     /// ```
     pub fn methods(&self) -> Result<Vec<Method>> {
-        let impls = self
-            .client_mut()?
-            .get_inherent_impls(&self.crate_name, &self.path)?;
+        let impls = lock_client(&self.client).get_inherent_impls(&self.crate_name, &self.path)?;
         Ok(impls
             .into_iter()
             .flat_map(|impl_block| {
@@ -451,26 +1082,91 @@ impl StructDef {
             .collect())
     }
 
-    /// Get memory layout information for this struct.
+    /// Find the methods a call to `name` on this struct may resolve to.
     ///
-    /// Returns [`LayoutInfo`] containing size, alignment, field offsets,
-    /// and auto-trait implementations (Send, Sync, Copy).
+    /// Each candidate is paired with the trait providing it, or `None` for
+    /// an inherent method. Inherent methods come first, since they take
+    /// precedence; several trait candidates mean the call is ambiguous.
     ///
     /// # Example
     ///
     /// ```ignore
     /// let user = krate.get_struct("User")?;
-    /// let layout = user.layout()?;
-    /// println!("Size: {} bytes", layout.size);
-    /// println!("Alignment: {} bytes", layout.align);
-    /// println!("Is Copy: {}", layout.is_copy);
+    /// for (trait_path, method) in user.resolve_method("id")? {
+    ///     println!("{}: {}", trait_path.as_deref().unwrap_or("<inherent>"), method.signature);
+    /// }
     /// ```
-    pub fn layout(&self) -> Result<LayoutInfo> {
-        self.client_mut()?.get_layout(&self.crate_name, &self.path)
+    pub fn resolve_method(&self, name: &str) -> Result<Vec<(Option<String>, Method)>> {
+        let candidates =
+            lock_client(&self.client).resolve_method(&self.crate_name, &self.path, name)?;
+        Ok(candidates
+            .into_iter()
+            .map(|c| {
+                let method = Method::from_raw(c.method, &self.crate_name, Arc::clone(&self.client));
+                (c.trait_path, method)
+            })
+            .collect())
     }
 
-    /// Get the source code of this struct definition.
-    pub fn source(&self) -> Option<&str> {
+    /// Get memory layout information for this struct.
+    ///
+    /// A [`TypeLayout::Computed`] carries a [`LayoutInfo`] with the size,
+    /// alignment, field offsets and auto-trait implementations (Send, Sync,
+    /// Copy). Generic structs have no layout until they get arguments, see
+    /// [`Crate::get_struct_instantiated`], and dynamically sized ones have
+    /// no fixed size at all.
+    ///
+    /// Like [`fields`](Self::fields), this answers from the details the
+    /// `StructDef` was created with when they include a layout.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let user = krate.get_struct("User")?;
+    /// match user.layout()? {
+    ///     TypeLayout::Computed(layout) => {
+    ///         println!("Size: {} bytes", layout.size);
+    ///         println!("Alignment: {} bytes", layout.align);
+    ///         println!("Is Copy: {}", layout.is_copy);
+    ///     }
+    ///     TypeLayout::Generic { params } => println!("Needs arguments for {:?}", params),
+    ///     TypeLayout::Unsized => println!("Dynamically sized"),
+    /// }
+    /// ```
+    pub fn layout(&self) -> Result<TypeLayout> {
+        match self.details().and_then(|d| d.layout.clone()) {
+            Some(layout) if layout.is_sized => Ok(TypeLayout::Computed(layout)),
+            Some(_) => Ok(TypeLayout::Unsized),
+            None => lock_client(&self.client).get_layout(&self.crate_name, &self.path),
+        }
+    }
+
+    /// Get the fields of this struct with their `offset` and `size` filled
+    /// in from the struct's [`layout`](Self::layout).
+    ///
+    /// Generic and unsized structs have no computed layout, so their fields
+    /// come back as from [`fields`](Self::fields), without offsets.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for field in krate.get_struct("Packet")?.fields_with_layout()? {
+    ///     println!("{:?} at {:?}, {:?} bytes", field.name, field.offset, field.size);
+    /// }
+    /// ```
+    pub fn fields_with_layout(&self) -> Result<Vec<Field>> {
+        let mut fields = self.raw_fields()?;
+        if let TypeLayout::Computed(layout) = self.layout()? {
+            layout.apply_to_fields(&mut fields);
+        }
+        Ok(fields
+            .into_iter()
+            .map(|f| Field::from_raw(f, &self.crate_name, Arc::clone(&self.client)))
+            .collect())
+    }
+
+    /// Get the source code of this struct definition.
+    pub fn source(&self) -> Option<&str> {
         self.details().and_then(|d| d.source.as_deref())
     }
 
@@ -484,17 +1180,41 @@ impl StructDef {
         self.details().map(|d| &d.visibility)
     }
 
+    /// Check whether this struct is defined in the reflected crate, so the
+    /// orphan rules allow implementing foreign traits for it.
+    pub fn is_local(&self) -> Option<bool> {
+        self.details().map(|d| d.is_local)
+    }
+
+    /// Check whether this struct is `#[fundamental]`, so wrapping a local
+    /// type in it keeps that type local for the orphan rules.
+    pub fn is_fundamental(&self) -> Option<bool> {
+        self.details().map(|d| d.is_fundamental)
+    }
+
+    /// Get the variance of each generic parameter, in the order of
+    /// [`generics`](Self::generics): `covariant`, `contravariant`,
+    /// `invariant` or `bivariant`.
+    ///
+    /// This is the variance rustc computes, whose naming and edge cases
+    /// aren't stable across compiler versions.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let view = krate.get_struct("View")?;
+    /// for (param, variance) in view.generics.iter().zip(view.variances().unwrap_or_default()) {
+    ///     println!("{}: {}", param.name, variance);
+    /// }
+    /// ```
+    pub fn variances(&self) -> Option<&[String]> {
+        self.details().map(|d| d.variances.as_slice())
+    }
+
     /// Get doc comments.
     pub fn docs(&self) -> Option<&str> {
         self.details().and_then(|d| d.docs.as_deref())
     }
-
-    fn client_mut(&self) -> Result<&mut BronziteClient> {
-        unsafe {
-            let ptr = Arc::as_ptr(&self.client) as *mut BronziteClient;
-            Ok(&mut *ptr)
-        }
-    }
 }
 
 // ============================================================================
@@ -508,7 +1228,7 @@ pub struct EnumDef {
     pub path: String,
     pub generics: Vec<GenericParam>,
     crate_name: String,
-    client: Arc<BronziteClient>,
+    client: Arc<Mutex<BronziteClient>>,
     cached_details: Option<Box<TypeDetails>>,
 }
 
@@ -516,7 +1236,7 @@ impl EnumDef {
     fn from_details(
         details: TypeDetails,
         crate_name: &str,
-        client: Arc<BronziteClient>,
+        client: Arc<Mutex<BronziteClient>>,
     ) -> Result<Self> {
         Ok(Self {
             name: details.name.clone(),
@@ -528,16 +1248,97 @@ impl EnumDef {
         })
     }
 
+    /// Whether this enum takes type or const parameters. See
+    /// [`TypeSummary::is_generic`].
+    pub fn is_generic(&self) -> bool {
+        bronzite_types::is_generic(&self.generics)
+    }
+
+    /// Whether this enum has any type parameters.
+    pub fn has_type_params(&self) -> bool {
+        bronzite_types::has_type_params(&self.generics)
+    }
+
+    /// Whether this enum has any lifetime parameters.
+    pub fn has_lifetimes(&self) -> bool {
+        bronzite_types::has_lifetimes(&self.generics)
+    }
+
+    /// Whether this enum has any const parameters.
+    pub fn has_const_params(&self) -> bool {
+        bronzite_types::has_const_params(&self.generics)
+    }
+
     /// Get the enum's variants.
     pub fn variants(&self) -> Option<&[bronzite_types::EnumVariantInfo]> {
         self.details().and_then(|d| d.variants.as_deref())
     }
 
+    /// Get a variant by name, with fields that can navigate to their types.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // enum Msg { Create(CreateData), Delete(u64) }
+    /// let msg = krate.get_enum("Msg")?;
+    /// let create = msg.variant("Create").unwrap();
+    /// if let Some(Item::Struct(data)) = create.fields()[0].type_def()? {
+    ///     println!("Create carries {}", data.path); // CreateData
+    /// }
+    /// ```
+    pub fn variant(&self, name: &str) -> Option<Variant> {
+        self.variants()?
+            .iter()
+            .find(|v| v.name == name)
+            .map(|v| Variant::from_raw(v.clone(), &self.crate_name, Arc::clone(&self.client)))
+    }
+
+    /// Get a field of a variant, by name or, for tuple variants, by index.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // enum Shape { Circle { radius: f64 }, Rect(f64, f64) }
+    /// let shape = krate.get_enum("Shape")?;
+    /// let radius = shape.variant_field("Circle", "radius")?;
+    /// let height = shape.variant_field("Rect", "1")?;
+    /// ```
+    pub fn variant_field(&self, variant: &str, field: &str) -> Result<Field> {
+        let raw = lock_client(&self.client).get_variant_field(
+            &self.crate_name,
+            &self.path,
+            variant,
+            field,
+        )?;
+        Ok(Field::from_raw(
+            raw,
+            &self.crate_name,
+            Arc::clone(&self.client),
+        ))
+    }
+
+    /// Get each variant's name with its discriminant, in declaration order.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // #[repr(u8)] enum Opcode { Nop = 0, Load = 0x10, Store }
+    /// let opcode = krate.get_enum("Opcode")?;
+    /// for (name, value) in opcode.variant_discriminants() {
+    ///     println!("{} => {}", value, name); // ..., 17 => Store
+    /// }
+    /// ```
+    pub fn variant_discriminants(&self) -> Vec<(String, i128)> {
+        self.variants()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|v| Some((v.name.clone(), v.discriminant_value()?)))
+            .collect()
+    }
+
     /// Get trait implementations for this enum.
     pub fn trait_impls(&self) -> Result<Vec<TraitImpl>> {
-        let impls = self
-            .client_mut()?
-            .get_trait_impls(&self.crate_name, &self.path)?;
+        let impls = lock_client(&self.client).get_trait_impls(&self.crate_name, &self.path)?;
         Ok(impls
             .into_iter()
             .map(|i| TraitImpl::from_raw(i, &self.crate_name, Arc::clone(&self.client)))
@@ -547,16 +1348,13 @@ impl EnumDef {
     /// Check if this enum implements a specific trait.
     pub fn implements(&self, trait_path: &str) -> Result<bool> {
         let (implements, _) =
-            self.client_mut()?
-                .check_impl(&self.crate_name, &self.path, trait_path)?;
+            lock_client(&self.client).check_impl(&self.crate_name, &self.path, trait_path)?;
         Ok(implements)
     }
 
     /// Get inherent methods.
     pub fn methods(&self) -> Result<Vec<Method>> {
-        let impls = self
-            .client_mut()?
-            .get_inherent_impls(&self.crate_name, &self.path)?;
+        let impls = lock_client(&self.client).get_inherent_impls(&self.crate_name, &self.path)?;
         Ok(impls
             .into_iter()
             .flat_map(|impl_block| {
@@ -584,13 +1382,49 @@ impl EnumDef {
     pub fn docs(&self) -> Option<&str> {
         self.details().and_then(|d| d.docs.as_deref())
     }
+}
+
+/// A variant of a reflected enum.
+#[derive(Debug, Clone)]
+pub struct Variant {
+    pub name: String,
+    /// Position in declaration order
+    pub index: usize,
+    /// Discriminant value as a decimal integer
+    pub discriminant: Option<String>,
+    pub docs: Option<String>,
+    pub attributes: Vec<String>,
+    raw_fields: Vec<RawFieldInfo>,
+    crate_name: String,
+    client: Arc<Mutex<BronziteClient>>,
+}
 
-    fn client_mut(&self) -> Result<&mut BronziteClient> {
-        unsafe {
-            let ptr = Arc::as_ptr(&self.client) as *mut BronziteClient;
-            Ok(&mut *ptr)
+impl Variant {
+    fn from_raw(
+        raw: bronzite_types::EnumVariantInfo,
+        crate_name: &str,
+        client: Arc<Mutex<BronziteClient>>,
+    ) -> Self {
+        Self {
+            name: raw.name,
+            index: raw.index,
+            discriminant: raw.discriminant,
+            docs: raw.docs,
+            attributes: raw.attributes,
+            raw_fields: raw.fields,
+            crate_name: crate_name.to_string(),
+            client,
         }
     }
+
+    /// Get the variant's fields: none for a unit variant, unnamed ones for a
+    /// tuple variant.
+    pub fn fields(&self) -> Vec<Field> {
+        self.raw_fields
+            .iter()
+            .map(|f| Field::from_raw(f.clone(), &self.crate_name, Arc::clone(&self.client)))
+            .collect()
+    }
 }
 
 // ============================================================================
@@ -604,26 +1438,34 @@ pub struct UnionDef {
     pub path: String,
     pub generics: Vec<GenericParam>,
     crate_name: String,
-    client: Arc<BronziteClient>,
+    client: Arc<Mutex<BronziteClient>>,
 }
 
 impl UnionDef {
     /// Get the union's fields.
     pub fn fields(&self) -> Result<Vec<Field>> {
-        let fields = self
-            .client_mut()?
-            .get_fields(&self.crate_name, &self.path)?;
+        let fields = lock_client(&self.client).get_fields(&self.crate_name, &self.path)?;
         Ok(fields
             .into_iter()
             .map(|f| Field::from_raw(f, &self.crate_name, Arc::clone(&self.client)))
             .collect())
     }
 
-    fn client_mut(&self) -> Result<&mut BronziteClient> {
-        unsafe {
-            let ptr = Arc::as_ptr(&self.client) as *mut BronziteClient;
-            Ok(&mut *ptr)
+    /// Get the union's fields with their `offset` and `size` filled in.
+    ///
+    /// Every field starts at offset 0 and `size` is the field's own size,
+    /// so the largest field's size is the union's minimum size. A generic
+    /// union has no computed layout and its fields come back without offsets.
+    pub fn fields_with_layout(&self) -> Result<Vec<Field>> {
+        let mut client = lock_client(&self.client);
+        let mut fields = client.get_fields(&self.crate_name, &self.path)?;
+        if let TypeLayout::Computed(layout) = client.get_layout(&self.crate_name, &self.path)? {
+            layout.apply_to_fields(&mut fields);
         }
+        Ok(fields
+            .into_iter()
+            .map(|f| Field::from_raw(f, &self.crate_name, Arc::clone(&self.client)))
+            .collect())
     }
 }
 
@@ -643,7 +1485,7 @@ pub struct TraitDef {
     pub source: Option<String>,
     pub docs: Option<String>,
     crate_name: String,
-    client: Arc<BronziteClient>,
+    client: Arc<Mutex<BronziteClient>>,
     cached_details: Option<Box<RawTraitDetails>>,
 }
 
@@ -651,21 +1493,17 @@ impl TraitDef {
     fn from_info(
         info: bronzite_types::TraitInfo,
         crate_name: &str,
-        client: Arc<BronziteClient>,
+        client: Arc<Mutex<BronziteClient>>,
     ) -> Result<Self> {
         // Fetch full details
-        let client_mut = unsafe {
-            let ptr = Arc::as_ptr(&client) as *mut BronziteClient;
-            &mut *ptr
-        };
-        let details = client_mut.get_trait(crate_name, &info.path)?;
+        let details = lock_client(&client).get_trait(crate_name, &info.path)?;
         Self::from_trait_details(details, crate_name, client)
     }
 
     fn from_trait_details(
         details: RawTraitDetails,
         crate_name: &str,
-        client: Arc<BronziteClient>,
+        client: Arc<Mutex<BronziteClient>>,
     ) -> Result<Self> {
         Ok(Self {
             name: details.name.clone(),
@@ -686,23 +1524,65 @@ impl TraitDef {
     pub fn methods(&self) -> Vec<TraitMethod> {
         self.cached_details
             .as_ref()
-            .map(|d| {
-                d.methods
-                    .iter()
-                    .map(|m| TraitMethod {
-                        name: m.name.clone(),
-                        signature: m.signature.clone(),
-                        parsed_signature: m.parsed_signature.clone(),
-                        has_default: m.has_default,
-                        default_body: m.default_body.clone(),
-                        is_unsafe: m.is_unsafe,
-                        docs: m.docs.clone(),
-                    })
-                    .collect()
-            })
+            .map(|d| d.methods.iter().map(TraitMethod::from_raw).collect())
             .unwrap_or_default()
     }
 
+    /// Get a method of this trait by name.
+    ///
+    /// Returns `None` if the trait has no method with that name. Required
+    /// methods are returned with `has_default == false` and no body.
+    pub fn method(&self, name: &str) -> Option<TraitMethod> {
+        self.cached_details
+            .as_ref()?
+            .methods
+            .iter()
+            .find(|m| m.name == name)
+            .map(TraitMethod::from_raw)
+    }
+
+    /// Check whether this trait looks sealed, i.e. whether downstream code
+    /// is prevented from implementing it.
+    ///
+    /// This is a heuristic based on supertrait visibility: the trait counts
+    /// as sealed when one of its supertraits is private, `pub(restricted)`,
+    /// or declared in a module that is not reachable from outside the crate.
+    /// Other sealing tricks, such as private types in method signatures, are
+    /// not detected.
+    pub fn is_sealed(&self) -> bool {
+        self.cached_details.as_ref().is_some_and(|d| d.is_sealed)
+    }
+
+    /// Get the direct supertraits defined in the same crate. Supertraits
+    /// from other crates, like `Debug`, are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Walk the whole trait hierarchy
+    /// fn walk(t: &TraitDef, depth: usize) -> Result<()> {
+    ///     println!("{}{}", "  ".repeat(depth), t.path);
+    ///     for supertrait in t.supertrait_defs()? {
+    ///         walk(&supertrait, depth + 1)?;
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn supertrait_defs(&self) -> Result<Vec<TraitDef>> {
+        let mut traits = Vec::new();
+        for path in &self.supertraits {
+            let found = lock_client(&self.client).get_trait(&self.crate_name, path);
+            if let Ok(details) = found {
+                traits.push(TraitDef::from_trait_details(
+                    details,
+                    &self.crate_name,
+                    Arc::clone(&self.client),
+                )?);
+            }
+        }
+        Ok(traits)
+    }
+
     /// Get associated types.
     pub fn associated_types(&self) -> Vec<&AssocTypeInfo> {
         self.cached_details
@@ -721,20 +1601,23 @@ impl TraitDef {
 
     /// Get all types that implement this trait.
     pub fn implementors(&self) -> Result<Vec<Item>> {
-        let types = self
-            .client_mut()?
-            .get_implementors(&self.crate_name, &self.path)?;
+        let types = lock_client(&self.client).get_implementors(&self.crate_name, &self.path)?;
         types
             .into_iter()
             .map(|summary| Item::from_summary(summary, &self.crate_name, Arc::clone(&self.client)))
             .collect()
     }
 
-    fn client_mut(&self) -> Result<&mut BronziteClient> {
-        unsafe {
-            let ptr = Arc::as_ptr(&self.client) as *mut BronziteClient;
-            Ok(&mut *ptr)
-        }
+    /// Get every impl block of this trait in the crate, with its methods.
+    ///
+    /// Where [`implementors`](Self::implementors) only names the types, this
+    /// fetches the impls themselves in a single query.
+    pub fn impls(&self) -> Result<Vec<TraitImpl>> {
+        let impls = lock_client(&self.client).all_impls_of_trait(&self.crate_name, &self.path)?;
+        Ok(impls
+            .into_iter()
+            .map(|i| TraitImpl::from_raw(i, &self.crate_name, Arc::clone(&self.client)))
+            .collect())
     }
 }
 
@@ -746,10 +1629,42 @@ pub struct TraitMethod {
     pub parsed_signature: FunctionSignature,
     pub has_default: bool,
     pub default_body: Option<String>,
+    /// Default implementation as tokens (simplified AST)
+    pub default_body_tokens: Option<Vec<Token>>,
     pub is_unsafe: bool,
+    /// Whether this is a `const fn`
+    pub is_const: bool,
+    /// Whether this is an `async fn`
+    pub is_async: bool,
+    /// Whether this method is callable through `dyn Trait`
+    pub is_dyn_compatible: bool,
     pub docs: Option<String>,
 }
 
+impl TraitMethod {
+    fn from_raw(raw: &TraitMethodInfo) -> Self {
+        Self {
+            name: raw.name.clone(),
+            signature: raw.signature.clone(),
+            parsed_signature: raw.parsed_signature.clone(),
+            has_default: raw.has_default,
+            default_body: raw.default_body.clone(),
+            default_body_tokens: raw.default_body_tokens.clone(),
+            is_unsafe: raw.is_unsafe,
+            is_const: raw.is_const,
+            is_async: raw.is_async,
+            is_dyn_compatible: raw.is_dyn_compatible,
+            docs: raw.docs.clone(),
+        }
+    }
+
+    /// Get the default implementation as simplified tokens. `None` for
+    /// required methods, or when bodies weren't extracted.
+    pub fn default_body_tokens(&self) -> Option<&[Token]> {
+        self.default_body_tokens.as_deref()
+    }
+}
+
 // ============================================================================
 // Type Alias Definition
 // ============================================================================
@@ -759,34 +1674,117 @@ pub struct TraitMethod {
 pub struct TypeAliasDef {
     pub path: String,
     pub resolved_path: String,
+    /// This alias, each alias it is defined in terms of, and finally the
+    /// expanded type
     pub resolution_chain: Vec<String>,
+    pub generics: Vec<GenericParam>,
     crate_name: String,
-    client: Arc<BronziteClient>,
+    client: Arc<Mutex<BronziteClient>>,
 }
 
 impl TypeAliasDef {
     /// Resolve this alias to its concrete type.
     pub fn resolve(&self) -> Result<Item> {
         // Get the final resolved type
-        let details = self
-            .client_mut()?
-            .get_type(&self.crate_name, &self.resolved_path)?;
+        let details = lock_client(&self.client).get_type(&self.crate_name, &self.resolved_path)?;
 
         let summary = TypeSummary {
             name: details.name.clone(),
             path: details.path.clone(),
             kind: details.kind.clone(),
             generics: details.generics.clone(),
+            crate_name: None,
         };
 
         Item::from_summary(summary, &self.crate_name, Arc::clone(&self.client))
     }
 
-    fn client_mut(&self) -> Result<&mut BronziteClient> {
-        unsafe {
-            let ptr = Arc::as_ptr(&self.client) as *mut BronziteClient;
-            Ok(&mut *ptr)
+    /// Resolve this alias with concrete arguments for its generic parameters.
+    ///
+    /// The arguments are substituted into the alias first, so the returned
+    /// item has the instantiated field types and layout of the type the alias
+    /// expands to.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // type Shared<T> = Wrapper<Arc<T>>;
+    /// let alias = krate.get_type_alias("Shared")?;
+    /// if let Item::Struct(s) = alias.resolve_with_args(&["u32"])? {
+    ///     assert!(s.fields()?[0].ty.starts_with("std::sync::Arc<u32"));
+    /// }
+    /// ```
+    pub fn resolve_with_args(&self, args: &[&str]) -> Result<Item> {
+        let details =
+            lock_client(&self.client).get_type_instantiated(&self.crate_name, &self.path, args)?;
+
+        match details.kind {
+            bronzite_types::TypeKind::Struct => Ok(Item::Struct(StructDef::from_details(
+                details,
+                &self.crate_name,
+                Arc::clone(&self.client),
+            )?)),
+            bronzite_types::TypeKind::Enum => Ok(Item::Enum(EnumDef::from_details(
+                details,
+                &self.crate_name,
+                Arc::clone(&self.client),
+            )?)),
+            _ => {
+                let summary = TypeSummary {
+                    name: details.name,
+                    path: details.path,
+                    kind: details.kind,
+                    generics: details.generics,
+                    crate_name: None,
+                };
+                Item::from_summary(summary, &self.crate_name, Arc::clone(&self.client))
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Trait Alias Definition
+// ============================================================================
+
+/// A reflected trait alias (`trait Shape = Area + Debug;`).
+#[derive(Debug, Clone)]
+pub struct TraitAliasDef {
+    pub name: String,
+    pub path: String,
+    /// The traits and lifetimes the alias stands for
+    pub bounds: Vec<String>,
+    pub generics: Vec<GenericParam>,
+    pub docs: Option<String>,
+    visibility: Visibility,
+    crate_name: String,
+    client: Arc<Mutex<BronziteClient>>,
+}
+
+impl TraitAliasDef {
+    /// Get the alias's visibility.
+    pub fn visibility(&self) -> &Visibility {
+        &self.visibility
+    }
+
+    /// Navigate to the definitions of the traits in the alias.
+    ///
+    /// Traits from other crates (like `Debug`) and lifetime bounds are
+    /// skipped.
+    pub fn traits(&self) -> Result<Vec<TraitDef>> {
+        let mut traits = Vec::new();
+        for bound in self.bounds.iter().filter(|b| !b.starts_with('\'')) {
+            let path = bound.split('<').next().unwrap_or(bound);
+            let found = lock_client(&self.client).get_trait(&self.crate_name, path);
+            if let Ok(details) = found {
+                traits.push(TraitDef::from_trait_details(
+                    details,
+                    &self.crate_name,
+                    Arc::clone(&self.client),
+                )?);
+            }
         }
+        Ok(traits)
     }
 }
 
@@ -831,12 +1829,27 @@ pub struct Field {
     pub offset: Option<usize>,
     /// Size in bytes (if layout is known)
     pub size: Option<usize>,
+    /// Crate defining the field's type, if it is a named type
+    pub defined_in_crate: Option<String>,
     crate_name: String,
-    client: Arc<BronziteClient>,
+    client: Arc<Mutex<BronziteClient>>,
+}
+
+/// Where a field's type is defined, see [`Field::type_origin`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeOrigin {
+    /// Defined in the queried crate, at this path
+    Local(String),
+    /// Defined in `std`, `core` or `alloc`
+    Std,
+    /// Defined in another crate
+    Dependency(String),
+    /// A primitive or structural type (reference, tuple, array, ...)
+    Primitive,
 }
 
 impl Field {
-    fn from_raw(raw: RawFieldInfo, crate_name: &str, client: Arc<BronziteClient>) -> Self {
+    fn from_raw(raw: RawFieldInfo, crate_name: &str, client: Arc<Mutex<BronziteClient>>) -> Self {
         Self {
             name: raw.name,
             index: raw.index,
@@ -846,11 +1859,57 @@ impl Field {
             docs: raw.docs,
             offset: raw.offset,
             size: raw.size,
+            defined_in_crate: raw.defined_in_crate,
             crate_name: crate_name.to_string(),
             client,
         }
     }
 
+    /// Whether this field is `pub`.
+    pub fn is_public(&self) -> bool {
+        matches!(self.visibility, Visibility::Public)
+    }
+
+    /// Whether code anywhere in the defining crate can access this field,
+    /// i.e. it is `pub` or `pub(crate)`.
+    pub fn is_accessible_from_crate(&self) -> bool {
+        matches!(self.visibility, Visibility::Public | Visibility::Crate)
+    }
+
+    /// Where this field's type is defined.
+    ///
+    /// Only [`TypeOrigin::Local`] types can be navigated to with
+    /// [`type_def`](Self::type_def).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // struct User { name: String, address: Address, age: u32 }
+    /// for field in user.fields()? {
+    ///     match field.type_origin() {
+    ///         TypeOrigin::Local(path) => println!("{} can be inspected", path),
+    ///         TypeOrigin::Std => println!("from std"),
+    ///         TypeOrigin::Dependency(krate) => println!("from {}", krate),
+    ///         TypeOrigin::Primitive => println!("built in"),
+    ///     }
+    /// }
+    /// ```
+    pub fn type_origin(&self) -> TypeOrigin {
+        let Some(krate) = &self.defined_in_crate else {
+            return TypeOrigin::Primitive;
+        };
+
+        match krate.as_str() {
+            "std" | "core" | "alloc" => TypeOrigin::Std,
+            _ if *krate == self.crate_name.replace('-', "_") => {
+                let ty = self.resolved_ty.as_ref().unwrap_or(&self.ty);
+                let path = ty.split('<').next().unwrap_or(ty);
+                TypeOrigin::Local(path.to_string())
+            }
+            _ => TypeOrigin::Dependency(krate.clone()),
+        }
+    }
+
     /// Navigate to the type definition for this field's type.
     ///
     /// Returns an [`Item`] representing the field's type definition, if it
@@ -879,15 +1938,26 @@ impl Field {
     /// - `Err(_)` - An error occurred querying the daemon
     pub fn type_def(&self) -> Result<Option<Item>> {
         let type_path = self.resolved_ty.as_ref().unwrap_or(&self.ty);
-
-        // Try to get type details
-        match self.client_mut()?.get_type(&self.crate_name, type_path) {
+        // Field types normally come with aliases expanded, but one may still
+        // be spelled as a local alias (e.g. under `lazy_type_alias`)
+        let found = lookup_type_through_alias(
+            &mut *lock_client(&self.client),
+            type_path,
+            |client, path| client.get_type(&self.crate_name, path),
+            |client, path| {
+                let (_, resolved, _) = client.resolve_alias(&self.crate_name, path)?;
+                Ok(resolved)
+            },
+        );
+
+        match found {
             Ok(details) => {
                 let summary = TypeSummary {
                     name: details.name.clone(),
                     path: details.path.clone(),
                     kind: details.kind.clone(),
                     generics: details.generics.clone(),
+                    crate_name: None,
                 };
                 Ok(Some(Item::from_summary(
                     summary,
@@ -899,11 +1969,23 @@ impl Field {
         }
     }
 
-    fn client_mut(&self) -> Result<&mut BronziteClient> {
-        unsafe {
-            let ptr = Arc::as_ptr(&self.client) as *mut BronziteClient;
-            Ok(&mut *ptr)
-        }
+    /// Get the structure of this field's type.
+    ///
+    /// Unlike [`type_def`](Self::type_def), this also describes tuples,
+    /// arrays, slices and references. Named types defined in the crate carry
+    /// a [`TypeSummary`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // struct Packet { header: [u8; 4], .. }
+    /// if let TypeStructure::Array { element, len } = field.type_structure()? {
+    ///     println!("{} elements of {:?}", len, element); // 4 elements of u8
+    /// }
+    /// ```
+    pub fn type_structure(&self) -> Result<TypeStructure> {
+        let ty = self.resolved_ty.as_ref().unwrap_or(&self.ty);
+        lock_client(&self.client).describe_type(&self.crate_name, ty)
     }
 }
 
@@ -914,19 +1996,29 @@ impl Field {
 /// A trait implementation block.
 #[derive(Debug, Clone)]
 pub struct TraitImpl {
+    /// The implementing type
+    pub self_ty: String,
+    /// The implementing type's generic arguments (e.g. `["u8"]` for
+    /// `impl Serialize for Wrapper<u8>`)
+    pub self_ty_args: Vec<String>,
     pub trait_path: String,
+    /// The trait's generic arguments (e.g. `["u64"]` for `impl From<u64>`)
+    pub trait_args: Vec<String>,
     pub generics: Vec<GenericParam>,
     pub is_unsafe: bool,
     pub source: Option<String>,
     raw: RawTraitImpl,
     crate_name: String,
-    client: Arc<BronziteClient>,
+    client: Arc<Mutex<BronziteClient>>,
 }
 
 impl TraitImpl {
-    fn from_raw(raw: RawTraitImpl, crate_name: &str, client: Arc<BronziteClient>) -> Self {
+    fn from_raw(raw: RawTraitImpl, crate_name: &str, client: Arc<Mutex<BronziteClient>>) -> Self {
         Self {
+            self_ty: raw.self_ty.clone(),
+            self_ty_args: raw.self_ty_args.clone(),
             trait_path: raw.trait_path.clone(),
+            trait_args: raw.trait_args.clone(),
             generics: raw.generics.clone(),
             is_unsafe: raw.is_unsafe,
             source: raw.source.clone(),
@@ -951,9 +2043,13 @@ impl TraitImpl {
     /// }
     /// ```
     pub fn trait_def(&self) -> Result<TraitDef> {
-        let details = self
-            .client_mut()?
-            .get_trait(&self.crate_name, &self.trait_path)?;
+        // `trait_path` includes generic args, e.g. `From<u64>`
+        let path = self
+            .trait_path
+            .split('<')
+            .next()
+            .unwrap_or(&self.trait_path);
+        let details = lock_client(&self.client).get_trait(&self.crate_name, path)?;
         TraitDef::from_trait_details(details, &self.crate_name, Arc::clone(&self.client))
     }
 
@@ -966,6 +2062,80 @@ impl TraitImpl {
             .collect()
     }
 
+    /// Get every method callable through this impl: the methods it defines,
+    /// followed by the trait's default methods it inherits.
+    ///
+    /// Each method is paired with `true` if the impl defines it itself and
+    /// `false` if it comes from the trait's default. Inherited methods carry
+    /// the trait's default body.
+    ///
+    /// The defaults are only known for traits of the reflected crate. For a
+    /// foreign trait like `Display` this returns just the impl's own
+    /// methods; their [`overrides_default`](Method::overrides_default) still
+    /// tells which of them replace a default.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for (method, is_overridden) in impl_block.effective_methods()? {
+    ///     let origin = if is_overridden { "impl" } else { "default" };
+    ///     println!("{} ({})", method.name, origin);
+    /// }
+    /// ```
+    pub fn effective_methods(&self) -> Result<Vec<(Method, bool)>> {
+        let mut methods: Vec<(Method, bool)> =
+            self.methods().into_iter().map(|m| (m, true)).collect();
+
+        let trait_def = match self.trait_def() {
+            Ok(trait_def) => trait_def,
+            // Not a trait of this crate
+            Err(crate::Error::DaemonError(_)) => return Ok(methods),
+            Err(error) => return Err(error),
+        };
+        let Some(details) = trait_def.cached_details else {
+            return Ok(methods);
+        };
+        let inherited: Vec<&TraitMethodInfo> = details
+            .methods
+            .iter()
+            .filter(|m| m.has_default && !methods.iter().any(|(own, _)| own.name == m.name))
+            .collect();
+        for m in inherited {
+            let method = Method {
+                name: m.name.clone(),
+                signature: m.signature.clone(),
+                parsed_signature: m.parsed_signature.clone(),
+                body_source: m.default_body.clone(),
+                body_tokens: m.default_body_tokens.clone(),
+                is_unsafe: m.is_unsafe,
+                is_const: m.is_const,
+                is_async: m.is_async,
+                docs: m.docs.clone(),
+                path: format!("<{} as {}>::{}", self.self_ty, self.trait_path, m.name),
+                // Trait items share the trait's visibility
+                visibility: details.visibility.clone(),
+                is_method: m.parsed_signature.receiver.is_some(),
+                overrides_default: false,
+                crate_name: self.crate_name.clone(),
+                client: Arc::clone(&self.client),
+            };
+            methods.push((method, false));
+        }
+        Ok(methods)
+    }
+
+    /// Get the signature of a trait method as this impl provides it, with
+    /// `Self` and associated types resolved. Also works for default methods
+    /// that aren't part of [`methods`](Self::methods).
+    pub fn method_signature(&self, name: &str) -> Result<FunctionSignature> {
+        lock_client(&self.client).impl_method_signature(
+            &self.crate_name,
+            &self.self_ty,
+            &self.trait_path,
+            name,
+        )
+    }
+
     /// Get associated types in this impl.
     pub fn associated_types(&self) -> &[AssocTypeInfo] {
         &self.raw.assoc_types
@@ -976,11 +2146,38 @@ impl TraitImpl {
         &self.raw.assoc_consts
     }
 
-    fn client_mut(&self) -> Result<&mut BronziteClient> {
-        unsafe {
-            let ptr = Arc::as_ptr(&self.client) as *mut BronziteClient;
-            Ok(&mut *ptr)
-        }
+    /// Get an associated constant of this impl by name, with its evaluated
+    /// value.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // impl Versioned for Packet { const VERSION: u32 = 3; }
+    /// let version = packet_impl.assoc_const("VERSION").and_then(|c| c.value.as_deref());
+    /// assert_eq!(version, Some("3"));
+    /// ```
+    pub fn assoc_const(&self, name: &str) -> Option<&AssocConstInfo> {
+        self.raw.assoc_consts.iter().find(|c| c.name == name)
+    }
+
+    /// Get the doc comments on the impl block.
+    pub fn docs(&self) -> Option<&str> {
+        self.raw.docs.as_deref()
+    }
+
+    /// Get the attributes on the impl block.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Skip impls generated by `#[derive]`
+    /// let handwritten = user
+    ///     .trait_impls()?
+    ///     .into_iter()
+    ///     .filter(|i| !i.attributes().iter().any(|a| a == "#[automatically_derived]"));
+    /// ```
+    pub fn attributes(&self) -> &[String] {
+        &self.raw.attributes
     }
 }
 
@@ -1022,6 +2219,8 @@ pub struct Method {
     pub parsed_signature: FunctionSignature,
     /// Method body source code (if available)
     pub body_source: Option<String>,
+    /// Method body as simplified tokens (if available)
+    pub body_tokens: Option<Vec<Token>>,
     /// Whether this is an unsafe method
     pub is_unsafe: bool,
     /// Whether this is a const method
@@ -1030,26 +2229,127 @@ pub struct Method {
     pub is_async: bool,
     /// Doc comments
     pub docs: Option<String>,
+    path: String,
+    visibility: Visibility,
+    is_method: bool,
+    overrides_default: bool,
     crate_name: String,
-    client: Arc<BronziteClient>,
+    client: Arc<Mutex<BronziteClient>>,
 }
 
 impl Method {
-    fn from_raw(raw: RawMethodDetails, crate_name: &str, client: Arc<BronziteClient>) -> Self {
+    fn from_raw(
+        raw: RawMethodDetails,
+        crate_name: &str,
+        client: Arc<Mutex<BronziteClient>>,
+    ) -> Self {
         Self {
             name: raw.name,
             signature: raw.signature,
             parsed_signature: raw.parsed_signature,
             body_source: raw.body_source,
+            body_tokens: raw.body_tokens,
             is_unsafe: raw.is_unsafe,
             is_const: raw.is_const,
             is_async: raw.is_async,
             docs: raw.docs,
+            path: raw.path,
+            visibility: raw.visibility,
+            is_method: raw.is_method,
+            overrides_default: raw.overrides_default,
             crate_name: crate_name.to_string(),
             client,
         }
     }
 
+    /// Get the method's visibility.
+    pub fn visibility(&self) -> &Visibility {
+        &self.visibility
+    }
+
+    /// Check whether this takes a `self` receiver (e.g. `user.is_active()`).
+    pub fn is_method(&self) -> bool {
+        self.is_method
+    }
+
+    /// How this method takes `self`; `None` for associated functions.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mutators: Vec<_> = user
+    ///     .methods()?
+    ///     .into_iter()
+    ///     .filter(|m| m.receiver_kind() == Some(&ReceiverKind::RefMut))
+    ///     .collect();
+    /// ```
+    pub fn receiver_kind(&self) -> Option<&ReceiverKind> {
+        self.parsed_signature
+            .receiver
+            .as_ref()
+            .map(|receiver| &receiver.receiver_kind)
+    }
+
+    /// Guess whether this method is a getter, setter or constructor.
+    ///
+    /// A getter takes `&self` and nothing else and returns a value, a
+    /// setter is a `set_*` method taking `&mut self` and one value and
+    /// returning nothing, and a constructor has no receiver and returns the
+    /// implementing type.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let setters: Vec<_> = user
+    ///     .methods()?
+    ///     .into_iter()
+    ///     .filter(|m| m.classify() == MethodRole::Setter)
+    ///     .collect();
+    /// ```
+    pub fn classify(&self) -> MethodRole {
+        MethodRole::classify(&self.path, &self.parsed_signature)
+    }
+
+    /// Check whether this is an associated function without a receiver
+    /// (e.g. `User::new(...)`).
+    pub fn is_associated_fn(&self) -> bool {
+        !self.is_method
+    }
+
+    /// Get the method body as simplified tokens, for analyzing its control
+    /// flow. `None` when bodies weren't extracted.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use bronzite_client::Token;
+    ///
+    /// let returns_early = method
+    ///     .body_tokens()
+    ///     .is_some_and(|tokens| tokens.iter().any(|t| matches!(t, Token::Return { .. })));
+    /// ```
+    pub fn body_tokens(&self) -> Option<&[Token]> {
+        self.body_tokens.as_deref()
+    }
+
+    /// Get the functions and methods this method calls, for building a call
+    /// graph. Empty when bodies weren't extracted.
+    ///
+    /// See [`called_paths`](crate::called_paths) for what is (and isn't)
+    /// detected.
+    pub fn called_functions(&self) -> Vec<String> {
+        self.body_tokens
+            .as_deref()
+            .map(crate::called_paths)
+            .unwrap_or_default()
+    }
+
+    /// Check whether this trait impl method overrides a default provided by
+    /// the trait. Always false for required and inherent methods.
+    pub fn overrides_default(&self) -> bool {
+        self.overrides_default
+    }
+
     /// Navigate to the return type definition.
     ///
     /// Returns an [`Item`] representing the method's return type definition,
@@ -1073,33 +2373,15 @@ impl Method {
     /// - `Ok(None)` - No return type, or primitive/external type
     /// - `Err(_)` - An error occurred querying the daemon
     pub fn return_type_def(&self) -> Result<Option<Item>> {
-        if let Some(return_ty) = &self.parsed_signature.return_ty {
-            match self.client_mut()?.get_type(&self.crate_name, return_ty) {
-                Ok(details) => {
-                    let summary = TypeSummary {
-                        name: details.name.clone(),
-                        path: details.path.clone(),
-                        kind: details.kind.clone(),
-                        generics: details.generics.clone(),
-                    };
-                    Ok(Some(Item::from_summary(
-                        summary,
-                        &self.crate_name,
-                        Arc::clone(&self.client),
-                    )?))
-                }
-                Err(_) => Ok(None),
-            }
-        } else {
-            Ok(None)
-        }
+        signature_return_type_def(&self.parsed_signature, &self.crate_name, &self.client)
     }
 
     /// Navigate to parameter type definitions.
     ///
-    /// Returns a vector of optional [`Item`]s, one for each parameter.
-    /// Each entry is `Some(Item)` if the type can be resolved, or `None`
-    /// for primitive/external types.
+    /// Returns a [`ParamTypeRef`] for each parameter. References are looked
+    /// through, so `&User` and `&mut User` resolve to `User`, and so is the
+    /// first type argument of external wrappers like `Vec<User>` or
+    /// `Option<Box<User>>`.
     ///
     /// # Example
     ///
@@ -1107,48 +2389,286 @@ impl Method {
     /// let user = krate.get_struct("User")?;
     /// for method in user.methods()? {
     ///     println!("Method: {}", method.name);
-    ///     for (i, param_type) in method.param_types()?.into_iter().enumerate() {
-    ///         if let Some(ptype) = param_type {
-    ///             println!("  Param {}: {}", i, ptype.name());
+    ///     for (i, param) in method.param_types()?.into_iter().enumerate() {
+    ///         if let Some(ptype) = &param.item {
+    ///             let by = if param.is_mut { "&mut " } else if param.is_ref { "&" } else { "" };
+    ///             println!("  Param {}: {}{}", i, by, ptype.name());
     ///         }
     ///     }
     /// }
     /// ```
+    pub fn param_types(&self) -> Result<Vec<ParamTypeRef>> {
+        signature_param_types(&self.parsed_signature, &self.crate_name, &self.client)
+    }
+}
+
+// ============================================================================
+// Function Definition
+// ============================================================================
+
+/// A reflected free function.
+///
+/// # Example
+///
+/// ```ignore
+/// let handler = krate.get_function("handlers::on_start")?;
+/// println!("{} is async: {}", handler.name, handler.is_async);
+/// if let Some(ret) = handler.return_type_def()? {
+///     println!("returns {}", ret.path());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FunctionDef {
+    /// Function name
+    pub name: String,
+    /// Full path to the function
+    pub path: String,
+    /// Full signature as a string
+    pub signature: String,
+    /// Parsed signature components
+    pub parsed_signature: FunctionSignature,
+    /// Function body source code (if available)
+    pub body_source: Option<String>,
+    /// Whether this is an unsafe function
+    pub is_unsafe: bool,
+    /// Whether this is a const function
+    pub is_const: bool,
+    /// Whether this is an async function
+    pub is_async: bool,
+    /// Doc comments
+    pub docs: Option<String>,
+    visibility: Visibility,
+    crate_name: String,
+    client: Arc<Mutex<BronziteClient>>,
+}
+
+impl FunctionDef {
+    fn from_details(
+        details: FunctionDetails,
+        crate_name: &str,
+        client: Arc<Mutex<BronziteClient>>,
+    ) -> Self {
+        Self {
+            name: details.name,
+            path: details.path,
+            signature: details.signature,
+            parsed_signature: details.parsed_signature,
+            body_source: details.body_source,
+            is_unsafe: details.is_unsafe,
+            is_const: details.is_const,
+            is_async: details.is_async,
+            docs: details.docs,
+            visibility: details.visibility,
+            crate_name: crate_name.to_string(),
+            client,
+        }
+    }
+
+    /// Get the function's visibility.
+    pub fn visibility(&self) -> &Visibility {
+        &self.visibility
+    }
+
+    /// Navigate to the return type definition.
     ///
-    /// # Returns
+    /// Returns `None` if the function returns `()`, or if the type is
+    /// primitive or external.
+    pub fn return_type_def(&self) -> Result<Option<Item>> {
+        signature_return_type_def(&self.parsed_signature, &self.crate_name, &self.client)
+    }
+
+    /// Navigate to parameter type definitions, one entry per parameter.
     ///
-    /// A vector where each element corresponds to a parameter:
-    /// - `Some(Item)` - The parameter type was found
-    /// - `None` - The type is primitive or external
-    pub fn param_types(&self) -> Result<Vec<Option<Item>>> {
-        self.parsed_signature
-            .params
-            .iter()
-            .map(
-                |param| match self.client_mut()?.get_type(&self.crate_name, &param.ty) {
-                    Ok(details) => {
-                        let summary = TypeSummary {
-                            name: details.name.clone(),
-                            path: details.path.clone(),
-                            kind: details.kind.clone(),
-                            generics: details.generics.clone(),
-                        };
-                        Ok(Some(Item::from_summary(
-                            summary,
-                            &self.crate_name,
-                            Arc::clone(&self.client),
-                        )?))
-                    }
-                    Err(_) => Ok(None),
-                },
-            )
-            .collect()
+    /// See [`Method::param_types`] for how parameter types are resolved.
+    pub fn param_types(&self) -> Result<Vec<ParamTypeRef>> {
+        signature_param_types(&self.parsed_signature, &self.crate_name, &self.client)
     }
+}
+
+/// A parameter type resolved by `param_types`.
+#[derive(Debug, Clone)]
+pub struct ParamTypeRef {
+    /// Whether the parameter is passed by reference
+    pub is_ref: bool,
+    /// Whether that reference is `&mut`
+    pub is_mut: bool,
+    /// The referenced (or wrapped) type, if it is defined in the crate
+    pub item: Option<Item>,
+}
+
+/// Look up the type at `path` with `get_type`, and if that fails, expand
+/// `path` as a local type alias with `resolve_alias` and look up the result.
+/// A failed alias lookup reports the original error, since `path` wasn't an
+/// alias either.
+pub(crate) fn lookup_type_through_alias<C, T, E>(
+    client: &mut C,
+    path: &str,
+    get_type: impl Fn(&mut C, &str) -> std::result::Result<T, E>,
+    resolve_alias: impl Fn(&mut C, &str) -> std::result::Result<String, E>,
+) -> std::result::Result<T, E> {
+    get_type(client, path).or_else(|e| {
+        let resolved = resolve_alias(client, path).map_err(|_| e)?;
+        get_type(client, &resolved)
+    })
+}
 
-    fn client_mut(&self) -> Result<&mut BronziteClient> {
-        unsafe {
-            let ptr = Arc::as_ptr(&self.client) as *mut BronziteClient;
-            Ok(&mut *ptr)
+/// Look up a type named in a signature, returning `None` for primitive and
+/// external types.
+fn signature_type_def(
+    ty: &str,
+    crate_name: &str,
+    client: &Arc<Mutex<BronziteClient>>,
+) -> Result<Option<Item>> {
+    let found = lock_client(client).get_type(crate_name, ty);
+    match found {
+        Ok(details) => {
+            let summary = TypeSummary {
+                name: details.name.clone(),
+                path: details.path.clone(),
+                kind: details.kind.clone(),
+                generics: details.generics.clone(),
+                crate_name: None,
+            };
+            Ok(Some(Item::from_summary(
+                summary,
+                crate_name,
+                Arc::clone(client),
+            )?))
         }
+        Err(_) => Ok(None),
+    }
+}
+
+fn signature_return_type_def(
+    sig: &FunctionSignature,
+    crate_name: &str,
+    client: &Arc<Mutex<BronziteClient>>,
+) -> Result<Option<Item>> {
+    match sig.return_ty.as_ref().and_then(|ret| ret.ty()) {
+        Some(return_ty) => signature_type_def(return_ty, crate_name, client),
+        None => Ok(None),
+    }
+}
+
+fn signature_param_types(
+    sig: &FunctionSignature,
+    crate_name: &str,
+    client: &Arc<Mutex<BronziteClient>>,
+) -> Result<Vec<ParamTypeRef>> {
+    sig.params
+        .iter()
+        .map(|param| {
+            let ty = TypeStructure::parse(&param.ty);
+            let (is_ref, is_mut) = match ty {
+                TypeStructure::Reference { mutable, .. } => (true, mutable),
+                _ => (false, false),
+            };
+
+            // Look through references and wrappers until a type of this
+            // crate turns up
+            let mut item = None;
+            for path in ty.wrapped_paths() {
+                item = signature_type_def(path, crate_name, client)?;
+                if item.is_some() {
+                    break;
+                }
+            }
+
+            Ok(ParamTypeRef {
+                is_ref,
+                is_mut,
+                item,
+            })
+        })
+        .collect()
+}
+
+/// Paths that may name a type inside a type string, e.g. `Node` and
+/// `std::boxed::Box` in `std::option::Option<std::boxed::Box<Node>>`.
+///
+/// Lifetimes, keywords and std paths are skipped, as they never name a type
+/// in the queried crate.
+fn type_paths_in(ty: &str) -> impl Iterator<Item = &str> {
+    ty.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':' || c == '\''))
+        .filter(|token| {
+            !token.is_empty()
+                && !token.starts_with(|c: char| c == '\'' || c == ':' || c.is_ascii_digit())
+                && !matches!(*token, "mut" | "const" | "dyn" | "impl" | "as" | "for")
+                && !["std::", "core::", "alloc::"]
+                    .iter()
+                    .any(|prefix| token.starts_with(prefix))
+        })
+}
+
+// ============================================================================
+// Const Definition
+// ============================================================================
+
+/// A reflected top-level `const` or `static` item.
+///
+/// # Example
+///
+/// ```ignore
+/// // pub const MAX_USERS: usize = 16 * 4;
+/// let max = krate.get_const("MAX_USERS")?;
+/// assert_eq!(max.ty, "usize");
+/// assert_eq!(max.value.as_deref(), Some("64"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConstDef {
+    /// Const name
+    pub name: String,
+    /// Full path to the const
+    pub path: String,
+    /// Whether this is a `static` rather than a `const`
+    pub is_static: bool,
+    /// The declared type
+    pub ty: String,
+    /// The evaluated value, if it could be evaluated. Always `None` for
+    /// statics
+    pub value: Option<String>,
+    /// The initializer expression as written
+    pub init_source: Option<String>,
+    /// Doc comments
+    pub docs: Option<String>,
+    /// The full source of the item
+    pub source: Option<String>,
+    visibility: Visibility,
+    crate_name: String,
+    client: Arc<Mutex<BronziteClient>>,
+}
+
+impl ConstDef {
+    fn from_details(
+        details: ConstDetails,
+        crate_name: &str,
+        client: Arc<Mutex<BronziteClient>>,
+    ) -> Self {
+        Self {
+            name: details.name,
+            path: details.path,
+            is_static: details.is_static,
+            ty: details.ty,
+            value: details.value,
+            init_source: details.init_source,
+            docs: details.docs,
+            source: details.source,
+            visibility: details.visibility,
+            crate_name: crate_name.to_string(),
+            client,
+        }
+    }
+
+    /// Get the const's visibility.
+    pub fn visibility(&self) -> &Visibility {
+        &self.visibility
+    }
+
+    /// Navigate to the definition of the const's type.
+    ///
+    /// Returns `None` if the type is primitive or external.
+    pub fn type_def(&self) -> Result<Option<Item>> {
+        let path = self.ty.split('<').next().unwrap_or(&self.ty);
+        signature_type_def(path, &self.crate_name, &self.client)
     }
 }
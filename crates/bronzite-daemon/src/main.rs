@@ -4,27 +4,43 @@
 //! with `--extract` mode, caches the extracted type information, and serves
 //! queries from proc-macros over a Unix socket.
 //!
+//! Extraction is lazy: the first query for a crate builds an item index
+//! (`--index-only`), along with details for the items that query touches
+//! (`--detail <path>`). Details for other items are extracted the first time
+//! a query touches them (`--filter <path>`), and crate-wide queries such as
+//! `GetTraits` trigger a single full extraction.
+//!
+//! Every client connection is served on its own thread. Queries for the same
+//! crate take turns, so a crate is never compiled twice at once. Queries for
+//! other crates don't wait on that, although all crates build into the same
+//! target directory, so cargo may still serialize their compiles. Queries
+//! that wait for a compile hand what they need to the query running it, so
+//! one run covers all of them.
+//!
 //! # Daemon Auto-Start
 //!
 //! The daemon supports an `--ensure` mode for use by proc-macros:
 //! - If a daemon is already running, it exits immediately with success
 //! - If no daemon is running, it spawns one in the background and waits for it to be ready
 
-use std::collections::HashMap;
-use std::io::{BufRead, BufReader, Write};
-use std::path::PathBuf;
-use std::process::{Command, Stdio};
-use std::sync::mpsc::{self, Receiver, Sender};
-use std::sync::{Arc, Mutex};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::{Arc, Mutex, MutexGuard, TryLockError};
 use std::thread;
 use std::time::{Duration, Instant};
 
 #[cfg(unix)]
 use std::os::unix::net::{UnixListener, UnixStream};
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
 
 use bronzite_types::{
-    CrateTypeInfo, InherentImplDetails, Query, QueryData, QueryResult, Request, Response,
-    TraitImplDetails, TraitInfo, TypeSummary,
+    CrateTypeInfo, FunctionDetails, InherentImplDetails, ItemKind, MethodCandidate, ModuleTree,
+    PROTOCOL_VERSION, Query, QueryData, QueryResult, Request, Response, TOOLCHAIN, TraitDetails,
+    TraitImplDetails, TraitInfo, TypeAliasInfo, TypeSummary, normalize_crate_name, sort_by_path,
+    trait_path_matches, type_path_matches,
 };
 use clap::Parser;
 
@@ -37,7 +53,8 @@ struct Args {
     #[arg(short, long)]
     manifest_path: Option<PathBuf>,
 
-    /// Socket path for IPC
+    /// Socket path for IPC [default: $BRONZITE_SOCKET, or bronzite.sock in
+    /// the temp directory]
     #[arg(short, long)]
     socket: Option<PathBuf>,
 
@@ -57,35 +74,324 @@ struct Args {
     /// Timeout in seconds when using --ensure to wait for daemon to be ready
     #[arg(long, default_value = "30")]
     ensure_timeout: u64,
+
+    /// Only extract items matching this path pattern (e.g. "models::**").
+    /// Extraction is unfiltered by default so the cache is complete; only set
+    /// this when every client of this daemon is known to want a subtree.
+    #[arg(long)]
+    filter: Option<String>,
+
+    /// Number of rustc frontend threads used during extraction
+    #[arg(long)]
+    threads: Option<usize>,
+
+    /// Reflect a single `.rs` file (compiled directly with rustc) instead of
+    /// a cargo workspace. The crate is named after the file.
+    #[arg(long)]
+    file: Option<PathBuf>,
+
+    /// Rust edition to compile `--file` with
+    #[arg(long, default_value = "2021")]
+    edition: String,
+
+    /// Seconds to let a single crate compile run before killing it
+    #[arg(long, default_value = "300")]
+    compile_timeout: u64,
+
+    /// Directory for build artifacts. Defaults to `bronzite/` under
+    /// `$CARGO_TARGET_DIR` if set, or under the workspace's `target/`.
+    #[arg(long)]
+    target_dir: Option<PathBuf>,
 }
 
-/// Message sent to the cache manager thread
-enum CacheMessage {
-    Query {
-        crate_name: String,
-        query: Query,
-        response_tx: Sender<QueryResult>,
-    },
-    InvalidateCache {
-        crate_name: String,
-    },
-    Shutdown,
+/// What a single run of the query plugin should extract
+enum ExtractScope {
+    /// The item index, plus details for items matching these patterns
+    Index(Vec<String>),
+    /// Details for items whose path matches any of these patterns
+    Items(Vec<String>),
+    /// Details for the whole crate
+    Crate,
+}
+
+/// The part of a crate's details a query reads.
+enum QueryNeed {
+    /// Nothing beyond the item index
+    Nothing,
+    /// Every item matching a path pattern
+    Pattern(String),
+    /// A single item, by full or suffix path
+    Item(String),
+    /// A single item, by stable id
+    Id(String),
+    /// Details for the whole crate
+    Crate,
+}
+
+impl QueryNeed {
+    /// Anything that isn't listed here needs the whole crate, so a new query
+    /// is answered correctly before it learns to extract less.
+    fn of(query: &Query) -> Self {
+        match query {
+            Query::ListItems
+            | Query::GetCrateMeta
+            | Query::GetModuleTree
+            | Query::Ping
+            | Query::Version
+            | Query::Shutdown
+            | Query::ListCachedCrates
+            | Query::GetTypeInstantiated { .. }
+            | Query::CheckConvertible { .. }
+            | Query::CheckImplFor { .. }
+            | Query::CheckSized { .. }
+            | Query::GetSpanText { .. }
+            | Query::GetImplMethodSignature { .. } => QueryNeed::Nothing,
+            // The plugin's `--filter` is case-sensitive
+            Query::FindTypes { options, .. }
+            | Query::StreamTypes { options, .. }
+            | Query::FindFunctions { options, .. }
+                if options.case_insensitive =>
+            {
+                QueryNeed::Crate
+            }
+            Query::FindTypes { pattern, .. }
+            | Query::StreamTypes { pattern, .. }
+            | Query::FindFunctions { pattern, .. }
+            | Query::GetTypeAliases { pattern } => QueryNeed::Pattern(pattern.clone()),
+            Query::GetTypeById { id } => QueryNeed::Id(id.clone()),
+            Query::GetType { path }
+            | Query::GetTypeFull { path }
+            | Query::GetTrait { path }
+            | Query::GetTraitAlias { path }
+            | Query::GetFunction { path }
+            | Query::GetMacro { path }
+            | Query::GetConst { path }
+            | Query::ResolveAlias { path }
+            | Query::GetTraitImpls { type_path: path }
+            | Query::GetInherentImpls { type_path: path }
+            | Query::GetFields { type_path: path }
+            | Query::GetLayout { type_path: path }
+            | Query::GetVariantField {
+                enum_path: path, ..
+            }
+            | Query::CheckImpl {
+                type_path: path, ..
+            }
+            | Query::ResolveMethod {
+                type_path: path, ..
+            } => {
+                // Generic arguments (`Baz<i32>`) aren't part of item paths
+                QueryNeed::Item(path.split('<').next().unwrap_or(path).to_string())
+            }
+            _ => QueryNeed::Crate,
+        }
+    }
+
+    /// Patterns to extract details for while the crate is being indexed.
+    ///
+    /// Without the index a suffix path can't be resolved, so every item it
+    /// could refer to is included.
+    fn index_details(&self) -> Vec<String> {
+        match self {
+            QueryNeed::Pattern(pattern) => vec![pattern.clone()],
+            QueryNeed::Item(path) => vec![path.clone(), format!("*::{}", path)],
+            QueryNeed::Nothing | QueryNeed::Id(_) | QueryNeed::Crate => Vec::new(),
+        }
+    }
+}
+
+/// Cached type information for one crate.
+///
+/// An entry starts out as an item index and accumulates details as queries
+/// touch individual items.
+struct CacheEntry {
+    info: CrateTypeInfo,
+    /// Whether `info` holds details for every item
+    complete: bool,
+    /// Patterns and item paths whose details have already been merged into
+    /// `info`
+    extracted: HashSet<String>,
+    /// `info` without its non-public parts, built on first use and dropped
+    /// whenever `info` changes
+    public_info: Option<CrateTypeInfo>,
+}
+
+impl CacheEntry {
+    fn new(index: CrateTypeInfo) -> Self {
+        Self {
+            info: index,
+            complete: false,
+            extracted: HashSet::new(),
+            public_info: None,
+        }
+    }
+
+    /// Work out what still has to be extracted to answer all of `queries`
+    /// with one run of the plugin.
+    fn missing(&self, queries: &[Query]) -> Option<ExtractScope> {
+        if self.complete {
+            return None;
+        }
+
+        let mut patterns = Vec::new();
+        for query in queries {
+            let pattern = match QueryNeed::of(query) {
+                QueryNeed::Nothing => continue,
+                QueryNeed::Crate => return Some(ExtractScope::Crate),
+                QueryNeed::Pattern(pattern) => pattern,
+                // Ids of items that aren't indexed can't be resolved
+                QueryNeed::Id(id) => match self.info.items.iter().find(|i| i.stable_id == id) {
+                    Some(item) => item.path.clone(),
+                    None => return Some(ExtractScope::Crate),
+                },
+                QueryNeed::Item(path) => match self.resolve(&path) {
+                    Some(path) => path,
+                    // Ambiguous or not a local item (e.g. impls on `i32`)
+                    None => return Some(ExtractScope::Crate),
+                },
+            };
+            if !self.extracted.contains(&pattern) && !patterns.contains(&pattern) {
+                patterns.push(pattern);
+            }
+        }
+
+        (!patterns.is_empty()).then_some(ExtractScope::Items(patterns))
+    }
+
+    /// Resolve a (possibly suffix) path against the index, so the filter
+    /// selects the same item the query lookup will find.
+    fn resolve(&self, path: &str) -> Option<String> {
+        let suffix = format!("::{}", path);
+        let mut candidates = self.info.items.iter().filter(|item| {
+            matches!(
+                item.kind,
+                ItemKind::Struct
+                    | ItemKind::Enum
+                    | ItemKind::Union
+                    | ItemKind::Trait
+                    | ItemKind::TypeAlias
+                    | ItemKind::Function
+                    | ItemKind::Macro
+                    | ItemKind::Const
+                    | ItemKind::Static
+                    | ItemKind::TraitAlias
+            ) && (item.path == path || item.path.ends_with(&suffix))
+        });
+
+        match (candidates.next(), candidates.next()) {
+            (Some(item), None) => Some(item.path.clone()),
+            _ => None,
+        }
+    }
+
+    /// Record that details were extracted for `patterns`, and so for every
+    /// indexed item they match.
+    fn mark_extracted(&mut self, patterns: &[String]) {
+        for item in &self.info.items {
+            if patterns
+                .iter()
+                .any(|pattern| bronzite_types::path_matches_pattern(&item.path, pattern))
+            {
+                self.extracted.insert(item.path.clone());
+            }
+        }
+        self.extracted.extend(patterns.iter().cloned());
+    }
+
+    /// Merge the details from a filtered extraction into this entry.
+    fn merge(&mut self, partial: CrateTypeInfo) {
+        self.public_info = None;
+        self.info.types.extend(partial.types);
+        self.info.traits.extend(partial.traits);
+        // Filtering keeps every impl of a matching self type, so whole keys
+        // can be replaced
+        self.info.trait_impls.extend(partial.trait_impls);
+        self.info.inherent_impls.extend(partial.inherent_impls);
+        self.info.functions.extend(partial.functions);
+        self.info.macros.extend(partial.macros);
+        self.info.consts.extend(partial.consts);
+        self.info.type_aliases.extend(partial.type_aliases);
+        self.info.trait_aliases.extend(partial.trait_aliases);
+        self.info.layouts.extend(partial.layouts);
+        for warning in partial.warnings {
+            if !self.info.warnings.contains(&warning) {
+                self.info.warnings.push(warning);
+            }
+        }
+    }
 }
 
-/// Cache manager that holds extracted type information
+/// Cache manager settings taken from the command line
+struct CacheConfig {
+    workspace_dir: Option<PathBuf>,
+    file: Option<PathBuf>,
+    edition: String,
+    filter: Option<String>,
+    threads: Option<usize>,
+    compile_timeout: Duration,
+    target_dir: Option<PathBuf>,
+    verbose: bool,
+}
+
+/// Everything the daemon keeps for one crate.
+///
+/// Each crate has its own lock, held for the whole of a query. A crate is
+/// therefore only compiled by one query at a time, and queries for other
+/// crates don't queue behind it. If a query panics while holding the lock,
+/// the next one resets the state rather than failing.
+#[derive(Default)]
+struct CrateState {
+    /// Extracted type information, once the crate has been indexed
+    entry: Option<CacheEntry>,
+    /// Cached results of live queries, keyed by query JSON
+    live_results: HashMap<String, QueryResult>,
+}
+
+/// Runs one extraction for a crate: the crate's name and directory, what to
+/// extract and whether to skip bodies.
+type ExtractFn = Box<
+    dyn Fn(&CacheManager, &str, &Path, &ExtractScope, bool) -> Result<CrateTypeInfo, String>
+        + Send
+        + Sync,
+>;
+
+/// Cache manager that holds extracted type information. It is shared by
+/// all client threads.
 struct CacheManager {
-    /// Cached type information per crate
-    cache: HashMap<String, CrateTypeInfo>,
+    /// State per crate. The map itself is only locked to look a crate up
+    crates: Mutex<HashMap<String, Arc<Mutex<CrateState>>>>,
+    /// Names of the crates that have been indexed, readable without waiting
+    /// for a crate's lock while it compiles
+    indexed: Mutex<BTreeSet<String>>,
+    /// Queries waiting for a crate's lock, keyed by crate name. The query
+    /// holding the lock extracts what they need in the same run
+    waiting: Mutex<HashMap<String, Vec<Query>>>,
+    /// Runs the query plugin. Tests replace it to count compiles
+    extract: ExtractFn,
     /// Path to the cargo-bronzite-query binary
     query_binary: PathBuf,
-    /// Working directory for compilation
+    /// Workspace root (defaults to the current directory)
     workspace_dir: Option<PathBuf>,
+    /// Directory of each workspace member, keyed by crate name
+    crate_dirs: Mutex<HashMap<String, PathBuf>>,
+    /// Single source file to reflect instead of a cargo workspace
+    file: Option<PathBuf>,
+    /// Edition the single source file is compiled with
+    edition: String,
+    /// Path pattern passed to the plugin's `--filter` (None extracts everything)
+    filter: Option<String>,
+    /// Frontend thread count passed to the plugin's `--threads`
+    threads: Option<usize>,
+    /// How long a single compile may run before it is killed
+    compile_timeout: Duration,
+    /// Build directory override from `--target-dir`
+    target_dir: Option<PathBuf>,
     /// Verbose logging
     verbose: bool,
 }
 
 impl CacheManager {
-    fn new(workspace_dir: Option<PathBuf>, verbose: bool) -> Self {
+    fn new(config: CacheConfig) -> Self {
         // Find the bronzite-query binary
         let query_binary = std::env::current_exe()
             .ok()
@@ -94,36 +400,296 @@ impl CacheManager {
             .unwrap_or_else(|| PathBuf::from("cargo-bronzite-query"));
 
         Self {
-            cache: HashMap::new(),
+            crates: Mutex::new(HashMap::new()),
+            indexed: Mutex::new(BTreeSet::new()),
+            waiting: Mutex::new(HashMap::new()),
+            extract: Box::new(CacheManager::compile_and_extract),
             query_binary,
-            workspace_dir,
-            verbose,
+            workspace_dir: config.workspace_dir,
+            crate_dirs: Mutex::new(HashMap::new()),
+            file: config.file,
+            edition: config.edition,
+            filter: config.filter,
+            threads: config.threads,
+            compile_timeout: config.compile_timeout,
+            target_dir: config.target_dir,
+            verbose: config.verbose,
+        }
+    }
+
+    /// The state of `crate_name`, created empty on first use.
+    fn crate_state(&self, crate_name: &str) -> Arc<Mutex<CrateState>> {
+        let mut crates = self.crates.lock().unwrap();
+        Arc::clone(crates.entry(crate_name.to_string()).or_default())
+    }
+
+    fn get_or_compile<'a>(
+        &self,
+        state: &'a mut CrateState,
+        crate_name: &str,
+        query: &Query,
+        skip_bodies: bool,
+        include_private: bool,
+    ) -> Result<&'a CrateTypeInfo, String> {
+        let work_dir = self.crate_dir(crate_name)?;
+
+        // A body-less entry can't serve a client that wants bodies
+        let usable = state
+            .entry
+            .as_ref()
+            .is_some_and(|entry| skip_bodies || entry.info.bodies_included);
+        if !usable {
+            // Queries already waiting get their details from the same run.
+            // They stay registered until the index exists, so queries that
+            // arrive during the compile are collected with them below
+            let details: Vec<String> = std::iter::once(query)
+                .chain(
+                    self.waiting
+                        .lock()
+                        .unwrap()
+                        .get(crate_name)
+                        .into_iter()
+                        .flatten(),
+                )
+                .flat_map(|query| QueryNeed::of(query).index_details())
+                .collect();
+            let scope = ExtractScope::Index(details.clone());
+            let index = (self.extract)(self, crate_name, &work_dir, &scope, skip_bodies)?;
+            let mut entry = CacheEntry::new(index);
+            entry.mark_extracted(&details);
+            state.entry = Some(entry);
+            self.indexed.lock().unwrap().insert(crate_name.to_string());
+        }
+
+        // Waiting queries for this crate are answered from what this run
+        // extracts, so each one doesn't pay for a compile of its own
+        let mut queries = vec![query.clone()];
+        queries.extend(self.take_waiting(crate_name));
+
+        let entry = state.entry.as_mut().unwrap();
+        if let Some(scope) = entry.missing(&queries) {
+            let skip_bodies = !entry.info.bodies_included;
+            let partial = (self.extract)(self, crate_name, &work_dir, &scope, skip_bodies)?;
+            match scope {
+                ExtractScope::Items(patterns) => {
+                    entry.merge(partial);
+                    entry.mark_extracted(&patterns);
+                }
+                ExtractScope::Crate | ExtractScope::Index(_) => {
+                    entry.info = partial;
+                    entry.complete = true;
+                    entry.public_info = None;
+                }
+            }
+        }
+
+        if include_private {
+            return Ok(&entry.info);
         }
+        Ok(entry.public_info.get_or_insert_with(|| {
+            let mut public = entry.info.clone();
+            public.retain_public();
+            public
+        }))
+    }
+
+    /// Take the queries waiting for `crate_name`'s lock.
+    fn take_waiting(&self, crate_name: &str) -> Vec<Query> {
+        self.waiting
+            .lock()
+            .unwrap()
+            .remove(crate_name)
+            .unwrap_or_default()
+    }
+
+    fn workspace_root(&self) -> PathBuf {
+        self.workspace_dir
+            .clone()
+            .unwrap_or_else(|| std::env::current_dir().unwrap())
     }
 
-    fn get_or_compile(&mut self, crate_name: &str) -> Result<&CrateTypeInfo, String> {
-        if !self.cache.contains_key(crate_name) {
-            let info = self.compile_and_extract(crate_name)?;
-            self.cache.insert(crate_name.to_string(), info);
+    /// The directory cargo builds into.
+    ///
+    /// Builds are kept apart from the user's own so the two don't keep
+    /// invalidating each other across toolchain versions. The default lives
+    /// at the workspace root so members share one build.
+    fn target_dir(&self) -> PathBuf {
+        if let Some(ref dir) = self.target_dir {
+            return dir.clone();
+        }
+        match std::env::var_os("CARGO_TARGET_DIR") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir).join("bronzite"),
+            _ => self.workspace_root().join("target").join("bronzite"),
         }
-        Ok(self.cache.get(crate_name).unwrap())
     }
 
-    fn compile_and_extract(&self, crate_name: &str) -> Result<CrateTypeInfo, String> {
+    /// Find the directory to compile `crate_name` in.
+    ///
+    /// Workspace members are looked up with `cargo metadata`, which is only
+    /// re-run when a crate isn't in the map yet. If `cargo metadata` itself
+    /// fails, the workspace root is used.
+    fn crate_dir(&self, crate_name: &str) -> Result<PathBuf, String> {
+        let root = self.workspace_root();
+        if crate_name.is_empty() || self.file.is_some() {
+            return Ok(root);
+        }
+
+        let mut crate_dirs = self.crate_dirs.lock().unwrap();
+        if !crate_dirs.contains_key(crate_name) {
+            // The crate may have been added since we last looked
+            match workspace_crate_dirs(&root) {
+                Ok(dirs) => *crate_dirs = dirs,
+                Err(e) => {
+                    if self.verbose {
+                        eprintln!("[bronzite-daemon] {}", e);
+                    }
+                    return Ok(root);
+                }
+            }
+        }
+
+        crate_dirs.get(crate_name).cloned().ok_or_else(|| {
+            format!(
+                "Crate '{}' is not a member of the workspace at {}",
+                crate_name,
+                root.display()
+            )
+        })
+    }
+
+    fn compile_and_extract(
+        &self,
+        crate_name: &str,
+        work_dir: &Path,
+        scope: &ExtractScope,
+        skip_bodies: bool,
+    ) -> Result<CrateTypeInfo, String> {
         if self.verbose {
-            eprintln!("[bronzite-daemon] Compiling crate: {}", crate_name);
+            match scope {
+                ExtractScope::Index(_) => {
+                    eprintln!("[bronzite-daemon] Indexing crate: {}", crate_name)
+                }
+                ExtractScope::Items(patterns) => eprintln!(
+                    "[bronzite-daemon] Extracting '{}' from crate: {}",
+                    patterns.join("', '"),
+                    crate_name
+                ),
+                ExtractScope::Crate => {
+                    eprintln!("[bronzite-daemon] Compiling crate: {}", crate_name)
+                }
+            }
         }
 
-        let work_dir = self
-            .workspace_dir
-            .clone()
-            .unwrap_or_else(|| std::env::current_dir().unwrap());
+        let mut cmd = self.plugin_command(crate_name, work_dir)?;
+        cmd.arg("--extract");
+
+        match scope {
+            ExtractScope::Items(patterns) => {
+                for pattern in patterns {
+                    cmd.arg("--filter").arg(pattern);
+                }
+            }
+            ExtractScope::Index(_) | ExtractScope::Crate => {
+                if let Some(ref filter) = self.filter {
+                    cmd.arg("--filter").arg(filter);
+                }
+            }
+        }
+        if let ExtractScope::Index(details) = scope {
+            cmd.arg("--index-only");
+            for pattern in details {
+                cmd.arg("--detail").arg(pattern);
+            }
+        }
+        if skip_bodies {
+            cmd.arg("--skip-bodies");
+        }
+
+        let output = self.run_plugin(cmd)?;
+
+        // Parse the output - it may contain multiple JSON objects (one per crate)
+        // The output is pretty-printed, so we need to find complete JSON objects
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut found_info: Option<CrateTypeInfo> = None;
+        let mut found_names = Vec::new();
+
+        // Extract JSON objects by tracking brace depth
+        for json_str in extract_json_objects(&stdout) {
+            match serde_json::from_str::<CrateTypeInfo>(&json_str) {
+                Ok(info) => {
+                    found_names.push(info.crate_name.clone());
+                    let matches =
+                        normalize_crate_name(&info.crate_name) == normalize_crate_name(crate_name);
+                    if matches || crate_name.is_empty() {
+                        found_info = Some(info);
+                        if !crate_name.is_empty() {
+                            break;
+                        }
+                    }
+                }
+                Err(e) => {
+                    if self.verbose {
+                        eprintln!("[bronzite-daemon] Failed to parse JSON: {}", e);
+                    }
+                }
+            }
+        }
+
+        found_info.ok_or_else(|| {
+            format!(
+                "Crate '{}' not found in compilation output (found: {})",
+                crate_name,
+                found_names.join(", ")
+            )
+        })
+    }
 
-        // The specific nightly toolchain that bronzite requires
-        const BRONZITE_TOOLCHAIN: &str = "nightly-2025-08-20";
+    /// Answer a query by running the plugin in query mode against the live
+    /// compiler, for queries the cached extraction can't serve.
+    fn run_live_query(
+        &self,
+        state: &mut CrateState,
+        crate_name: &str,
+        query: &Query,
+    ) -> Result<QueryResult, String> {
+        let query_json = serde_json::to_string(query).map_err(|e| e.to_string())?;
+        if let Some(result) = state.live_results.get(&query_json) {
+            return Ok(result.clone());
+        }
+
+        let work_dir = self.crate_dir(crate_name)?;
+        if self.verbose {
+            eprintln!(
+                "[bronzite-daemon] Running query {} on crate: {}",
+                query_json, crate_name
+            );
+        }
 
+        let mut cmd = self.plugin_command(crate_name, &work_dir)?;
+        cmd.arg("--query").arg(&query_json);
+        let output = self.run_plugin(cmd)?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let result = extract_json_objects(&stdout)
+            .iter()
+            .find_map(|json_str| serde_json::from_str::<QueryResult>(json_str).ok())
+            .ok_or_else(|| {
+                format!(
+                    "No query result for crate '{}' in plugin output",
+                    crate_name
+                )
+            })?;
+
+        if matches!(result, QueryResult::Success { .. }) {
+            state.live_results.insert(query_json, result.clone());
+        }
+        Ok(result)
+    }
+
+    /// Build the command that runs the bronzite-query plugin on `crate_name`.
+    fn plugin_command(&self, crate_name: &str, work_dir: &Path) -> Result<Command, String> {
         // Get the rustc sysroot for the bronzite toolchain
-        let sysroot = get_rustc_sysroot_for_toolchain(BRONZITE_TOOLCHAIN)?;
+        let sysroot = get_rustc_sysroot_for_toolchain(TOOLCHAIN)?;
         let lib_path = PathBuf::from(&sysroot).join("lib");
 
         // Set the library path environment variable
@@ -134,25 +700,54 @@ impl CacheManager {
         #[cfg(target_os = "windows")]
         let lib_path_var = "PATH";
 
-        // Use a separate target directory to avoid polluting user's cache
-        // This also avoids conflicts with different toolchain versions
-        let bronzite_target_dir = work_dir.join("target").join("bronzite");
+        // Cargo only reports an unwritable target directory deep inside its
+        // own output, so check up front
+        let bronzite_target_dir = self.target_dir();
+        ensure_writable_dir(&bronzite_target_dir)?;
 
-        // Run cargo-bronzite-query with --extract flag using the specific toolchain
+        // Run cargo-bronzite-query using the specific toolchain
         // We use `rustup run <toolchain>` to ensure the correct nightly is used
-        let output = Command::new("rustup")
-            .arg("run")
-            .arg(BRONZITE_TOOLCHAIN)
+        let mut cmd = Command::new("rustup");
+        cmd.arg("run")
+            .arg(TOOLCHAIN)
             .arg(&self.query_binary)
-            .arg("bronzite-query")
-            .arg("--extract")
-            .current_dir(&work_dir)
+            .arg("bronzite-query");
+
+        if let Some(ref file) = self.file {
+            cmd.arg("--file").arg(file);
+            cmd.arg("--edition").arg(&self.edition);
+        }
+
+        // Only extract the requested crate, even though cargo checks the
+        // whole workspace
+        if !crate_name.is_empty() {
+            cmd.arg("--package").arg(crate_name);
+        }
+
+        if let Some(threads) = self.threads {
+            cmd.arg("--threads").arg(threads.to_string());
+        }
+
+        cmd.current_dir(work_dir)
             .env(lib_path_var, &lib_path)
             .env("CARGO_TARGET_DIR", &bronzite_target_dir)
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
+            .stderr(Stdio::piped());
+
+        // Put cargo and every rustc it spawns into one process group, so a
+        // timeout can kill the whole tree
+        #[cfg(unix)]
+        cmd.process_group(0);
+
+        Ok(cmd)
+    }
+
+    /// Run a plugin command, killing it if it exceeds the compile timeout.
+    fn run_plugin(&self, mut cmd: Command) -> Result<Output, String> {
+        let child = cmd
+            .spawn()
             .map_err(|e| format!("Failed to run bronzite-query: {}", e))?;
+        let output = wait_with_timeout(child, self.compile_timeout)?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -162,69 +757,133 @@ impl CacheManager {
             }
         }
 
-        // Parse the output - it may contain multiple JSON objects (one per crate)
-        // The output is pretty-printed, so we need to find complete JSON objects
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let mut found_info: Option<CrateTypeInfo> = None;
+        Ok(output)
+    }
 
-        // Extract JSON objects by tracking brace depth
-        for json_str in extract_json_objects(&stdout) {
-            match serde_json::from_str::<CrateTypeInfo>(&json_str) {
-                Ok(info) => {
-                    if info.crate_name == crate_name || crate_name.is_empty() {
-                        found_info = Some(info);
-                        if !crate_name.is_empty() {
-                            break;
-                        }
-                    }
-                }
+    /// Names of the workspace members and cached crates, sorted.
+    fn known_crates(&self) -> Vec<String> {
+        let mut names = BTreeSet::new();
+        if self.file.is_none() {
+            let mut crate_dirs = self.crate_dirs.lock().unwrap();
+            match workspace_crate_dirs(&self.workspace_root()) {
+                Ok(dirs) => *crate_dirs = dirs,
                 Err(e) => {
                     if self.verbose {
-                        eprintln!("[bronzite-daemon] Failed to parse JSON: {}", e);
+                        eprintln!("[bronzite-daemon] {}", e);
                     }
                 }
             }
+            names.extend(crate_dirs.keys().cloned());
         }
 
-        found_info.ok_or_else(|| format!("Crate '{}' not found in compilation output", crate_name))
+        names.extend(self.indexed.lock().unwrap().iter().cloned());
+        names.into_iter().collect()
     }
 
-    fn invalidate(&mut self, crate_name: &str) {
-        self.cache.remove(crate_name);
+    fn invalidate(&self, crate_name: &str) {
+        *lock_crate_state(&self.crate_state(crate_name)) = CrateState::default();
+        self.indexed.lock().unwrap().remove(crate_name);
         if self.verbose {
             eprintln!("[bronzite-daemon] Invalidated cache for: {}", crate_name);
         }
     }
 
-    fn execute_query(&mut self, crate_name: &str, query: Query) -> QueryResult {
+    fn execute_query(
+        &self,
+        crate_name: &str,
+        query: Query,
+        skip_bodies: bool,
+        include_private: bool,
+    ) -> QueryResult {
         // Handle queries that don't need crate info
+        if let Some(result) = immediate_result(&query) {
+            return result;
+        }
         match &query {
-            Query::Ping => {
+            Query::Shutdown => {
                 return QueryResult::Success {
-                    data: QueryData::Pong,
+                    data: QueryData::ShuttingDown,
                 };
             }
-            Query::Shutdown => {
+            Query::ListCachedCrates => {
                 return QueryResult::Success {
-                    data: QueryData::ShuttingDown,
+                    data: QueryData::Crates {
+                        crates: self.known_crates(),
+                    },
                 };
             }
             _ => {}
         }
 
-        // Get or compile the crate info
-        let info = match self.get_or_compile(crate_name) {
-            Ok(info) => info,
-            Err(e) => {
-                return QueryResult::Error { message: e };
+        let state = self.crate_state(crate_name);
+        let mut state = match state.try_lock() {
+            Ok(state) => state,
+            Err(TryLockError::Poisoned(poisoned)) => {
+                reset_crate_state(&state, poisoned.into_inner(), self.verbose)
+            }
+            Err(TryLockError::WouldBlock) => {
+                // Usually another query compiling the crate. Its result is
+                // cached by the time we get the lock, so concurrent first
+                // queries share a single compile
+                if self.verbose {
+                    eprintln!(
+                        "[bronzite-daemon] Waiting for another query on crate: {}",
+                        crate_name
+                    );
+                }
+                self.waiting
+                    .lock()
+                    .unwrap()
+                    .entry(crate_name.to_string())
+                    .or_default()
+                    .push(query.clone());
+                lock_crate_state(&state)
             }
         };
+        if let Query::GetTypeInstantiated { .. }
+        | Query::CheckConvertible { .. }
+        | Query::CheckImplFor { .. }
+        | Query::CheckSized { .. }
+        | Query::GetSpanText { .. }
+        | Query::GetImplMethodSignature { .. } = query
+        {
+            return self
+                .run_live_query(&mut state, crate_name, &query)
+                .unwrap_or_else(|message| QueryResult::Error { message });
+        }
+
+        // Get or compile the crate info
+        let info =
+            match self.get_or_compile(&mut state, crate_name, &query, skip_bodies, include_private)
+            {
+                Ok(info) => info,
+                Err(e) => {
+                    return QueryResult::Error { message: e };
+                }
+            };
 
         // Execute the specific query
         match query {
-            Query::ListItems => QueryResult::Success {
-                data: QueryData::Items {
-                    items: info.items.clone(),
+            Query::ListItems => {
+                let mut items = info.items.clone();
+                sort_by_path(&mut items, |item| &item.path);
+
+                QueryResult::Success {
+                    data: QueryData::Items {
+                        items,
+                        warnings: info.warnings.clone(),
+                    },
+                }
+            }
+
+            Query::GetCrateInfo => QueryResult::Success {
+                data: QueryData::CrateInfo(Box::new(info.clone())),
+            },
+
+            Query::GetCrateMeta => QueryResult::Success {
+                data: QueryData::CrateMeta {
+                    edition: info.edition.clone(),
+                    rustc_version: info.rustc_version.clone(),
                 },
             },
 
@@ -247,6 +906,58 @@ impl CacheManager {
                 }
             }
 
+            Query::GetTypeFull { path } => {
+                let type_info = info.types.get(&path).or_else(|| {
+                    info.types
+                        .values()
+                        .find(|t| t.path.ends_with(&format!("::{}", path)))
+                });
+
+                match type_info {
+                    Some(details) => {
+                        // Impls of generic types are keyed with their arguments
+                        // (`Pair<T>`), so match on the base path
+                        let is_self = |key: &String| {
+                            key == &details.path || key.split('<').next() == Some(&details.path)
+                        };
+                        let trait_impls = info
+                            .trait_impls
+                            .iter()
+                            .filter(|(key, _)| is_self(key))
+                            .flat_map(|(_, impls)| impls.iter().cloned())
+                            .collect();
+                        let inherent_impls = info
+                            .inherent_impls
+                            .iter()
+                            .filter(|(key, _)| is_self(key))
+                            .flat_map(|(_, impls)| impls.iter().cloned())
+                            .collect();
+
+                        QueryResult::Success {
+                            data: QueryData::TypeFull {
+                                details: details.clone(),
+                                fields: details.fields.clone().unwrap_or_default(),
+                                trait_impls,
+                                inherent_impls,
+                                layout: info.layouts.get(&details.path).cloned(),
+                            },
+                        }
+                    }
+                    None => QueryResult::Error {
+                        message: format!("Type '{}' not found", path),
+                    },
+                }
+            }
+
+            Query::GetTypeById { id } => match info.types.values().find(|t| t.stable_id == id) {
+                Some(type_info) => QueryResult::Success {
+                    data: QueryData::TypeInfo(type_info.clone()),
+                },
+                None => QueryResult::Error {
+                    message: format!("No type with id '{}'", id),
+                },
+            },
+
             Query::GetTraitImpls { type_path } => {
                 // trait_impls is HashMap<String, Vec<TraitImplDetails>> keyed by self_ty
                 let mut impls: Vec<TraitImplDetails> = Vec::new();
@@ -292,6 +1003,74 @@ impl CacheManager {
                 }
             }
 
+            Query::ResolveMethod {
+                type_path,
+                method_name,
+            } => {
+                let suffix = format!("::{}", type_path);
+                let key_matches = |key: &String| *key == type_path || key.ends_with(&suffix);
+                let mut candidates: Vec<MethodCandidate> = Vec::new();
+
+                // Inherent methods shadow trait methods, so they go first
+                for (key, impls) in &info.inherent_impls {
+                    if key_matches(key) {
+                        for impl_ in impls {
+                            candidates.extend(
+                                impl_
+                                    .methods
+                                    .iter()
+                                    .filter(|m| m.name == method_name)
+                                    .map(|m| MethodCandidate {
+                                        trait_path: None,
+                                        method: m.clone(),
+                                    }),
+                            );
+                        }
+                    }
+                }
+                for (key, impls) in &info.trait_impls {
+                    if key_matches(key) {
+                        for impl_ in impls {
+                            candidates.extend(
+                                impl_
+                                    .methods
+                                    .iter()
+                                    .filter(|m| m.name == method_name)
+                                    .map(|m| MethodCandidate {
+                                        trait_path: Some(impl_.trait_path.clone()),
+                                        method: m.clone(),
+                                    }),
+                            );
+                        }
+                    }
+                }
+
+                QueryResult::Success {
+                    data: QueryData::MethodCandidates { candidates },
+                }
+            }
+
+            Query::DescribeType { type_path } => QueryResult::Success {
+                data: QueryData::TypeStructure(info.describe_type(&type_path)),
+            },
+
+            #[cfg(feature = "regex")]
+            Query::FindTypesRegex { regex } => match info.find_types_regex(&regex) {
+                Ok(types) => QueryResult::Success {
+                    data: QueryData::Types {
+                        types,
+                        warnings: info.warnings.clone(),
+                    },
+                },
+                Err(e) => QueryResult::Error {
+                    message: format!("Invalid regex '{}': {}", regex, e),
+                },
+            },
+            #[cfg(not(feature = "regex"))]
+            Query::FindTypesRegex { .. } => QueryResult::Error {
+                message: "This daemon was built without the `regex` feature".to_string(),
+            },
+
             Query::GetFields { type_path } => {
                 // types is HashMap<String, TypeDetails>
                 let type_info = info.types.get(&type_path).or_else(|| {
@@ -313,10 +1092,21 @@ impl CacheManager {
                 }
             }
 
+            Query::GetVariantField {
+                enum_path,
+                variant,
+                field,
+            } => match info.variant_field(&enum_path, &variant, &field) {
+                Ok(field) => QueryResult::Success {
+                    data: QueryData::Field(field.clone()),
+                },
+                Err(message) => QueryResult::Error { message },
+            },
+
             Query::GetLayout { type_path } => {
-                if let Some(layout) = info.layouts.get(&type_path) {
+                if let Some(layout) = info.type_layout(&type_path) {
                     QueryResult::Success {
-                        data: QueryData::Layout(layout.clone()),
+                        data: QueryData::Layout(layout),
                     }
                 } else {
                     QueryResult::Error {
@@ -327,24 +1117,43 @@ impl CacheManager {
 
             Query::GetTraits => {
                 // traits is HashMap<String, TraitDetails>
+                let traits: Vec<TraitInfo> = info.traits.values().map(trait_info).collect();
+
+                QueryResult::Success {
+                    data: QueryData::Traits {
+                        traits,
+                        warnings: info.warnings.clone(),
+                    },
+                }
+            }
+
+            Query::FindTraitsBySupertrait { supertrait } => {
                 let traits: Vec<TraitInfo> = info
                     .traits
                     .values()
-                    .map(|t| TraitInfo {
-                        name: t.name.clone(),
-                        path: t.path.clone(),
-                        generics: t.generics.clone(),
-                        required_methods: t.methods.iter().filter(|m| !m.has_default).count(),
-                        provided_methods: t.methods.iter().filter(|m| m.has_default).count(),
-                        supertraits: t.supertraits.clone(),
+                    .filter(|t| {
+                        t.all_supertraits
+                            .iter()
+                            .any(|s| trait_path_matches(s, &supertrait))
                     })
+                    .map(trait_info)
                     .collect();
 
                 QueryResult::Success {
-                    data: QueryData::Traits { traits },
+                    data: QueryData::Traits {
+                        traits,
+                        warnings: info.warnings.clone(),
+                    },
                 }
             }
 
+            Query::FindTypesUsingType { type_path } => QueryResult::Success {
+                data: QueryData::TypeUsages {
+                    usages: info.types_using_type(&type_path),
+                    warnings: info.warnings.clone(),
+                },
+            },
+
             Query::GetTrait { path } => {
                 // traits is HashMap<String, TraitDetails>
                 let trait_info = info.traits.get(&path).or_else(|| {
@@ -364,22 +1173,139 @@ impl CacheManager {
                 }
             }
 
-            Query::FindTypes { pattern } => {
+            Query::GetTraitAlias { path } => {
+                let alias = info.trait_aliases.get(&path).or_else(|| {
+                    info.trait_aliases
+                        .values()
+                        .find(|a| a.path.ends_with(&format!("::{}", path)))
+                });
+
+                if let Some(alias) = alias {
+                    QueryResult::Success {
+                        data: QueryData::TraitAlias(alias.clone()),
+                    }
+                } else {
+                    QueryResult::Error {
+                        message: format!("Trait alias '{}' not found", path),
+                    }
+                }
+            }
+
+            Query::GetModuleTree => QueryResult::Success {
+                data: QueryData::ModuleTree(ModuleTree::build(
+                    &info.crate_name,
+                    &info.modules,
+                    &info.items,
+                )),
+            },
+
+            Query::GetFunction { path } => {
+                let function = info.functions.get(&path).or_else(|| {
+                    info.functions
+                        .values()
+                        .find(|f| f.path.ends_with(&format!("::{}", path)))
+                });
+
+                if let Some(function) = function {
+                    QueryResult::Success {
+                        data: QueryData::FunctionDetails(function.clone()),
+                    }
+                } else {
+                    QueryResult::Error {
+                        message: format!("Function '{}' not found", path),
+                    }
+                }
+            }
+
+            Query::GetMacro { path } => {
+                let macro_details = info.macros.get(&path).or_else(|| {
+                    info.macros
+                        .values()
+                        .find(|m| m.path.ends_with(&format!("::{}", path)))
+                });
+
+                if let Some(macro_details) = macro_details {
+                    QueryResult::Success {
+                        data: QueryData::MacroDetails(macro_details.clone()),
+                    }
+                } else {
+                    QueryResult::Error {
+                        message: format!("Macro '{}' not found", path),
+                    }
+                }
+            }
+
+            Query::GetConst { path } => {
+                let const_details = info.consts.get(&path).or_else(|| {
+                    info.consts
+                        .values()
+                        .find(|c| c.path.ends_with(&format!("::{}", path)))
+                });
+
+                if let Some(const_details) = const_details {
+                    QueryResult::Success {
+                        data: QueryData::ConstDetails(const_details.clone()),
+                    }
+                } else {
+                    QueryResult::Error {
+                        message: format!("Const '{}' not found", path),
+                    }
+                }
+            }
+
+            Query::FindMethods {
+                pattern,
+                include_trait_impls,
+            } => QueryResult::Success {
+                data: QueryData::Methods {
+                    methods: info.find_methods(&pattern, include_trait_impls),
+                    warnings: info.warnings.clone(),
+                },
+            },
+
+            Query::FindFunctions { pattern, options } => {
+                let mut functions: Vec<FunctionDetails> = info
+                    .functions
+                    .values()
+                    .filter(|f| {
+                        bronzite_types::path_matches_pattern_with(&f.path, &pattern, &options)
+                    })
+                    .cloned()
+                    .collect();
+                sort_by_path(&mut functions, |f| &f.path);
+
+                QueryResult::Success {
+                    data: QueryData::Functions {
+                        functions,
+                        warnings: info.warnings.clone(),
+                    },
+                }
+            }
+
+            // `handle_client` sends the matches of a stream one by one
+            Query::FindTypes { pattern, options } | Query::StreamTypes { pattern, options } => {
                 // types is HashMap<String, TypeDetails>
-                let types: Vec<TypeSummary> = info
+                let mut types: Vec<TypeSummary> = info
                     .types
                     .values()
-                    .filter(|t| bronzite_types::path_matches_pattern(&t.path, &pattern))
+                    .filter(|t| {
+                        bronzite_types::path_matches_pattern_with(&t.path, &pattern, &options)
+                    })
                     .map(|t| TypeSummary {
                         name: t.name.clone(),
                         path: t.path.clone(),
                         kind: t.kind.clone(),
                         generics: t.generics.clone(),
+                        crate_name: Some(info.crate_name.clone()),
                     })
                     .collect();
+                sort_by_path(&mut types, |t| &t.path);
 
                 QueryResult::Success {
-                    data: QueryData::Types { types },
+                    data: QueryData::Types {
+                        types,
+                        warnings: info.warnings.clone(),
+                    },
                 }
             }
 
@@ -396,7 +1322,8 @@ impl CacheManager {
                         data: QueryData::ResolvedType {
                             original: alias.path.clone(),
                             resolved: alias.resolved_ty.clone(),
-                            chain: vec![alias.ty.clone()],
+                            chain: alias.chain.clone(),
+                            generics: alias.generics.clone(),
                         },
                     }
                 } else {
@@ -406,6 +1333,23 @@ impl CacheManager {
                 }
             }
 
+            Query::GetTypeAliases { pattern } => {
+                let mut aliases: Vec<TypeAliasInfo> = info
+                    .type_aliases
+                    .values()
+                    .filter(|a| bronzite_types::path_matches_pattern(&a.path, &pattern))
+                    .cloned()
+                    .collect();
+                sort_by_path(&mut aliases, |a| &a.path);
+
+                QueryResult::Success {
+                    data: QueryData::TypeAliases {
+                        aliases,
+                        warnings: info.warnings.clone(),
+                    },
+                }
+            }
+
             Query::CheckImpl {
                 type_path,
                 trait_path,
@@ -425,8 +1369,9 @@ impl CacheManager {
 
                 for (self_ty, impls) in &info.trait_impls {
                     for impl_ in impls {
-                        let matches = impl_.trait_path == trait_path
-                            || impl_.trait_path.ends_with(&format!("::{}", trait_path));
+                        // A type may implement a generic trait several times
+                        let matches = trait_path_matches(&impl_.trait_path, &trait_path)
+                            && !types.iter().any(|t| t.path == *self_ty);
 
                         if matches {
                             if let Some(type_info) = info.types.get(self_ty) {
@@ -435,6 +1380,7 @@ impl CacheManager {
                                     path: type_info.path.clone(),
                                     kind: type_info.kind.clone(),
                                     generics: type_info.generics.clone(),
+                                    crate_name: Some(info.crate_name.clone()),
                                 });
                             }
                         }
@@ -446,26 +1392,57 @@ impl CacheManager {
                 }
             }
 
-            Query::Ping | Query::Shutdown => unreachable!(),
+            Query::GetAllImplsOfTrait { trait_path } => {
+                let mut impls: Vec<TraitImplDetails> = info
+                    .trait_impls
+                    .values()
+                    .flatten()
+                    .filter(|impl_| trait_path_matches(&impl_.trait_path, &trait_path))
+                    .cloned()
+                    .collect();
+                sort_by_path(&mut impls, |impl_| &impl_.self_ty);
+
+                QueryResult::Success {
+                    data: QueryData::TraitImpls { impls },
+                }
+            }
+
+            Query::Ping
+            | Query::Version
+            | Query::Shutdown
+            | Query::ListCachedCrates
+            | Query::GetTypeInstantiated { .. }
+            | Query::CheckConvertible { .. }
+            | Query::CheckImplFor { .. }
+            | Query::CheckSized { .. }
+            | Query::GetSpanText { .. }
+            | Query::GetImplMethodSignature { .. } => unreachable!(),
         }
     }
 }
 
+fn trait_info(t: &TraitDetails) -> TraitInfo {
+    TraitInfo {
+        name: t.name.clone(),
+        path: t.path.clone(),
+        generics: t.generics.clone(),
+        required_methods: t.methods.iter().filter(|m| !m.has_default).count(),
+        provided_methods: t.methods.iter().filter(|m| m.has_default).count(),
+        supertraits: t.supertraits.clone(),
+    }
+}
+
 fn check_impl_from_cache(
     info: &CrateTypeInfo,
     type_path: &str,
     trait_path: &str,
 ) -> (bool, Option<TraitImplDetails>) {
-    // trait_impls is HashMap<String, Vec<TraitImplDetails>> keyed by self_ty
+    // trait_impls is HashMap<String, Vec<TraitImplDetails>> keyed by self_ty,
+    // without generic arguments for ADTs
     for (key, impls) in &info.trait_impls {
-        let type_matches = key == type_path || key.ends_with(&format!("::{}", type_path));
-
-        if type_matches {
+        if type_path_matches(key, type_path) {
             for impl_ in impls {
-                let trait_matches = impl_.trait_path == trait_path
-                    || impl_.trait_path.ends_with(&format!("::{}", trait_path));
-
-                if trait_matches {
+                if trait_path_matches(&impl_.trait_path, trait_path) {
                     return (true, Some(impl_.clone()));
                 }
             }
@@ -474,6 +1451,145 @@ fn check_impl_from_cache(
     (false, None)
 }
 
+/// Map each package in the workspace at `root` to its manifest directory,
+/// keyed by crate name (package name with `-` replaced by `_`).
+fn workspace_crate_dirs(root: &Path) -> Result<HashMap<String, PathBuf>, String> {
+    let output = Command::new("cargo")
+        .args(["metadata", "--no-deps", "--format-version", "1"])
+        .current_dir(root)
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|e| format!("Failed to run cargo metadata: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "cargo metadata failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    let metadata: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse cargo metadata: {}", e))?;
+
+    let mut dirs = HashMap::new();
+    for package in metadata["packages"].as_array().into_iter().flatten() {
+        let (Some(name), Some(manifest_path)) =
+            (package["name"].as_str(), package["manifest_path"].as_str())
+        else {
+            continue;
+        };
+        if let Some(dir) = Path::new(manifest_path).parent() {
+            dirs.insert(normalize_crate_name(name), dir.to_path_buf());
+        }
+    }
+
+    Ok(dirs)
+}
+
+/// Lock a crate's state, recovering it if a query panicked while holding it.
+fn lock_crate_state(state: &Mutex<CrateState>) -> MutexGuard<'_, CrateState> {
+    match state.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => reset_crate_state(state, poisoned.into_inner(), false),
+    }
+}
+
+/// Take over a crate's state from a query that panicked while holding its
+/// lock. The state may be half-updated, so it starts over from scratch.
+fn reset_crate_state<'a>(
+    state: &Mutex<CrateState>,
+    mut guard: MutexGuard<'a, CrateState>,
+    verbose: bool,
+) -> MutexGuard<'a, CrateState> {
+    if verbose {
+        eprintln!("[bronzite-daemon] Resetting crate state after a panicked query");
+    }
+    state.clear_poison();
+    *guard = CrateState::default();
+    guard
+}
+
+/// Make sure `dir` exists and can be written to, by creating it and a
+/// scratch file inside it.
+fn ensure_writable_dir(dir: &Path) -> Result<(), String> {
+    let probe = dir.join(".bronzite-write-check");
+    std::fs::create_dir_all(dir)
+        .and_then(|()| std::fs::write(&probe, b""))
+        .and_then(|()| std::fs::remove_file(&probe))
+        .map_err(|e| {
+            format!(
+                "Cannot write to build directory '{}': {}. Set CARGO_TARGET_DIR \
+                 (or pass --target-dir to the daemon) to a writable location.",
+                dir.display(),
+                e
+            )
+        })
+}
+
+/// Wait for a child process to exit, collecting its output.
+///
+/// If the child is still running after `timeout`, its process group is
+/// killed and an error is returned.
+fn wait_with_timeout(mut child: Child, timeout: Duration) -> Result<Output, String> {
+    // Drain the pipes on their own threads so a chatty child can't block on a
+    // full pipe while we poll
+    let stdout = child.stdout.take().map(|mut pipe| {
+        thread::spawn(move || {
+            let mut buf = Vec::new();
+            let _ = pipe.read_to_end(&mut buf);
+            buf
+        })
+    });
+    let stderr = child.stderr.take().map(|mut pipe| {
+        thread::spawn(move || {
+            let mut buf = Vec::new();
+            let _ = pipe.read_to_end(&mut buf);
+            buf
+        })
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() >= deadline => {
+                kill_process_group(&mut child);
+                let _ = child.wait();
+                return Err("compilation timed out".to_string());
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(50)),
+            Err(e) => return Err(format!("Failed to wait for bronzite-query: {}", e)),
+        }
+    };
+
+    Ok(Output {
+        status,
+        stdout: stdout
+            .and_then(|handle| handle.join().ok())
+            .unwrap_or_default(),
+        stderr: stderr
+            .and_then(|handle| handle.join().ok())
+            .unwrap_or_default(),
+    })
+}
+
+/// Kill a child spawned as a process group leader, along with everything
+/// it spawned.
+fn kill_process_group(child: &mut Child) {
+    #[cfg(unix)]
+    {
+        // SAFETY: kill(2) has no memory-safety preconditions. The child was
+        // spawned with `process_group(0)`, so its pid is also its group id.
+        unsafe {
+            libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = child.kill();
+    }
+}
+
 /// Extract complete JSON objects from a string that may contain multiple objects.
 fn extract_json_objects(input: &str) -> Vec<String> {
     let mut objects = Vec::new();
@@ -562,6 +1678,9 @@ fn is_daemon_running(socket_path: &PathBuf) -> bool {
                 id: 0,
                 crate_name: String::new(),
                 query: Query::Ping,
+                skip_bodies: false,
+                include_private: true,
+                fresh: false,
             };
 
             if let Ok(json) = serde_json::to_string(&request) {
@@ -596,6 +1715,21 @@ fn spawn_daemon_background(args: &Args) -> Result<(), String> {
     if let Some(ref socket) = args.socket {
         cmd.arg("--socket").arg(socket);
     }
+    if let Some(ref file) = args.file {
+        cmd.arg("--file").arg(file);
+        cmd.arg("--edition").arg(&args.edition);
+    }
+    if let Some(ref filter) = args.filter {
+        cmd.arg("--filter").arg(filter);
+    }
+    if let Some(threads) = args.threads {
+        cmd.arg("--threads").arg(threads.to_string());
+    }
+    if let Some(ref target_dir) = args.target_dir {
+        cmd.arg("--target-dir").arg(target_dir);
+    }
+    cmd.arg("--compile-timeout")
+        .arg(args.compile_timeout.to_string());
     if args.verbose {
         cmd.arg("--verbose");
     }
@@ -714,15 +1848,11 @@ fn main() {
         eprintln!("[bronzite-daemon] Listening on {:?}", socket_path);
     }
 
-    // Channel for communicating with the cache manager thread
-    let (cache_tx, cache_rx): (Sender<CacheMessage>, Receiver<CacheMessage>) = mpsc::channel();
-
     // Shared flag for shutdown
     let running = Arc::new(Mutex::new(true));
     let running_clone = running.clone();
 
     // Handle Ctrl+C gracefully
-    let cache_tx_shutdown = cache_tx.clone();
     std::thread::spawn(move || {
         // Simple signal handling - poll the running flag
         loop {
@@ -733,19 +1863,39 @@ fn main() {
         }
     });
 
-    // Spawn the cache manager thread
     let verbose = args.verbose;
-    let workspace_dir = args.manifest_path.clone().and_then(|p| {
-        if p.is_file() {
-            p.parent().map(|p| p.to_path_buf())
-        } else {
-            Some(p)
-        }
-    });
-
-    let cache_handle = thread::spawn(move || {
-        run_cache_manager(cache_rx, workspace_dir, verbose);
-    });
+    // A single file is compiled from its own directory unless a manifest
+    // path says otherwise
+    let file = args
+        .file
+        .clone()
+        .map(|f| std::fs::canonicalize(&f).unwrap_or(f));
+    let workspace_dir = args
+        .manifest_path
+        .clone()
+        .and_then(|p| {
+            if p.is_file() {
+                p.parent().map(|p| p.to_path_buf())
+            } else {
+                Some(p)
+            }
+        })
+        .or_else(|| {
+            file.as_ref()
+                .and_then(|f| f.parent().map(|p| p.to_path_buf()))
+        });
+
+    let config = CacheConfig {
+        workspace_dir,
+        file,
+        edition: args.edition.clone(),
+        filter: args.filter.clone(),
+        threads: args.threads,
+        compile_timeout: Duration::from_secs(args.compile_timeout),
+        target_dir: args.target_dir.clone(),
+        verbose,
+    };
+    let manager = Arc::new(CacheManager::new(config));
 
     // Set socket to non-blocking for graceful shutdown
     listener
@@ -760,11 +1910,11 @@ fn main() {
 
         match listener.accept() {
             Ok((stream, _)) => {
-                let tx = cache_tx.clone();
+                let manager = Arc::clone(&manager);
                 let verbose = args.verbose;
                 let running_for_client = running.clone();
                 thread::spawn(move || {
-                    handle_client(stream, tx, verbose, running_for_client);
+                    handle_client(stream, manager, verbose, running_for_client);
                 });
             }
             Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
@@ -780,8 +1930,6 @@ fn main() {
     }
 
     // Cleanup
-    let _ = cache_tx_shutdown.send(CacheMessage::Shutdown);
-    let _ = cache_handle.join();
     let _ = std::fs::remove_file(&socket_path);
 
     if args.verbose {
@@ -789,39 +1937,47 @@ fn main() {
     }
 }
 
-fn run_cache_manager(rx: Receiver<CacheMessage>, workspace_dir: Option<PathBuf>, verbose: bool) {
-    let mut manager = CacheManager::new(workspace_dir, verbose);
+/// Answer the queries that need no daemon state. `handle_client` answers
+/// these before touching the cache, so health checks never wait for a
+/// compile.
+fn immediate_result(query: &Query) -> Option<QueryResult> {
+    let data = match query {
+        Query::Ping => QueryData::Pong,
+        Query::Version => QueryData::Version {
+            daemon_version: env!("CARGO_PKG_VERSION").to_string(),
+            protocol_version: PROTOCOL_VERSION,
+            toolchain: TOOLCHAIN.to_string(),
+        },
+        _ => return None,
+    };
+    Some(QueryResult::Success { data })
+}
 
-    loop {
-        match rx.recv() {
-            Ok(CacheMessage::Query {
-                crate_name,
-                query,
-                response_tx,
-            }) => {
-                let result = manager.execute_query(&crate_name, query);
-                let _ = response_tx.send(result);
-            }
-            Ok(CacheMessage::InvalidateCache { crate_name }) => {
-                manager.invalidate(&crate_name);
-            }
-            Ok(CacheMessage::Shutdown) => {
-                if verbose {
-                    eprintln!("[bronzite-daemon] Cache manager shutting down");
-                }
-                break;
-            }
-            Err(_) => {
-                // Channel closed, exit
-                break;
-            }
-        }
-    }
+/// Split the answer to a StreamTypes query into a response per type and a
+/// closing `StreamEnd`. Errors are passed through as they are.
+fn split_stream(result: QueryResult) -> Vec<QueryResult> {
+    let QueryResult::Success {
+        data: QueryData::Types { types, warnings },
+    } = result
+    else {
+        return vec![result];
+    };
+
+    let count = types.len();
+    types
+        .into_iter()
+        .map(|t| QueryResult::Success {
+            data: QueryData::TypeItem(t),
+        })
+        .chain(std::iter::once(QueryResult::Success {
+            data: QueryData::StreamEnd { count, warnings },
+        }))
+        .collect()
 }
 
 fn handle_client(
     mut stream: UnixStream,
-    cache_tx: Sender<CacheMessage>,
+    manager: Arc<CacheManager>,
     verbose: bool,
     running: Arc<Mutex<bool>>,
 ) {
@@ -871,40 +2027,37 @@ fn handle_client(
 
         // Check for shutdown request
         let is_shutdown = matches!(request.query, Query::Shutdown);
-
-        // Send query to cache manager
-        let (response_tx, response_rx) = mpsc::channel();
-        let msg = CacheMessage::Query {
-            crate_name: request.crate_name.clone(),
-            query: request.query,
-            response_tx,
+        let is_stream = matches!(request.query, Query::StreamTypes { .. });
+
+        let result = match immediate_result(&request.query) {
+            Some(result) => result,
+            None => {
+                let crate_name = normalize_crate_name(&request.crate_name);
+                if request.fresh {
+                    manager.invalidate(&crate_name);
+                }
+                manager.execute_query(
+                    &crate_name,
+                    request.query,
+                    request.skip_bodies,
+                    request.include_private,
+                )
+            }
         };
 
-        if cache_tx.send(msg).is_err() {
+        let results = if is_stream {
+            split_stream(result)
+        } else {
+            vec![result]
+        };
+        let written = results.into_iter().try_for_each(|result| {
             let response = Response {
                 id: request.id,
-                result: QueryResult::Error {
-                    message: "Cache manager unavailable".to_string(),
-                },
+                result,
             };
-            let _ = writeln!(stream, "{}", serde_json::to_string(&response).unwrap());
-            break;
-        }
-
-        // Wait for response
-        let result = match response_rx.recv() {
-            Ok(r) => r,
-            Err(_) => QueryResult::Error {
-                message: "No response from cache manager".to_string(),
-            },
-        };
-
-        let response = Response {
-            id: request.id,
-            result,
-        };
-
-        if let Err(e) = writeln!(stream, "{}", serde_json::to_string(&response).unwrap()) {
+            writeln!(stream, "{}", serde_json::to_string(&response).unwrap())
+        });
+        if let Err(e) = written {
             if verbose {
                 eprintln!("[bronzite-daemon] Write error: {}", e);
             }
@@ -922,3 +2075,337 @@ fn handle_client(
         eprintln!("[bronzite-daemon] Client disconnected");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Barrier, mpsc};
+
+    const THREADS: usize = 8;
+
+    /// A manager for a single-file crate that extracts with `extract`
+    /// instead of running the plugin.
+    fn stub_manager(extract: ExtractFn) -> CacheManager {
+        let mut manager = CacheManager::new(CacheConfig {
+            workspace_dir: None,
+            file: Some(PathBuf::from("demo.rs")),
+            edition: "2021".to_string(),
+            filter: None,
+            threads: None,
+            compile_timeout: Duration::from_secs(60),
+            target_dir: None,
+            verbose: false,
+        });
+        manager.extract = extract;
+        manager
+    }
+
+    /// A manager whose extraction is a stub returning `info`, counting index
+    /// and item runs separately.
+    fn counting_manager(
+        info: CrateTypeInfo,
+        index_runs: Arc<AtomicUsize>,
+        item_runs: Arc<AtomicUsize>,
+    ) -> CacheManager {
+        stub_manager(Box::new(move |manager, crate_name, _, scope, _| {
+            match scope {
+                // Keep the first compile going until every other query waits
+                ExtractScope::Index(_) => {
+                    let first = index_runs.fetch_add(1, Ordering::SeqCst) == 0;
+                    while first
+                        && manager
+                            .waiting
+                            .lock()
+                            .unwrap()
+                            .get(crate_name)
+                            .map_or(0, Vec::len)
+                            < THREADS - 1
+                    {
+                        thread::sleep(Duration::from_millis(5));
+                    }
+                }
+                ExtractScope::Items(_) | ExtractScope::Crate => {
+                    item_runs.fetch_add(1, Ordering::SeqCst);
+                }
+            }
+            Ok(info.clone())
+        }))
+    }
+
+    /// A manager whose extraction of `blocked` signals `started` and then
+    /// waits until `release` is dropped. Other crates extract at once.
+    fn blocking_manager(
+        blocked: &'static str,
+    ) -> (CacheManager, mpsc::Receiver<()>, mpsc::Sender<()>) {
+        let (started_tx, started) = mpsc::channel();
+        let (release, release_rx) = mpsc::channel::<()>();
+        let (started_tx, release_rx) = (Mutex::new(started_tx), Mutex::new(release_rx));
+        let manager = stub_manager(Box::new(move |_, crate_name, _, _, _| {
+            if crate_name == blocked {
+                started_tx.lock().unwrap().send(()).unwrap();
+                let _ = release_rx.lock().unwrap().recv();
+            }
+            Ok(CrateTypeInfo {
+                crate_name: crate_name.to_string(),
+                ..Default::default()
+            })
+        }));
+        (manager, started, release)
+    }
+
+    /// Open a connection served by `handle_client` on its own thread.
+    fn connect(manager: &Arc<CacheManager>) -> (UnixStream, thread::JoinHandle<()>) {
+        let (client, server) = UnixStream::pair().unwrap();
+        let manager = Arc::clone(manager);
+        let running = Arc::new(Mutex::new(true));
+        let handler = thread::spawn(move || handle_client(server, manager, false, running));
+        (client, handler)
+    }
+
+    fn send(mut stream: &UnixStream, crate_name: &str, query: Query) {
+        let request = Request {
+            id: 1,
+            crate_name: crate_name.to_string(),
+            query,
+            skip_bodies: true,
+            include_private: true,
+            fresh: false,
+        };
+        writeln!(stream, "{}", serde_json::to_string(&request).unwrap()).unwrap();
+    }
+
+    fn receive(stream: &UnixStream) -> QueryResult {
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line).unwrap();
+        serde_json::from_str::<Response>(&line).unwrap().result
+    }
+
+    fn run_concurrently(manager: &CacheManager, queries: Vec<Query>) -> Vec<QueryResult> {
+        let barrier = Barrier::new(queries.len());
+        thread::scope(|scope| {
+            let handles: Vec<_> = queries
+                .into_iter()
+                .map(|query| {
+                    let barrier = &barrier;
+                    scope.spawn(move || {
+                        barrier.wait();
+                        manager.execute_query("demo", query, true, true)
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        })
+    }
+
+    fn item(path: &str, kind: ItemKind) -> bronzite_types::ItemInfo {
+        bronzite_types::ItemInfo {
+            name: path.rsplit("::").next().unwrap().to_string(),
+            path: path.to_string(),
+            kind,
+            visibility: bronzite_types::Visibility::Public,
+            span: None,
+            is_test: false,
+            stable_id: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_concurrent_queries_share_one_index_compile() {
+        let index_runs = Arc::new(AtomicUsize::new(0));
+        let item_runs = Arc::new(AtomicUsize::new(0));
+        let info = CrateTypeInfo {
+            crate_name: "demo".to_string(),
+            items: vec![item("Config", ItemKind::Struct)],
+            ..Default::default()
+        };
+        let manager = counting_manager(info, Arc::clone(&index_runs), Arc::clone(&item_runs));
+
+        let results = run_concurrently(&manager, vec![Query::ListItems; THREADS]);
+        assert!(
+            results
+                .iter()
+                .all(|r| matches!(r, QueryResult::Success { .. }))
+        );
+        assert_eq!(index_runs.load(Ordering::SeqCst), 1);
+        assert_eq!(item_runs.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_panicked_query_resets_crate_state() {
+        let index_runs = Arc::new(AtomicUsize::new(0));
+        let runs = Arc::clone(&index_runs);
+        let manager = stub_manager(Box::new(move |_, _, _, _, _| {
+            if runs.fetch_add(1, Ordering::SeqCst) == 0 {
+                panic!("extraction panicked");
+            }
+            Ok(CrateTypeInfo::default())
+        }));
+
+        let panicked = thread::scope(|scope| {
+            scope
+                .spawn(|| manager.execute_query("demo", Query::ListItems, true, true))
+                .join()
+                .is_err()
+        });
+        assert!(panicked);
+
+        let result = manager.execute_query("demo", Query::ListItems, true, true);
+        assert!(matches!(result, QueryResult::Success { .. }));
+        assert_eq!(index_runs.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_waiting_queries_are_extracted_in_one_run() {
+        let index_runs = Arc::new(AtomicUsize::new(0));
+        let item_runs = Arc::new(AtomicUsize::new(0));
+        let names: Vec<String> = (0..THREADS).map(|i| format!("Type{}", i)).collect();
+        let info = CrateTypeInfo {
+            crate_name: "demo".to_string(),
+            items: names
+                .iter()
+                .map(|name| item(&format!("models::{}", name), ItemKind::Struct))
+                .collect(),
+            ..Default::default()
+        };
+        let manager = counting_manager(info, Arc::clone(&index_runs), Arc::clone(&item_runs));
+
+        let queries = names
+            .iter()
+            .map(|name| Query::GetType { path: name.clone() })
+            .collect();
+        run_concurrently(&manager, queries);
+        assert_eq!(index_runs.load(Ordering::SeqCst), 1);
+        // The first query's item comes with the index, the rest in one batch
+        assert_eq!(item_runs.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_type_by_id_round_trips_list_items() {
+        let mut config = item("models::Config", ItemKind::Struct);
+        config.stable_id = "9c0ffee".to_string();
+        let details = bronzite_types::TypeDetails {
+            name: config.name.clone(),
+            path: config.path.clone(),
+            kind: bronzite_types::TypeKind::Struct,
+            visibility: config.visibility.clone(),
+            is_local: true,
+            is_fundamental: false,
+            generics: Vec::new(),
+            variances: Vec::new(),
+            where_clause: None,
+            docs: None,
+            attributes: Vec::new(),
+            fields: Some(Vec::new()),
+            variants: None,
+            trait_impls: Vec::new(),
+            indirect_trait_impls: Vec::new(),
+            inherent_methods: Vec::new(),
+            layout: None,
+            source: None,
+            span: None,
+            stable_id: config.stable_id.clone(),
+        };
+        let mut info = CrateTypeInfo {
+            crate_name: "demo".to_string(),
+            items: vec![config],
+            ..Default::default()
+        };
+        info.types.insert(details.path.clone(), details);
+        let manager = stub_manager(Box::new(move |_, _, _, _, _| Ok(info.clone())));
+
+        let id = match manager.execute_query("demo", Query::ListItems, true, true) {
+            QueryResult::Success {
+                data: QueryData::Items { items, .. },
+            } => items[0].stable_id.clone(),
+            other => panic!("unexpected result: {:?}", other),
+        };
+        match manager.execute_query("demo", Query::GetTypeById { id }, true, true) {
+            QueryResult::Success {
+                data: QueryData::TypeInfo(details),
+            } => assert_eq!(details.path, "models::Config"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_listing_crates_does_not_wait_for_a_compile() {
+        let (manager, started, release) = blocking_manager("slow");
+        manager.execute_query("fast", Query::ListItems, true, true);
+
+        let manager = &manager;
+        thread::scope(|scope| {
+            scope.spawn(|| manager.execute_query("slow", Query::ListItems, true, true));
+            started.recv().unwrap();
+
+            let (listed, crates) = mpsc::channel();
+            scope.spawn(move || {
+                let result = manager.execute_query("", Query::ListCachedCrates, true, true);
+                listed.send(result).unwrap();
+            });
+            let result = crates
+                .recv_timeout(Duration::from_secs(10))
+                .expect("listing crates waited for the compile");
+            drop(release);
+            match result {
+                QueryResult::Success {
+                    data: QueryData::Crates { crates },
+                } => assert_eq!(crates, ["fast"]),
+                other => panic!("unexpected result: {:?}", other),
+            }
+        });
+    }
+
+    #[test]
+    fn test_ping_answers_during_a_compile() {
+        let (manager, started, release) = blocking_manager("slow");
+        let manager = Arc::new(manager);
+
+        let (compiling, compile_handler) = connect(&manager);
+        send(&compiling, "slow", Query::ListItems);
+        started.recv().unwrap();
+
+        let (pinging, ping_handler) = connect(&manager);
+        pinging
+            .set_read_timeout(Some(Duration::from_secs(10)))
+            .unwrap();
+        send(&pinging, "slow", Query::Ping);
+        assert!(matches!(
+            receive(&pinging),
+            QueryResult::Success {
+                data: QueryData::Pong
+            }
+        ));
+
+        drop(release);
+        assert!(matches!(receive(&compiling), QueryResult::Success { .. }));
+        drop((compiling, pinging));
+        compile_handler.join().unwrap();
+        ping_handler.join().unwrap();
+    }
+
+    #[test]
+    fn test_package_and_crate_names_share_an_entry() {
+        let index_runs = Arc::new(AtomicUsize::new(0));
+        let runs = Arc::clone(&index_runs);
+        let manager = Arc::new(stub_manager(Box::new(move |_, crate_name, _, _, _| {
+            runs.fetch_add(1, Ordering::SeqCst);
+            Ok(CrateTypeInfo {
+                crate_name: crate_name.to_string(),
+                items: vec![item("Config", ItemKind::Struct)],
+                ..Default::default()
+            })
+        })));
+
+        let (stream, handler) = connect(&manager);
+        for requested in ["my-types", "my_types"] {
+            send(&stream, requested, Query::ListItems);
+            assert!(matches!(receive(&stream), QueryResult::Success { .. }));
+        }
+        drop(stream);
+        handler.join().unwrap();
+
+        assert_eq!(index_runs.load(Ordering::SeqCst), 1);
+        assert_eq!(manager.known_crates(), ["my_types"]);
+    }
+}
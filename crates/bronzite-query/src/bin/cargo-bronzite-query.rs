@@ -1,6 +1,15 @@
 #![feature(rustc_private)]
 
+use clap::Parser;
+
 fn main() {
     env_logger::init();
+
+    // Single files are compiled by the driver directly, without cargo
+    let args = bronzite_query::Args::parse_from(std::env::args().skip(1));
+    if let Some(ref file) = args.file {
+        std::process::exit(bronzite_query::run_on_file(&args, file));
+    }
+
     rustc_plugin::cli_main(bronzite_query::BronziteQueryPlugin);
 }
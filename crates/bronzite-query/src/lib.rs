@@ -2,6 +2,7 @@
 
 extern crate rustc_abi;
 extern crate rustc_ast;
+extern crate rustc_data_structures;
 extern crate rustc_driver;
 extern crate rustc_hir;
 extern crate rustc_infer;
@@ -16,22 +17,34 @@ use rustc_infer::infer::TyCtxtInferExt;
 use rustc_trait_selection::infer::InferCtxtExt;
 
 use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
 
 use bronzite_types::{
-    AssocConstInfo, AssocTypeInfo, CrateTypeInfo, Delimiter, EnumVariantInfo, FieldInfo,
-    FunctionSignature, GenericParam, GenericParamKind, InherentImplDetails, ItemInfo, ItemKind,
-    LayoutInfo, LiteralKind, MatchArm, MethodDetails, MethodSummary, ModuleInfo, ParamInfo, Query,
-    QueryData, QueryResult, ReceiverInfo, ReexportInfo, SpanInfo, Token, TraitDetails,
-    TraitImplDetails, TraitInfo, TraitMethodInfo, TypeAliasInfo, TypeDetails, TypeKind,
-    TypeSummary, Visibility,
+    AssocConstInfo, AssocTypeInfo, ConstDetails, CrateTypeInfo, Delimiter, EnumVariantInfo,
+    FieldInfo, FieldLayoutInfo, FunctionDetails, FunctionSignature, GenericParam, GenericParamKind,
+    IndirectTraitImpl, InherentImplDetails, ItemInfo, ItemKind, LayoutInfo, LiteralKind,
+    MacroDetails, MacroKind, MatchArm, MethodCandidate, MethodDetails, MethodSummary, ModuleInfo,
+    ModuleTree, ParamInfo, Query, QueryData, QueryResult, ReceiverInfo, ReceiverKind, ReexportInfo,
+    ReturnType, SpanInfo, Token, TraitAliasInfo, TraitDetails, TraitImplDetails, TraitInfo,
+    TraitMethodInfo, TypeAliasInfo, TypeDetails, TypeKind, TypeSummary, Visibility, WherePredicate,
 };
 use clap::Parser;
 use rustc_ast::ast;
+use rustc_data_structures::sync::par_map;
 use rustc_hir as hir;
-use rustc_hir::def::DefKind;
+use rustc_hir::LangItem;
+use rustc_hir::attrs::AttributeKind;
+use rustc_hir::def::{DefKind, MacroKinds};
 use rustc_hir::def_id::{DefId, LOCAL_CRATE, LocalDefId};
-use rustc_middle::ty::{self, TyCtxt, TypingEnv};
+use rustc_middle::mir;
+use rustc_middle::ty::print::PrintTraitRefExt;
+use rustc_middle::ty::{
+    self, TyCtxt, TypeFoldable, TypeFolder, TypeSuperFoldable, TypeVisitableExt, TypingEnv,
+    elaborate,
+};
+use rustc_span::hygiene::{AstPass, ExpnKind};
 use rustc_span::symbol::sym;
+use rustc_span::{BytePos, Span};
 use serde::{Deserialize, Serialize};
 
 // Re-export key types for external users
@@ -51,6 +64,153 @@ pub struct Args {
     /// Output file for extraction (defaults to stdout)
     #[arg(long)]
     pub output: Option<String>,
+
+    /// Analyze a single `.rs` file as a library crate instead of the cargo
+    /// package in the current directory. The crate is named after the file.
+    #[arg(long)]
+    pub file: Option<String>,
+
+    /// Rust edition to compile `--file` with. Cargo packages use the edition
+    /// from their manifest.
+    #[arg(long, default_value = "2021")]
+    pub edition: String,
+
+    /// Only extract items whose path matches this pattern (e.g. "models::**").
+    /// May be given more than once to keep items matching any of them.
+    ///
+    /// Extraction is unfiltered by default so that cached results are complete;
+    /// use this when the consumer is known to only care about a subtree.
+    #[arg(long)]
+    pub filter: Vec<String>,
+
+    /// Skip method body extraction (`body_source`, `body_tokens` and default
+    /// trait method bodies). Much faster when only signatures are needed.
+    #[arg(long)]
+    pub skip_bodies: bool,
+
+    /// Only extract the item index (names, paths, kinds, modules) and skip
+    /// type, trait, impl and layout details. The daemon uses this for its
+    /// first pass and then fetches details per item with `--filter`.
+    #[arg(long)]
+    pub index_only: bool,
+
+    /// With `--index-only`, still extract details for items whose path
+    /// matches this pattern. May be given more than once. The daemon uses
+    /// this to answer the query that triggered indexing from the same compile.
+    #[arg(long)]
+    pub detail: Vec<String>,
+
+    /// Leave out everything that isn't `pub`: private items, fields and
+    /// inherent methods, and the impls of private types.
+    #[arg(long)]
+    pub public_only: bool,
+
+    /// Leave out test-only items (anything under `#[cfg(test)]`). They are
+    /// only present when `--cfg test` is passed, e.g. through `RUSTFLAGS`.
+    #[arg(long)]
+    pub exclude_tests: bool,
+
+    /// Only run on the workspace crate with this name. Other members are
+    /// still checked by cargo, but nothing is extracted or printed for them.
+    #[arg(long)]
+    pub package: Option<String>,
+
+    /// Number of rustc frontend threads (`-Zthreads`). Values above 1 let
+    /// per-item extraction run in parallel on large crates.
+    #[arg(long)]
+    pub threads: Option<usize>,
+}
+
+impl Args {
+    /// Build the extraction options described by these arguments.
+    pub fn extract_options(&self) -> ExtractOptions {
+        ExtractOptions {
+            filter: self.filter.clone(),
+            skip_bodies: self.skip_bodies,
+            index_only: self.index_only,
+            detail: self.detail.clone(),
+            public_only: self.public_only,
+            exclude_tests: self.exclude_tests,
+        }
+    }
+}
+
+/// Options controlling what `extract_crate_info` collects.
+#[derive(Debug, Clone, Default)]
+pub struct ExtractOptions {
+    /// Only extract items whose path matches one of these patterns
+    pub filter: Vec<String>,
+    /// Leave method bodies out of the extracted data
+    pub skip_bodies: bool,
+    /// Only collect `ItemInfo`s and modules
+    pub index_only: bool,
+    /// Items that still get details when `index_only` is set
+    pub detail: Vec<String>,
+    /// Drop everything that isn't `pub`
+    pub public_only: bool,
+    /// Skip items that only exist in test builds
+    pub exclude_tests: bool,
+}
+
+impl ExtractOptions {
+    /// Check whether an item path passes the configured filter.
+    fn includes(&self, path: &str) -> bool {
+        self.filter.is_empty()
+            || self
+                .filter
+                .iter()
+                .any(|pattern| bronzite_types::path_matches_pattern(path, pattern))
+    }
+
+    /// Check whether details should be extracted for an included item.
+    fn wants_details(&self, path: &str) -> bool {
+        !self.index_only
+            || self
+                .detail
+                .iter()
+                .any(|pattern| bronzite_types::path_matches_pattern(path, pattern))
+    }
+}
+
+/// Run the plugin on a single source file without cargo.
+///
+/// This invokes `bronzite-query-driver` (next to the current executable)
+/// directly as rustc, and returns its exit code.
+pub fn run_on_file(args: &Args, file: &str) -> i32 {
+    let path = std::path::Path::new(file);
+    let crate_name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().replace('-', "_"))
+        .unwrap_or_else(|| "main".to_string());
+
+    let mut driver = std::env::current_exe()
+        .expect("current executable path invalid")
+        .with_file_name("bronzite-query-driver");
+    if cfg!(windows) {
+        driver.set_extension("exe");
+    }
+
+    // These are the variables `rustc_plugin`'s cargo wrapper sets to tell the
+    // driver to run the plugin rather than plain rustc
+    let status = std::process::Command::new(driver)
+        .arg(path)
+        .args(["--crate-type", "lib", "--edition", &args.edition])
+        .arg("--crate-name")
+        .arg(&crate_name)
+        .env("RUSTC_PLUGIN_ALL_TARGETS", "")
+        .env(
+            "PLUGIN_ARGS",
+            serde_json::to_string(args).expect("Failed to serialize"),
+        )
+        .status();
+
+    match status {
+        Ok(status) => status.code().unwrap_or(-1),
+        Err(e) => {
+            eprintln!("Failed to run bronzite-query-driver: {}", e);
+            1
+        }
+    }
 }
 
 /// The Bronzite query plugin
@@ -80,15 +240,41 @@ impl rustc_plugin::RustcPlugin for BronziteQueryPlugin {
 
     fn run(
         self,
-        compiler_args: Vec<String>,
+        mut compiler_args: Vec<String>,
         plugin_args: Self::Args,
     ) -> rustc_interface::interface::Result<()> {
+        // Cargo marks a package's build script as part of the primary
+        // package too. It has to be compiled for real so it can run and
+        // generate the `OUT_DIR` sources the crate itself includes.
+        if is_build_script(&compiler_args) {
+            rustc_driver::run_compiler(&compiler_args, &mut RustcCallbacks);
+            return Ok(());
+        }
+
+        if let Some(threads) = plugin_args.threads {
+            compiler_args.push(format!("-Zthreads={}", threads));
+        }
         let mut callbacks = BronziteQueryCallbacks { args: plugin_args };
         rustc_driver::run_compiler(&compiler_args, &mut callbacks);
         Ok(())
     }
 }
 
+/// Whether rustc is being invoked on a build script, which cargo names
+/// `build_script_build` (or after the file in `package.build`).
+fn is_build_script(compiler_args: &[String]) -> bool {
+    compiler_args
+        .iter()
+        .skip_while(|arg| *arg != "--crate-name")
+        .nth(1)
+        .is_some_and(|name| name.starts_with("build_script_"))
+}
+
+/// Callbacks for compiling a crate without running the plugin.
+struct RustcCallbacks;
+
+impl rustc_driver::Callbacks for RustcCallbacks {}
+
 pub struct BronziteQueryCallbacks {
     args: Args,
 }
@@ -99,8 +285,22 @@ impl rustc_driver::Callbacks for BronziteQueryCallbacks {
         _compiler: &rustc_interface::interface::Compiler,
         tcx: TyCtxt<'_>,
     ) -> rustc_driver::Compilation {
+        if let Some(ref package) = self.args.package {
+            // Package names may use `-` where the crate name has `_`
+            if tcx.crate_name(LOCAL_CRATE).as_str() != package.replace('-', "_") {
+                return rustc_driver::Compilation::Stop;
+            }
+        }
+
+        // Extraction evaluates consts rustc itself never would, like unused
+        // impl consts. Their errors aren't the crate's, so they must neither
+        // be printed nor fail the build.
+        let dcx = tcx.dcx();
+        let errors_before = dcx.err_count();
+        dcx.make_silent();
+
         if self.args.extract {
-            let info = extract_crate_info(tcx);
+            let info = extract_crate_info(tcx, &self.args.extract_options());
             output_extracted_info(&info, &self.args.output);
         } else if let Some(ref query_str) = self.args.query {
             let query = parse_query(query_str);
@@ -108,6 +308,12 @@ impl rustc_driver::Callbacks for BronziteQueryCallbacks {
             output_query_result(&result);
         }
 
+        if errors_before == 0 && dcx.err_count() > 0 {
+            // The error count can't just be reset: the incremental cache would
+            // then try to save the failed evaluations. End the compile here.
+            let _ = std::io::Write::flush(&mut std::io::stdout());
+            std::process::exit(0);
+        }
         rustc_driver::Compilation::Stop
     }
 }
@@ -131,6 +337,12 @@ fn get_source_for_def(tcx: TyCtxt<'_>, def_id: DefId) -> Option<String> {
     get_source_for_span(tcx, span)
 }
 
+/// Source of the initializer expression of a const or static.
+fn get_init_source(tcx: TyCtxt<'_>, def_id: DefId) -> Option<String> {
+    let body = tcx.hir_maybe_body_owned_by(def_id.as_local()?)?;
+    get_source_for_span(tcx, body.value.span)
+}
+
 // ============================================================================
 // Doc Comments Extraction
 // ============================================================================
@@ -184,6 +396,12 @@ fn extract_attributes(tcx: TyCtxt<'_>, def_id: DefId) -> Vec<String> {
         .iter()
         .filter(|attr| !attr.is_doc_comment() && !attr.has_name(sym::doc))
         .filter_map(|attr| {
+            // Attributes rustc has parsed itself, like `#[inline]` or
+            // `#[automatically_derived]`, no longer have a path
+            if let hir::Attribute::Parsed(kind) = attr {
+                return Some(format!("#[{}]", parsed_attribute_name(kind)));
+            }
+
             // Reconstruct attribute from path symbols to avoid panics on parsed attributes
             // (attr.span() can panic for inline/parsed attributes in newer rustc versions)
             let path_symbols = attr.path();
@@ -197,6 +415,28 @@ fn extract_attributes(tcx: TyCtxt<'_>, def_id: DefId) -> Vec<String> {
         .collect()
 }
 
+/// The name a parsed attribute is written with, taken from its kind
+/// (`MustUse` is `must_use`).
+fn parsed_attribute_name(kind: &AttributeKind) -> String {
+    let debug = format!("{:?}", kind);
+    let variant = debug
+        .split(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or_default();
+    if variant == "Deprecation" {
+        return "deprecated".to_string();
+    }
+
+    let mut name = String::new();
+    for (i, c) in variant.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            name.push('_');
+        }
+        name.push(c.to_ascii_lowercase());
+    }
+    name
+}
+
 // ============================================================================
 // Token/AST Extraction
 // ============================================================================
@@ -456,47 +696,99 @@ fn extract_stmt_tokens(tcx: TyCtxt<'_>, stmt: &hir::Stmt<'_>) -> Token {
 // Type Resolution
 // ============================================================================
 
-/// Resolve a type alias chain to its final type
+/// Resolve a type alias chain to its final type.
+///
+/// The chain starts with the alias itself, then each alias it is written in
+/// terms of (`type A = B;`), and ends with the fully expanded type.
 fn resolve_type_alias_chain(tcx: TyCtxt<'_>, def_id: DefId) -> (String, Vec<String>) {
-    let mut chain = Vec::new();
+    let mut chain = vec![tcx.def_path_str(def_id)];
     let mut current_def_id = def_id;
     let mut seen = std::collections::HashSet::new();
 
-    loop {
-        if seen.contains(&current_def_id) {
-            break; // Avoid infinite loops
-        }
-        seen.insert(current_def_id);
-
-        let ty = tcx.type_of(current_def_id).skip_binder();
-        let ty_str = format!("{:?}", ty);
-        chain.push(ty_str.clone());
-
-        // Check if this type is itself an alias
-        if let ty::TyKind::Alias(ty::Projection | ty::Opaque, alias_ty) = ty.kind() {
-            current_def_id = alias_ty.def_id;
-        } else if let Some(adt) = ty.ty_adt_def() {
-            // If it's an ADT, we've reached the end
-            chain.push(tcx.def_path_str(adt.did()));
+    // `type_of` sees through nested aliases, so the hops have to be read
+    // from the HIR. Avoid infinite loops on (invalid) cyclic aliases.
+    while seen.insert(current_def_id) {
+        let Some(next) = aliased_type_alias(tcx, current_def_id) else {
             break;
-        } else {
-            break;
-        }
+        };
+        chain.push(tcx.def_path_str(next));
+        current_def_id = next;
     }
 
-    let resolved = chain.last().cloned().unwrap_or_default();
+    let ty = tcx.type_of(current_def_id).skip_binder();
+    chain.push(format!("{:?}", ty));
+
+    let resolved = match ty.ty_adt_def() {
+        Some(adt) => tcx.def_path_str(adt.did()),
+        None => format!("{:?}", ty),
+    };
     (resolved, chain)
 }
 
+/// If a local type alias is written directly as another type alias, return
+/// that alias.
+fn aliased_type_alias(tcx: TyCtxt<'_>, def_id: DefId) -> Option<DefId> {
+    let hir_ty = tcx.hir_node_by_def_id(def_id.as_local()?).alias_ty()?;
+    match hir_ty.kind {
+        hir::TyKind::Path(hir::QPath::Resolved(None, path)) => match path.res {
+            hir::def::Res::Def(DefKind::TyAlias, target) => Some(target),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 /// Get fully resolved type string
 fn get_resolved_type<'tcx>(tcx: TyCtxt<'tcx>, ty: ty::Ty<'tcx>) -> String {
     // Attempt to normalize the type
     let typing_env = TypingEnv::fully_monomorphized();
 
     match tcx.try_normalize_erasing_regions(typing_env, ty) {
-        Ok(normalized) => format!("{:?}", normalized),
-        Err(_) => format!("{:?}", ty),
+        Ok(normalized) => bronzite_types::tidy_const_args(&format!("{:?}", normalized)),
+        Err(_) => format_field_type(tcx, ty),
+    }
+}
+
+/// The crate defining a named type, after resolving aliases. `None` for
+/// primitives and structural types like references or tuples.
+fn defining_crate<'tcx>(tcx: TyCtxt<'tcx>, ty: ty::Ty<'tcx>) -> Option<String> {
+    let ty = tcx
+        .try_normalize_erasing_regions(TypingEnv::fully_monomorphized(), ty)
+        .unwrap_or(ty);
+    let def_id = match ty.kind() {
+        ty::TyKind::Adt(adt_def, _) => adt_def.did(),
+        ty::TyKind::Foreign(def_id) => *def_id,
+        _ => return None,
+    };
+    Some(tcx.crate_name(def_id.krate).to_string())
+}
+
+/// Render a field type as written, but with constant array lengths and
+/// const arguments evaluated where possible (`[u8; LEN]` shows as
+/// `[u8; 32]`). Lengths that depend on a const parameter stay symbolic.
+fn format_field_type<'tcx>(tcx: TyCtxt<'tcx>, ty: ty::Ty<'tcx>) -> String {
+    struct ConstEvaluator<'tcx> {
+        tcx: TyCtxt<'tcx>,
+    }
+
+    impl<'tcx> TypeFolder<TyCtxt<'tcx>> for ConstEvaluator<'tcx> {
+        fn cx(&self) -> TyCtxt<'tcx> {
+            self.tcx
+        }
+
+        fn fold_const(&mut self, ct: ty::Const<'tcx>) -> ty::Const<'tcx> {
+            let ct = ct.super_fold_with(self);
+            if !matches!(ct.kind(), ty::ConstKind::Unevaluated(_)) || ct.has_param() {
+                return ct;
+            }
+            self.tcx
+                .try_normalize_erasing_regions(TypingEnv::fully_monomorphized(), ct)
+                .unwrap_or(ct)
+        }
     }
+
+    let ty = ty.fold_with(&mut ConstEvaluator { tcx });
+    bronzite_types::tidy_const_args(&format!("{:?}", ty))
 }
 
 // ============================================================================
@@ -504,91 +796,234 @@ fn get_resolved_type<'tcx>(tcx: TyCtxt<'tcx>, ty: ty::Ty<'tcx>) -> String {
 // ============================================================================
 
 /// Extract all type information from the crate
-pub fn extract_crate_info(tcx: TyCtxt<'_>) -> CrateTypeInfo {
+pub fn extract_crate_info(tcx: TyCtxt<'_>, options: &ExtractOptions) -> CrateTypeInfo {
     let crate_name = tcx.crate_name(LOCAL_CRATE).to_string();
 
     let mut info = CrateTypeInfo {
         crate_name,
         crate_version: None,
+        edition: tcx.sess.edition().to_string(),
+        rustc_version: rustc_interface::util::rustc_version_str()
+            .unwrap_or("unknown")
+            .to_string(),
         items: Vec::new(),
         types: HashMap::new(),
         traits: HashMap::new(),
         trait_impls: HashMap::new(),
         inherent_impls: HashMap::new(),
+        functions: HashMap::new(),
+        macros: HashMap::new(),
+        consts: HashMap::new(),
         type_aliases: HashMap::new(),
+        trait_aliases: HashMap::new(),
         layouts: HashMap::new(),
         modules: HashMap::new(),
+        bodies_included: !options.skip_bodies,
+        warnings: Vec::new(),
     };
 
     let crate_items = tcx.hir_crate_items(());
 
-    // First pass: collect all items
-    for item_id in crate_items.free_items() {
-        let def_id = item_id.owner_id.to_def_id();
-        let local_def_id = item_id.owner_id.def_id;
-        let def_kind = tcx.def_kind(def_id);
-        let path = tcx.def_path_str(def_id);
+    // Items are independent of each other, so extract them in parallel (this
+    // only fans out when rustc runs with more than one frontend thread) and
+    // merge the results in item order afterwards. A panic while extracting one
+    // item is recorded as a warning instead of losing the whole crate.
+    let free_items: Vec<hir::ItemId> = crate_items.free_items().collect();
+    let extracted: Vec<Result<Option<ExtractedItem>, String>> = par_map(free_items, |item_id| {
+        panic::catch_unwind(AssertUnwindSafe(|| extract_item(tcx, item_id, options))).map_err(
+            |payload| {
+                format!(
+                    "failed to extract `{}`: {}",
+                    tcx.def_path_str(item_id.owner_id.to_def_id()),
+                    panic_message(&*payload)
+                )
+            },
+        )
+    });
+
+    let mut items = Vec::new();
+    for result in extracted {
+        match result {
+            Ok(Some(item)) => items.push(item),
+            Ok(None) => {}
+            Err(warning) => info.warnings.push(warning),
+        }
+    }
 
-        // Extract basic item info
-        if let Some(item_info) = extract_item_info(tcx, def_id) {
+    for item in items {
+        if let Some(item_info) = item.item_info {
             info.items.push(item_info);
         }
 
-        // Extract detailed information based on kind
-        match def_kind {
-            DefKind::Struct | DefKind::Enum | DefKind::Union => {
-                if let Some(type_details) = extract_type_details(tcx, local_def_id) {
-                    if let Some(layout) = extract_layout_info(tcx, local_def_id) {
-                        info.layouts.insert(path.clone(), layout);
-                    }
-                    info.types.insert(path.clone(), type_details);
+        let path = item.path;
+        match item.details {
+            Some(ExtractedDetails::Type(type_details, layout)) => {
+                if let Some(layout) = layout {
+                    info.layouts.insert(path.clone(), layout);
                 }
+                info.types.insert(path, *type_details);
             }
-            DefKind::Trait => {
-                if let Some(trait_details) = extract_trait_details(tcx, def_id) {
-                    info.traits.insert(path.clone(), trait_details);
-                }
+            Some(ExtractedDetails::Trait(trait_details)) => {
+                info.traits.insert(path, *trait_details);
             }
-            DefKind::TyAlias => {
-                if let Some(alias_info) = extract_type_alias(tcx, def_id) {
-                    info.type_aliases.insert(path.clone(), alias_info);
-                }
+            Some(ExtractedDetails::TypeAlias(alias_info)) => {
+                info.type_aliases.insert(path, alias_info);
             }
-            DefKind::Mod => {
-                if let Some(module_info) = extract_module_info(tcx, local_def_id) {
-                    info.modules.insert(path.clone(), module_info);
-                }
+            Some(ExtractedDetails::TraitAlias(alias_info)) => {
+                info.trait_aliases.insert(path, alias_info);
             }
-            DefKind::Impl { .. } => {
-                if let Some(trait_ref) = tcx.impl_trait_ref(def_id) {
-                    // This is a trait impl
-                    if let Some(impl_details) = extract_trait_impl_details(tcx, def_id) {
-                        let self_ty = trait_ref.skip_binder().self_ty();
-                        let self_ty_str = get_type_path_string(tcx, self_ty);
-                        info.trait_impls
-                            .entry(self_ty_str)
-                            .or_default()
-                            .push(impl_details);
-                    }
-                } else {
-                    // This is an inherent impl
-                    if let Some(impl_details) = extract_inherent_impl_details(tcx, def_id) {
-                        let self_ty = tcx.type_of(def_id).skip_binder();
-                        let self_ty_str = get_type_path_string(tcx, self_ty);
-                        info.inherent_impls
-                            .entry(self_ty_str)
-                            .or_default()
-                            .push(impl_details);
-                    }
-                }
+            Some(ExtractedDetails::Function(function_details)) => {
+                info.functions.insert(path, *function_details);
             }
-            _ => {}
+            Some(ExtractedDetails::Macro(macro_details)) => {
+                info.macros
+                    .insert(macro_details.path.clone(), *macro_details);
+            }
+            Some(ExtractedDetails::Const(const_details)) => {
+                info.consts.insert(path, *const_details);
+            }
+            Some(ExtractedDetails::Module(module_info)) => {
+                info.modules.insert(path, module_info);
+            }
+            Some(ExtractedDetails::TraitImpl(self_ty, impl_details)) => {
+                info.trait_impls
+                    .entry(self_ty)
+                    .or_default()
+                    .push(*impl_details);
+            }
+            Some(ExtractedDetails::InherentImpl(self_ty, impl_details)) => {
+                info.inherent_impls
+                    .entry(self_ty)
+                    .or_default()
+                    .push(*impl_details);
+            }
+            None => {}
         }
     }
 
+    if options.public_only {
+        info.retain_public();
+    }
+
     info
 }
 
+/// Get a readable message out of a panic payload.
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic"
+    }
+}
+
+/// Everything extracted for a single free item.
+struct ExtractedItem {
+    path: String,
+    item_info: Option<ItemInfo>,
+    details: Option<ExtractedDetails>,
+}
+
+/// Kind-specific details for an extracted item.
+enum ExtractedDetails {
+    Type(Box<TypeDetails>, Option<LayoutInfo>),
+    Trait(Box<TraitDetails>),
+    TypeAlias(TypeAliasInfo),
+    TraitAlias(TraitAliasInfo),
+    Function(Box<FunctionDetails>),
+    Macro(Box<MacroDetails>),
+    Const(Box<ConstDetails>),
+    Module(ModuleInfo),
+    /// Trait impl, keyed by its self type
+    TraitImpl(String, Box<TraitImplDetails>),
+    /// Inherent impl, keyed by its self type
+    InherentImpl(String, Box<InherentImplDetails>),
+}
+
+/// Extract one free item. Returns `None` if the item is filtered out.
+fn extract_item(
+    tcx: TyCtxt<'_>,
+    item_id: hir::ItemId,
+    options: &ExtractOptions,
+) -> Option<ExtractedItem> {
+    let def_id = item_id.owner_id.to_def_id();
+    let local_def_id = item_id.owner_id.def_id;
+    let def_kind = tcx.def_kind(def_id);
+    let path = tcx.def_path_str(def_id);
+
+    // Impls are filtered by their self type rather than their own path
+    let filter_path = match def_kind {
+        DefKind::Impl { .. } => get_type_path_string(tcx, tcx.type_of(def_id).skip_binder()),
+        _ => path.clone(),
+    };
+    if !options.includes(&filter_path) {
+        return None;
+    }
+    if options.exclude_tests && is_test_item(tcx, def_id) {
+        return None;
+    }
+
+    let item_info = extract_item_info(tcx, def_id);
+
+    let details = match def_kind {
+        DefKind::Mod => extract_module_info(tcx, local_def_id).map(ExtractedDetails::Module),
+        _ if !options.wants_details(&filter_path) => None,
+        DefKind::Struct | DefKind::Enum | DefKind::Union => extract_type_details(tcx, local_def_id)
+            .map(|type_details| {
+                let layout = extract_layout_info(tcx, local_def_id);
+                ExtractedDetails::Type(Box::new(type_details), layout)
+            }),
+        DefKind::Trait => extract_trait_details(tcx, def_id, options)
+            .map(|trait_details| ExtractedDetails::Trait(Box::new(trait_details))),
+        DefKind::TyAlias => extract_type_alias(tcx, def_id).map(ExtractedDetails::TypeAlias),
+        DefKind::TraitAlias => Some(ExtractedDetails::TraitAlias(extract_trait_alias(
+            tcx, def_id,
+        ))),
+        DefKind::Macro(_) => extract_macro_details(tcx, def_id)
+            .map(|macro_details| ExtractedDetails::Macro(Box::new(macro_details))),
+        // Proc macro entry points can't be called as functions
+        DefKind::Fn => match extract_macro_details(tcx, def_id) {
+            Some(macro_details) => Some(ExtractedDetails::Macro(Box::new(macro_details))),
+            None => Some(ExtractedDetails::Function(Box::new(
+                extract_function_details(tcx, def_id, options),
+            ))),
+        },
+        DefKind::Const | DefKind::Static { .. } => Some(ExtractedDetails::Const(Box::new(
+            extract_const_details(tcx, def_id),
+        ))),
+        DefKind::Impl { .. } => {
+            if let Some(trait_ref) = tcx.impl_trait_ref(def_id) {
+                // This is a trait impl
+                extract_trait_impl_details(tcx, def_id, options).map(|impl_details| {
+                    let self_ty = trait_ref.skip_binder().self_ty();
+                    ExtractedDetails::TraitImpl(
+                        get_type_path_string(tcx, self_ty),
+                        Box::new(impl_details),
+                    )
+                })
+            } else {
+                // This is an inherent impl
+                extract_inherent_impl_details(tcx, def_id, options).map(|impl_details| {
+                    let self_ty = tcx.type_of(def_id).skip_binder();
+                    ExtractedDetails::InherentImpl(
+                        get_type_path_string(tcx, self_ty),
+                        Box::new(impl_details),
+                    )
+                })
+            }
+        }
+        _ => None,
+    };
+
+    Some(ExtractedItem {
+        path,
+        item_info,
+        details,
+    })
+}
+
 /// Get a clean path string for a type
 fn get_type_path_string(tcx: TyCtxt<'_>, ty: ty::Ty<'_>) -> String {
     if let Some(adt) = ty.ty_adt_def() {
@@ -641,15 +1076,64 @@ fn extract_item_info(tcx: TyCtxt<'_>, def_id: DefId) -> Option<ItemInfo> {
         kind,
         visibility,
         span,
+        is_test: is_test_item(tcx, def_id),
+        stable_id: stable_id(tcx, def_id),
     })
 }
 
+/// Identify an item by its def path hash rather than its path.
+fn stable_id(tcx: TyCtxt<'_>, def_id: DefId) -> String {
+    tcx.def_path_hash(def_id).0.to_hex()
+}
+
+/// Check whether an item only exists in test builds: it (or an enclosing
+/// module) is gated on `#[cfg(test)]`, it is part of a `#[test]`, or the
+/// test harness generated it.
+fn is_test_item(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    let mut current = def_id.as_local();
+    while let Some(local_def_id) = current {
+        let expn = tcx.def_span(local_def_id).ctxt().outer_expn_data();
+        if expn.kind == ExpnKind::AstPass(AstPass::TestHarness) {
+            return true;
+        }
+        let attrs = tcx.hir_attrs(tcx.local_def_id_to_hir_id(local_def_id));
+        let is_test = attrs.iter().any(|attr| {
+            // Satisfied `#[cfg]`s are kept as `cfg_trace` attributes
+            attr.has_name(sym::rustc_test_marker)
+                || (attr.has_name(sym::cfg_trace)
+                    && attr
+                        .meta_item_list()
+                        .is_some_and(|list| list.iter().any(|meta| meta.has_name(sym::test))))
+        });
+        if is_test {
+            return true;
+        }
+        current = tcx.opt_local_parent(local_def_id);
+    }
+    false
+}
+
 fn extract_visibility(tcx: TyCtxt<'_>, def_id: DefId) -> Visibility {
-    let vis = tcx.visibility(def_id);
-    if vis.is_public() {
-        Visibility::Public
-    } else {
+    let module = match tcx.visibility(def_id) {
+        ty::Visibility::Public => return Visibility::Public,
+        ty::Visibility::Restricted(module) => module,
+    };
+    // Restricted to the module the item is defined in is plain private. At
+    // the crate root that's indistinguishable from `pub(crate)`, and reported
+    // as such since the whole crate can access it.
+    let defining_module = def_id
+        .as_local()
+        .map(|local| tcx.parent_module_from_def_id(local).to_def_id());
+    if !module.is_local() {
         Visibility::Private
+    } else if module.is_crate_root() {
+        Visibility::Crate
+    } else if defining_module == Some(module) {
+        Visibility::Private
+    } else {
+        Visibility::Restricted {
+            path: tcx.def_path_str(module),
+        }
     }
 }
 
@@ -709,7 +1193,7 @@ fn extract_type_details(tcx: TyCtxt<'_>, local_def_id: LocalDefId) -> Option<Typ
     };
 
     // Get trait impls for this type
-    let trait_impls = get_trait_impl_paths(tcx, def_id);
+    let (trait_impls, indirect_trait_impls) = get_trait_impl_paths(tcx, def_id);
 
     // Get inherent methods
     let inherent_methods = extract_inherent_method_summaries(tcx, def_id);
@@ -722,20 +1206,42 @@ fn extract_type_details(tcx: TyCtxt<'_>, local_def_id: LocalDefId) -> Option<Typ
         path,
         kind,
         visibility,
+        is_local: def_id.is_local(),
+        is_fundamental: tcx.adt_def(def_id).is_fundamental(),
         generics,
+        variances: extract_variances(tcx, def_id),
         where_clause,
         docs,
         attributes,
         fields,
         variants,
         trait_impls,
+        indirect_trait_impls,
         inherent_methods,
         layout,
         source,
         span,
+        stable_id: stable_id(tcx, def_id),
     })
 }
 
+/// The inferred variance of each generic parameter of a type, in the order
+/// of [`extract_generics`].
+fn extract_variances(tcx: TyCtxt<'_>, def_id: DefId) -> Vec<String> {
+    tcx.variances_of(def_id)
+        .iter()
+        .map(|variance| {
+            match variance {
+                ty::Variance::Covariant => "covariant",
+                ty::Variance::Contravariant => "contravariant",
+                ty::Variance::Invariant => "invariant",
+                ty::Variance::Bivariant => "bivariant",
+            }
+            .to_string()
+        })
+        .collect()
+}
+
 fn extract_where_clause(tcx: TyCtxt<'_>, def_id: DefId) -> Option<String> {
     if !def_id.is_local() {
         return None;
@@ -767,16 +1273,26 @@ fn extract_generics(tcx: TyCtxt<'_>, def_id: DefId) -> Vec<GenericParam> {
         .iter()
         .filter_map(|param| {
             let name = param.name.to_string();
-            if name == "Self" {
+            // `impl Trait` arguments can't be named, so they're reported
+            // in the parameter types instead
+            if name == "Self"
+                || matches!(
+                    param.kind,
+                    ty::GenericParamDefKind::Type {
+                        synthetic: true,
+                        ..
+                    }
+                )
+            {
                 return None;
             }
 
             let kind = match param.kind {
                 ty::GenericParamDefKind::Lifetime => GenericParamKind::Lifetime,
                 ty::GenericParamDefKind::Type { .. } => GenericParamKind::Type,
-                ty::GenericParamDefKind::Const { .. } => {
-                    GenericParamKind::Const { ty: String::new() }
-                }
+                ty::GenericParamDefKind::Const { .. } => GenericParamKind::Const {
+                    ty: tcx.type_of(param.def_id).instantiate_identity().to_string(),
+                },
             };
 
             // Get bounds from predicates
@@ -827,7 +1343,7 @@ fn extract_struct_fields(tcx: TyCtxt<'_>, local_def_id: LocalDefId) -> Vec<Field
             FieldInfo {
                 name: Some(field.name.to_string()),
                 index,
-                ty: format!("{:?}", ty),
+                ty: format_field_type(tcx, ty),
                 resolved_ty: Some(get_resolved_type(tcx, ty)),
                 visibility,
                 docs,
@@ -835,6 +1351,7 @@ fn extract_struct_fields(tcx: TyCtxt<'_>, local_def_id: LocalDefId) -> Vec<Field
                 offset: None, // Filled in by layout
                 size: None,
                 span: extract_span_info(tcx, field.did),
+                defined_in_crate: defining_crate(tcx, ty),
             }
         })
         .collect()
@@ -844,6 +1361,12 @@ fn extract_enum_variants(tcx: TyCtxt<'_>, local_def_id: LocalDefId) -> Vec<EnumV
     let def_id = local_def_id.to_def_id();
     let adt_def = tcx.adt_def(def_id);
 
+    // Evaluated discriminants, explicit or implicit, in variant order
+    let discriminants: Vec<String> = adt_def
+        .discriminants(tcx)
+        .map(|(_, discr)| discr.to_string())
+        .collect();
+
     adt_def
         .variants()
         .iter()
@@ -869,7 +1392,7 @@ fn extract_enum_variants(tcx: TyCtxt<'_>, local_def_id: LocalDefId) -> Vec<EnumV
                     FieldInfo {
                         name,
                         index: field_index,
-                        ty: format!("{:?}", ty),
+                        ty: format_field_type(tcx, ty),
                         resolved_ty: Some(get_resolved_type(tcx, ty)),
                         visibility,
                         docs: extract_docs(tcx, field.did),
@@ -877,24 +1400,16 @@ fn extract_enum_variants(tcx: TyCtxt<'_>, local_def_id: LocalDefId) -> Vec<EnumV
                         offset: None,
                         size: None,
                         span: extract_span_info(tcx, field.did),
+                        defined_in_crate: defining_crate(tcx, ty),
                     }
                 })
                 .collect();
 
-            // Get discriminant value if explicit
-            let discriminant = match variant.discr {
-                ty::VariantDiscr::Explicit(def_id) => {
-                    // Try to get the evaluated value
-                    Some(format!("{:?}", def_id))
-                }
-                ty::VariantDiscr::Relative(offset) => Some(format!("{}", offset)),
-            };
-
             EnumVariantInfo {
                 name: variant.name.to_string(),
                 index,
                 fields,
-                discriminant,
+                discriminant: discriminants.get(index).cloned(),
                 docs,
                 attributes,
                 span: extract_span_info(tcx, variant.def_id),
@@ -906,13 +1421,45 @@ fn extract_enum_variants(tcx: TyCtxt<'_>, local_def_id: LocalDefId) -> Vec<EnumV
 fn extract_layout_info(tcx: TyCtxt<'_>, local_def_id: LocalDefId) -> Option<LayoutInfo> {
     let def_id = local_def_id.to_def_id();
     let ty = tcx.type_of(def_id).skip_binder();
+    extract_ty_layout(tcx, ty)
+}
 
+/// Compute layout information for a type. Fails for types that are still
+/// generic, since their layout depends on the arguments.
+fn extract_ty_layout<'tcx>(tcx: TyCtxt<'tcx>, ty: ty::Ty<'tcx>) -> Option<LayoutInfo> {
     let typing_env = TypingEnv::fully_monomorphized();
     let layout = tcx.layout_of(typing_env.as_query_input(ty)).ok()?;
 
     let size = layout.size.bytes() as usize;
     let align = layout.align.abi.bytes() as usize;
 
+    // Union fields all start at offset 0, which is what the union's
+    // field shape reports for each of them
+    let field_offsets = match ty.kind() {
+        ty::Adt(adt_def, args) if adt_def.is_struct() || adt_def.is_union() => Some(
+            adt_def
+                .non_enum_variant()
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| {
+                    let field_ty = field.ty(tcx, args);
+                    let size = tcx
+                        .layout_of(typing_env.as_query_input(field_ty))
+                        .map_or(0, |field_layout| field_layout.size.bytes() as usize);
+                    let name = field.name.to_string();
+                    FieldLayoutInfo {
+                        name: name.parse::<usize>().is_err().then_some(name),
+                        index,
+                        offset: layout.fields.offset(index).bytes() as usize,
+                        size,
+                    }
+                })
+                .collect(),
+        ),
+        _ => None,
+    };
+
     let is_copy = tcx.type_is_copy_modulo_regions(typing_env, ty);
     let is_sized = ty.is_sized(tcx, typing_env);
 
@@ -923,7 +1470,7 @@ fn extract_layout_info(tcx: TyCtxt<'_>, local_def_id: LocalDefId) -> Option<Layo
     Some(LayoutInfo {
         size,
         align,
-        field_offsets: None,
+        field_offsets,
         variants: None,
         is_sized,
         is_copy,
@@ -951,8 +1498,16 @@ fn check_trait_impl<'tcx>(tcx: TyCtxt<'tcx>, ty: ty::Ty<'tcx>, trait_path: &str)
     false
 }
 
-fn get_trait_impl_paths(tcx: TyCtxt<'_>, type_def_id: DefId) -> Vec<String> {
+/// The traits the crate implements for a type. The paths cover impls for
+/// the type itself and blanket impls (`impl<T: Display> Trait for T`) that
+/// apply to it; impls for the type behind references or single-argument
+/// wrappers (`&Type`, `Box<Type>`) are returned separately.
+fn get_trait_impl_paths(
+    tcx: TyCtxt<'_>,
+    type_def_id: DefId,
+) -> (Vec<String>, Vec<IndirectTraitImpl>) {
     let mut trait_paths = Vec::new();
+    let mut indirect = Vec::new();
     let crate_items = tcx.hir_crate_items(());
 
     for item_id in crate_items.free_items() {
@@ -961,21 +1516,66 @@ fn get_trait_impl_paths(tcx: TyCtxt<'_>, type_def_id: DefId) -> Vec<String> {
             continue;
         }
 
-        if let Some(trait_ref) = tcx.impl_trait_ref(impl_def_id) {
-            let impl_self_ty = trait_ref.skip_binder().self_ty();
-            let matches = match impl_self_ty.ty_adt_def() {
-                Some(adt) => adt.did() == type_def_id,
-                None => false,
-            };
+        let Some(trait_ref) = tcx.impl_trait_ref(impl_def_id) else {
+            continue;
+        };
+        let trait_ref = trait_ref.skip_binder();
+        let impl_self_ty = trait_ref.self_ty();
+        let trait_path = tcx.def_path_str(trait_ref.def_id);
+
+        if impl_self_ty
+            .ty_adt_def()
+            .is_some_and(|adt| adt.did() == type_def_id)
+        {
+            trait_paths.push(trait_path);
+        } else if wraps_adt(tcx, impl_self_ty, type_def_id) {
+            indirect.push(IndirectTraitImpl {
+                trait_path,
+                self_ty: ty::print::with_no_trimmed_paths!(impl_self_ty.to_string()),
+            });
+        } else if matches!(impl_self_ty.kind(), ty::Param(_))
+            && trait_ref.args.len() == 1
+            && blanket_impl_applies(tcx, trait_ref.def_id, type_def_id)
+        {
+            trait_paths.push(trait_path);
+        }
+    }
+
+    (trait_paths, indirect)
+}
 
-            if matches {
-                let trait_path = tcx.def_path_str(trait_ref.skip_binder().def_id);
-                trait_paths.push(trait_path);
+/// Whether `ty` is the ADT `adt_def_id` behind one or more references or
+/// wrappers with a single type argument (`&T`, `Box<T>`, `Arc<&T>`).
+/// Arguments left at their defaults, like `Box`'s allocator, don't count.
+fn wraps_adt<'tcx>(tcx: TyCtxt<'tcx>, ty: ty::Ty<'tcx>, adt_def_id: DefId) -> bool {
+    let mut ty = ty;
+    loop {
+        ty = match ty.kind() {
+            ty::Ref(_, inner, _) => *inner,
+            ty::Adt(adt, args) if adt.did() != adt_def_id => {
+                let args = tcx.generics_of(adt.did()).own_args_no_defaults(tcx, args);
+                let mut types = args.iter().filter_map(|arg| arg.as_type());
+                match (types.next(), types.next()) {
+                    (Some(inner), None) => inner,
+                    _ => return false,
+                }
             }
+            _ => return false,
+        };
+        if ty.ty_adt_def().is_some_and(|adt| adt.did() == adt_def_id) {
+            return true;
         }
     }
+}
 
-    trait_paths
+/// Whether a type, with its own generic parameters left abstract,
+/// satisfies a blanket impl of `trait_def_id`.
+fn blanket_impl_applies(tcx: TyCtxt<'_>, trait_def_id: DefId, type_def_id: DefId) -> bool {
+    let ty = tcx.type_of(type_def_id).instantiate_identity();
+    let infcx = tcx.infer_ctxt().build(ty::TypingMode::non_body_analysis());
+    infcx
+        .type_implements_trait(trait_def_id, [ty], tcx.param_env(type_def_id))
+        .must_apply_modulo_regions()
 }
 
 fn extract_inherent_method_summaries(tcx: TyCtxt<'_>, def_id: DefId) -> Vec<MethodSummary> {
@@ -990,9 +1590,12 @@ fn extract_inherent_method_summaries(tcx: TyCtxt<'_>, def_id: DefId) -> Vec<Meth
                     name: item.name().to_string(),
                     path: tcx.def_path_str(item_def_id),
                     signature: format!("{:?}", sig),
+                    visibility: extract_visibility(tcx, item_def_id),
+                    is_method: item.is_method(),
                     is_unsafe: sig.safety().is_unsafe(),
                     is_const: tcx.is_const_fn(item_def_id),
                     is_async: tcx.asyncness(item_def_id).is_async(),
+                    attributes: extract_attributes(tcx, item_def_id),
                 });
             }
         }
@@ -1001,7 +1604,11 @@ fn extract_inherent_method_summaries(tcx: TyCtxt<'_>, def_id: DefId) -> Vec<Meth
     methods
 }
 
-fn extract_trait_details(tcx: TyCtxt<'_>, trait_def_id: DefId) -> Option<TraitDetails> {
+fn extract_trait_details(
+    tcx: TyCtxt<'_>,
+    trait_def_id: DefId,
+    options: &ExtractOptions,
+) -> Option<TraitDetails> {
     let name = tcx.item_name(trait_def_id).to_string();
     let path = tcx.def_path_str(trait_def_id);
     let visibility = extract_visibility(tcx, trait_def_id);
@@ -1034,7 +1641,23 @@ fn extract_trait_details(tcx: TyCtxt<'_>, trait_def_id: DefId) -> Option<TraitDe
         })
         .collect();
 
-    let methods = extract_trait_methods(tcx, trait_def_id);
+    let all_supertraits: Vec<String> = elaborate::supertrait_def_ids(tcx, trait_def_id)
+        .filter(|&def_id| def_id != trait_def_id)
+        .map(|def_id| tcx.def_path_str(def_id))
+        .collect();
+
+    // A supertrait downstream code cannot name, either because it is not
+    // `pub` or because it sits in a private module, seals the trait
+    let is_sealed = elaborate::supertrait_def_ids(tcx, trait_def_id)
+        .filter(|&def_id| def_id != trait_def_id)
+        .any(|def_id| {
+            extract_visibility(tcx, def_id) != Visibility::Public
+                || def_id
+                    .as_local()
+                    .is_some_and(|local| !tcx.effective_visibilities(()).is_exported(local))
+        });
+
+    let methods = extract_trait_methods(tcx, trait_def_id, options);
     let assoc_types = extract_trait_assoc_types(tcx, trait_def_id);
     let assoc_consts = extract_trait_assoc_consts(tcx, trait_def_id);
     let implementors = get_trait_implementors(tcx, trait_def_id);
@@ -1048,6 +1671,8 @@ fn extract_trait_details(tcx: TyCtxt<'_>, trait_def_id: DefId) -> Option<TraitDe
         is_auto,
         is_unsafe,
         supertraits,
+        all_supertraits,
+        is_sealed,
         methods,
         assoc_types,
         assoc_consts,
@@ -1059,7 +1684,11 @@ fn extract_trait_details(tcx: TyCtxt<'_>, trait_def_id: DefId) -> Option<TraitDe
     })
 }
 
-fn extract_trait_methods(tcx: TyCtxt<'_>, trait_def_id: DefId) -> Vec<TraitMethodInfo> {
+fn extract_trait_methods(
+    tcx: TyCtxt<'_>,
+    trait_def_id: DefId,
+    options: &ExtractOptions,
+) -> Vec<TraitMethodInfo> {
     tcx.associated_item_def_ids(trait_def_id)
         .iter()
         .filter_map(|&item_def_id| {
@@ -1072,10 +1701,13 @@ fn extract_trait_methods(tcx: TyCtxt<'_>, trait_def_id: DefId) -> Vec<TraitMetho
             let has_default = item.defaultness(tcx).has_value();
             let docs = extract_docs(tcx, item_def_id);
             let attributes = extract_attributes(tcx, item_def_id);
-            let default_body = if has_default {
-                get_source_for_def(tcx, item_def_id)
+            let (default_body, default_body_tokens) = if has_default && !options.skip_bodies {
+                (
+                    get_source_for_def(tcx, item_def_id),
+                    extract_body_tokens(tcx, item_def_id),
+                )
             } else {
-                None
+                (None, None)
             };
 
             Some(TraitMethodInfo {
@@ -1084,7 +1716,15 @@ fn extract_trait_methods(tcx: TyCtxt<'_>, trait_def_id: DefId) -> Vec<TraitMetho
                 parsed_signature: parse_fn_signature(tcx, item_def_id),
                 has_default,
                 default_body,
+                default_body_tokens,
                 is_unsafe: sig.safety().is_unsafe(),
+                is_const: tcx.is_const_fn(item_def_id),
+                is_async: tcx.asyncness(item_def_id).is_async(),
+                is_dyn_compatible: rustc_trait_selection::traits::is_vtable_safe_method(
+                    tcx,
+                    trait_def_id,
+                    item,
+                ),
                 docs,
                 attributes,
                 span: extract_span_info(tcx, item_def_id),
@@ -1098,7 +1738,9 @@ fn extract_trait_assoc_types(tcx: TyCtxt<'_>, trait_def_id: DefId) -> Vec<AssocT
         .iter()
         .filter_map(|&item_def_id| {
             let item = tcx.associated_item(item_def_id);
-            if !matches!(item.kind, ty::AssocKind::Type { .. }) {
+            // Skip the unnamed types synthesized for `async fn` and
+            // return-position `impl Trait` in traits
+            if !matches!(item.kind, ty::AssocKind::Type { .. }) || item.is_impl_trait_in_trait() {
                 return None;
             }
 
@@ -1142,6 +1784,7 @@ fn extract_trait_assoc_consts(tcx: TyCtxt<'_>, trait_def_id: DefId) -> Vec<Assoc
                 name: item.name().to_string(),
                 ty: format!("{:?}", ty),
                 value: None,
+                init_source: get_init_source(tcx, item_def_id),
                 docs,
                 span: extract_span_info(tcx, item_def_id),
             })
@@ -1170,12 +1813,25 @@ fn get_trait_implementors(tcx: TyCtxt<'_>, trait_def_id: DefId) -> Vec<String> {
     implementors
 }
 
-fn extract_trait_impl_details(tcx: TyCtxt<'_>, impl_def_id: DefId) -> Option<TraitImplDetails> {
+fn extract_trait_impl_details(
+    tcx: TyCtxt<'_>,
+    impl_def_id: DefId,
+    options: &ExtractOptions,
+) -> Option<TraitImplDetails> {
     let trait_ref = tcx.impl_trait_ref(impl_def_id)?;
     let trait_ref = trait_ref.skip_binder();
 
     let self_ty = get_type_path_string(tcx, trait_ref.self_ty());
-    let trait_path = tcx.def_path_str(trait_ref.def_id);
+    // Keep the trait's generic args, so `From<String>` and `From<u64>` impls
+    // stay distinguishable
+    let trait_path = ty::print::with_no_trimmed_paths!(
+        ty::print::PrintTraitRefExt::print_only_trait_path(trait_ref).to_string()
+    );
+    // The first argument is the self type
+    let trait_args = trait_ref.args[1..]
+        .iter()
+        .map(|arg| ty::print::with_no_trimmed_paths!(arg.to_string()))
+        .collect();
     let generics = extract_generics(tcx, impl_def_id);
     let where_clause = extract_where_clause(tcx, impl_def_id);
     let span = extract_span_info(tcx, impl_def_id);
@@ -1184,13 +1840,15 @@ fn extract_trait_impl_details(tcx: TyCtxt<'_>, impl_def_id: DefId) -> Option<Tra
     let polarity = tcx.impl_polarity(impl_def_id);
     let is_negative = matches!(polarity, ty::ImplPolarity::Negative);
 
-    let methods = extract_impl_methods(tcx, impl_def_id);
+    let methods = extract_impl_methods(tcx, impl_def_id, options);
     let assoc_types = extract_impl_assoc_types(tcx, impl_def_id);
     let assoc_consts = extract_impl_assoc_consts(tcx, impl_def_id);
 
     Some(TraitImplDetails {
         self_ty,
+        self_ty_args: self_ty_args(trait_ref.self_ty()),
         trait_path,
+        trait_args,
         generics,
         where_clause,
         is_negative,
@@ -1200,12 +1858,28 @@ fn extract_trait_impl_details(tcx: TyCtxt<'_>, impl_def_id: DefId) -> Option<Tra
         assoc_consts,
         source,
         span,
+        docs: extract_docs(tcx, impl_def_id),
+        attributes: extract_attributes(tcx, impl_def_id),
     })
 }
 
+/// The generic arguments of an impl's self type, e.g. `["u8"]` for
+/// `Wrapper<u8>`.
+fn self_ty_args(self_ty: ty::Ty<'_>) -> Vec<String> {
+    match self_ty.kind() {
+        // `Display` renders params as `T` rather than the debug `T/#0`
+        ty::Adt(_, args) => args
+            .iter()
+            .map(|arg| ty::print::with_no_trimmed_paths!(arg.to_string()))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
 fn extract_inherent_impl_details(
     tcx: TyCtxt<'_>,
     impl_def_id: DefId,
+    options: &ExtractOptions,
 ) -> Option<InherentImplDetails> {
     let self_ty = tcx.type_of(impl_def_id).skip_binder();
     let generics = extract_generics(tcx, impl_def_id);
@@ -1213,12 +1887,13 @@ fn extract_inherent_impl_details(
     let span = extract_span_info(tcx, impl_def_id);
     let source = get_source_for_def(tcx, impl_def_id);
 
-    let methods = extract_impl_methods(tcx, impl_def_id);
+    let methods = extract_impl_methods(tcx, impl_def_id, options);
     let assoc_types = extract_impl_assoc_types(tcx, impl_def_id);
     let assoc_consts = extract_impl_assoc_consts(tcx, impl_def_id);
 
     Some(InherentImplDetails {
         self_ty: get_type_path_string(tcx, self_ty),
+        self_ty_args: self_ty_args(self_ty),
         generics,
         where_clause,
         is_unsafe: false,
@@ -1227,10 +1902,16 @@ fn extract_inherent_impl_details(
         assoc_types,
         source,
         span,
+        docs: extract_docs(tcx, impl_def_id),
+        attributes: extract_attributes(tcx, impl_def_id),
     })
 }
 
-fn extract_impl_methods(tcx: TyCtxt<'_>, impl_def_id: DefId) -> Vec<MethodDetails> {
+fn extract_impl_methods(
+    tcx: TyCtxt<'_>,
+    impl_def_id: DefId,
+    options: &ExtractOptions,
+) -> Vec<MethodDetails> {
     tcx.associated_item_def_ids(impl_def_id)
         .iter()
         .filter_map(|&item_def_id| {
@@ -1242,14 +1923,29 @@ fn extract_impl_methods(tcx: TyCtxt<'_>, impl_def_id: DefId) -> Vec<MethodDetail
             let sig = tcx.fn_sig(item_def_id).skip_binder();
             let docs = extract_docs(tcx, item_def_id);
             let attributes = extract_attributes(tcx, item_def_id);
-            let body_source = get_source_for_def(tcx, item_def_id);
-            let body_tokens = extract_body_tokens(tcx, item_def_id);
+            // Only trait impl items have a corresponding trait item
+            let overrides_default = item.trait_item_def_id.is_some_and(|trait_item_def_id| {
+                tcx.associated_item(trait_item_def_id)
+                    .defaultness(tcx)
+                    .has_value()
+            });
+            let (body_source, body_tokens) = if options.skip_bodies {
+                (None, None)
+            } else {
+                (
+                    get_source_for_def(tcx, item_def_id),
+                    extract_body_tokens(tcx, item_def_id),
+                )
+            };
 
             Some(MethodDetails {
                 name: item.name().to_string(),
                 path: tcx.def_path_str(item_def_id),
                 signature: format!("{:?}", sig),
                 parsed_signature: parse_fn_signature(tcx, item_def_id),
+                visibility: extract_visibility(tcx, item_def_id),
+                is_method: item.is_method(),
+                overrides_default,
                 has_body: true,
                 body_source,
                 body_tokens,
@@ -1264,12 +1960,140 @@ fn extract_impl_methods(tcx: TyCtxt<'_>, impl_def_id: DefId) -> Vec<MethodDetail
         .collect()
 }
 
+fn extract_function_details(
+    tcx: TyCtxt<'_>,
+    fn_def_id: DefId,
+    options: &ExtractOptions,
+) -> FunctionDetails {
+    let sig = tcx.fn_sig(fn_def_id).skip_binder();
+    let (body_source, body_tokens) = if options.skip_bodies {
+        (None, None)
+    } else {
+        (
+            get_source_for_def(tcx, fn_def_id),
+            extract_body_tokens(tcx, fn_def_id),
+        )
+    };
+
+    FunctionDetails {
+        name: tcx.item_name(fn_def_id).to_string(),
+        path: tcx.def_path_str(fn_def_id),
+        signature: format!("{:?}", sig),
+        parsed_signature: parse_fn_signature(tcx, fn_def_id),
+        visibility: extract_visibility(tcx, fn_def_id),
+        body_source,
+        body_tokens,
+        is_unsafe: sig.safety().is_unsafe(),
+        is_const: tcx.is_const_fn(fn_def_id),
+        is_async: tcx.asyncness(fn_def_id).is_async(),
+        docs: extract_docs(tcx, fn_def_id),
+        attributes: extract_attributes(tcx, fn_def_id),
+        span: extract_span_info(tcx, fn_def_id),
+    }
+}
+
+/// Extract a top-level `const` or `static`. Statics aren't evaluated: a
+/// `static mut` or one with interior mutability needn't keep its initial
+/// value.
+fn extract_const_details(tcx: TyCtxt<'_>, def_id: DefId) -> ConstDetails {
+    let is_static = tcx.is_static(def_id);
+    ConstDetails {
+        name: tcx.item_name(def_id).to_string(),
+        path: tcx.def_path_str(def_id),
+        is_static,
+        ty: format_field_type(tcx, tcx.type_of(def_id).instantiate_identity()),
+        value: if is_static {
+            None
+        } else {
+            eval_const_value(tcx, def_id)
+        },
+        init_source: get_init_source(tcx, def_id),
+        docs: extract_docs(tcx, def_id),
+        visibility: extract_visibility(tcx, def_id),
+        source: get_source_for_def(tcx, def_id),
+        span: extract_span_info(tcx, def_id),
+    }
+}
+
+/// Extract a `macro_rules!` macro, or the procedural macro a function
+/// defines. Returns `None` for functions that aren't proc macro entry points.
+fn extract_macro_details(tcx: TyCtxt<'_>, def_id: DefId) -> Option<MacroDetails> {
+    let item_name = tcx.item_name(def_id).to_string();
+
+    let (name, path, macro_kind, derive_trait, helper_attributes) = match tcx.def_kind(def_id) {
+        DefKind::Macro(kinds) => {
+            // `macro_rules!` can (unstably) define several kinds at once;
+            // report the one it is most commonly invoked as
+            let macro_kind = if kinds.contains(MacroKinds::BANG) {
+                MacroKind::Bang
+            } else if kinds.contains(MacroKinds::ATTR) {
+                MacroKind::Attribute
+            } else {
+                MacroKind::Derive
+            };
+            let path = tcx.def_path_str(def_id);
+            (item_name, path, macro_kind, None, Vec::new())
+        }
+        DefKind::Fn => {
+            let attrs = tcx.hir_attrs(tcx.local_def_id_to_hir_id(def_id.as_local()?));
+            // Proc macros are only usable from the crate root, by macro name
+            if let Some((trait_name, helpers)) = hir::find_attr!(
+                attrs,
+                AttributeKind::ProcMacroDerive { trait_name, helper_attrs, .. } => (trait_name, helper_attrs)
+            ) {
+                let helpers = helpers.iter().map(|h| h.to_string()).collect();
+                let trait_name = trait_name.to_string();
+                (
+                    trait_name.clone(),
+                    trait_name.clone(),
+                    MacroKind::Derive,
+                    Some(trait_name),
+                    helpers,
+                )
+            } else if hir::find_attr!(attrs, AttributeKind::ProcMacro(..)) {
+                (
+                    item_name.clone(),
+                    item_name,
+                    MacroKind::Bang,
+                    None,
+                    Vec::new(),
+                )
+            } else if hir::find_attr!(attrs, AttributeKind::ProcMacroAttribute(..)) {
+                (
+                    item_name.clone(),
+                    item_name,
+                    MacroKind::Attribute,
+                    None,
+                    Vec::new(),
+                )
+            } else {
+                return None;
+            }
+        }
+        _ => return None,
+    };
+
+    Some(MacroDetails {
+        name,
+        path,
+        macro_kind,
+        derive_trait,
+        helper_attributes,
+        visibility: extract_visibility(tcx, def_id),
+        docs: extract_docs(tcx, def_id),
+        source: get_source_for_def(tcx, def_id),
+        span: extract_span_info(tcx, def_id),
+    })
+}
+
 fn extract_impl_assoc_types(tcx: TyCtxt<'_>, impl_def_id: DefId) -> Vec<AssocTypeInfo> {
     tcx.associated_item_def_ids(impl_def_id)
         .iter()
         .filter_map(|&item_def_id| {
             let item = tcx.associated_item(item_def_id);
-            if !matches!(item.kind, ty::AssocKind::Type { .. }) {
+            // Skip the unnamed types synthesized for `async fn` and
+            // return-position `impl Trait` in traits
+            if !matches!(item.kind, ty::AssocKind::Type { .. }) || item.is_impl_trait_in_trait() {
                 return None;
             }
 
@@ -1303,7 +2127,8 @@ fn extract_impl_assoc_consts(tcx: TyCtxt<'_>, impl_def_id: DefId) -> Vec<AssocCo
             Some(AssocConstInfo {
                 name: item.name().to_string(),
                 ty: format!("{:?}", ty),
-                value: None,
+                value: eval_const_value(tcx, item_def_id),
+                init_source: get_init_source(tcx, item_def_id),
                 docs,
                 span: extract_span_info(tcx, item_def_id),
             })
@@ -1311,52 +2136,213 @@ fn extract_impl_assoc_consts(tcx: TyCtxt<'_>, impl_def_id: DefId) -> Vec<AssocCo
         .collect()
 }
 
-fn parse_fn_signature(tcx: TyCtxt<'_>, fn_def_id: DefId) -> FunctionSignature {
-    let sig = tcx.fn_sig(fn_def_id).skip_binder();
-    let inputs = sig.inputs().skip_binder();
-
-    let mut receiver = None;
-    let mut params = Vec::new();
+/// Evaluate a constant item and render its value, e.g. `3` or `"v1"`.
+/// Constants that depend on generic parameters have no single value, and
+/// neither do those that fail to evaluate. Evaluation errors are silenced
+/// in `after_analysis`.
+fn eval_const_value(tcx: TyCtxt<'_>, def_id: DefId) -> Option<String> {
+    if tcx.generics_of(def_id).requires_monomorphization(tcx) {
+        return None;
+    }
+    let value = tcx.const_eval_poly(def_id).ok()?;
+    let ty = tcx.type_of(def_id).instantiate_identity();
+    let mut rendered = ty::print::with_no_trimmed_paths!(mir::Const::Val(value, ty).to_string());
+    if ty
+        .walk()
+        .any(|arg| matches!(arg.as_type().map(|t| t.kind()), Some(ty::Adt(..))))
+    {
+        rendered = unescape_struct_braces(&rendered);
+    }
+    Some(bronzite_types::tidy_const_args(&rendered))
+}
 
-    for (i, ty) in inputs.iter().enumerate() {
-        if i == 0 {
-            let ty_str = format!("{:?}", ty);
-            if ty_str.contains("Self") || ty_str.contains("self") {
-                receiver = Some(ReceiverInfo {
-                    kind: ty_str.clone(),
-                    is_mut: ty_str.contains("mut"),
-                    is_ref: ty_str.contains("&"),
-                    lifetime: None,
-                });
-                continue;
-            }
+/// rustc writes the braces around struct fields pre-escaped, as
+/// `Point {{ x: 1 }}`. Undo that, leaving string and char literals alone:
+/// `"{{"` in a field value is what the string holds.
+fn unescape_struct_braces(rendered: &str) -> String {
+    let mut out = String::with_capacity(rendered.len());
+    let mut chars = rendered.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' | '\'' => {
+                // Copy the literal through its closing quote
+                out.push(c);
+                while let Some(next) = chars.next() {
+                    out.push(next);
+                    if next == '\\' {
+                        out.extend(chars.next());
+                    } else if next == c {
+                        break;
+                    }
+                }
+            }
+            '{' | '}' => {
+                out.push(c);
+                chars.next_if_eq(&c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Classify the type of a method's `self` parameter. `self_ty` is the
+/// implementing type, if the method is in an impl: `self` in an impl for
+/// `Box<Foo>` is an owned receiver, not a boxed one.
+fn receiver_kind<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ty: ty::Ty<'tcx>,
+    self_ty: Option<ty::Ty<'tcx>>,
+) -> ReceiverKind {
+    if self_ty.is_some_and(|self_ty| tcx.erase_regions(self_ty) == tcx.erase_regions(ty)) {
+        return ReceiverKind::Owned;
+    }
+    match ty.kind() {
+        ty::Ref(_, _, ty::Mutability::Not) => ReceiverKind::Ref,
+        ty::Ref(_, _, ty::Mutability::Mut) => ReceiverKind::RefMut,
+        ty::Adt(adt_def, _) if adt_def.is_box() => ReceiverKind::Box,
+        ty::Adt(adt_def, args) => {
+            let did = adt_def.did();
+            if tcx.is_diagnostic_item(sym::Rc, did) {
+                ReceiverKind::Rc
+            } else if tcx.is_diagnostic_item(sym::Arc, did) {
+                ReceiverKind::Arc
+            } else if tcx.is_lang_item(did, LangItem::Pin) {
+                ReceiverKind::Pin(Box::new(receiver_kind(tcx, args.type_at(0), self_ty)))
+            } else {
+                ReceiverKind::Owned
+            }
+        }
+        _ => ReceiverKind::Owned,
+    }
+}
+
+fn parse_fn_signature(tcx: TyCtxt<'_>, fn_def_id: DefId) -> FunctionSignature {
+    let sig = tcx.fn_sig(fn_def_id).skip_binder();
+    let inputs = sig.inputs().skip_binder();
+    // Parameter types are rendered without their late-bound lifetimes,
+    // which would otherwise print as debug output (`&'^1.Named(..) mut T`)
+    let erased_sig = tcx.instantiate_bound_regions_with_erased(sig);
+
+    let mut receiver = None;
+    let mut params = Vec::new();
+
+    let is_method = tcx
+        .opt_associated_item(fn_def_id)
+        .is_some_and(|item| item.is_method());
+    let impl_trait_params = impl_trait_params(tcx, fn_def_id);
+    let parent = tcx.parent(fn_def_id);
+    let impl_self_ty = matches!(tcx.def_kind(parent), DefKind::Impl { .. })
+        .then(|| tcx.type_of(parent).instantiate_identity());
+
+    for (i, ty) in inputs.iter().enumerate() {
+        if i == 0 && is_method {
+            receiver = Some(ReceiverInfo {
+                kind: format!("{:?}", ty),
+                receiver_kind: receiver_kind(tcx, erased_sig.inputs()[0], impl_self_ty),
+                is_mut: ty.ref_mutability().is_some_and(|m| m.is_mut()),
+                is_ref: ty.is_ref(),
+                lifetime: None,
+            });
+            continue;
         }
 
+        let ty = ty::print::with_no_trimmed_paths!(erased_sig.inputs()[i].to_string());
+        let ty = bronzite_types::tidy_const_args(&ty);
         params.push(ParamInfo {
             name: format!("arg{}", i),
-            ty: format!("{:?}", ty),
+            ty: bronzite_types::render_impl_trait_args(&ty, &impl_trait_params),
             is_mut: false,
         });
     }
 
-    let return_ty = {
-        let output = sig.output().skip_binder();
-        if output.is_unit() {
-            None
-        } else {
-            Some(format!("{:?}", output))
-        }
-    };
+    let output = sig.output().skip_binder();
+    let return_ty = extract_return_type(tcx, output, |ty| {
+        bronzite_types::tidy_const_args(&format!("{:?}", ty))
+    });
 
     FunctionSignature {
         receiver,
         params,
-        return_ty,
+        return_ty: Some(return_ty),
         generics: extract_generics(tcx, fn_def_id),
         where_clause: extract_where_clause(tcx, fn_def_id),
+        where_predicates: extract_where_predicates(tcx, fn_def_id),
     }
 }
 
+/// The `impl Trait` parameters of a function, which rustc turns into
+/// synthetic generic parameters named after their source text. Each comes
+/// with the bounds it stands for, with resolved paths, e.g.
+/// `("impl Iterator<Item = u8>", ["std::iter::Iterator<Item = u8>"])`.
+fn impl_trait_params(tcx: TyCtxt<'_>, fn_def_id: DefId) -> Vec<(String, Vec<String>)> {
+    let predicates = tcx.predicates_of(fn_def_id).instantiate_identity(tcx);
+
+    tcx.generics_of(fn_def_id)
+        .own_params
+        .iter()
+        .filter(|param| {
+            matches!(
+                param.kind,
+                ty::GenericParamDefKind::Type {
+                    synthetic: true,
+                    ..
+                }
+            )
+        })
+        .map(|param| {
+            let param_ty = ty::Ty::new_param(tcx, param.index, param.name);
+            let clauses: Vec<ty::Clause<'_>> = predicates
+                .predicates
+                .iter()
+                .copied()
+                .filter(|clause| match clause.kind().skip_binder() {
+                    ty::ClauseKind::Trait(pred) => pred.self_ty() == param_ty,
+                    ty::ClauseKind::Projection(proj) => proj.self_ty() == param_ty,
+                    ty::ClauseKind::TypeOutlives(outlives) => outlives.0 == param_ty,
+                    _ => false,
+                })
+                .collect();
+            (param.name.to_string(), render_bounds(tcx, &clauses))
+        })
+        .collect()
+}
+
+/// Extract the predicates written in the `where` clause of `def_id` itself.
+///
+/// Unlike `predicates_of`, this leaves out bounds declared inline on generic
+/// parameters, implicit `Sized` bounds and anything inherited from a parent
+/// trait or impl, so a trait method reports only e.g. `Self: Sized`.
+fn extract_where_predicates(tcx: TyCtxt<'_>, def_id: DefId) -> Vec<WherePredicate> {
+    let Some(generics) = def_id.as_local().and_then(|id| tcx.hir_get_generics(id)) else {
+        return Vec::new();
+    };
+    let source_map = tcx.sess.source_map();
+    let snippet = |span| source_map.span_to_snippet(span).unwrap_or_default();
+
+    generics
+        .predicates
+        .iter()
+        .filter_map(|pred| match pred.kind {
+            hir::WherePredicateKind::BoundPredicate(bound)
+                if bound.origin == hir::PredicateOrigin::WhereClause =>
+            {
+                Some(WherePredicate {
+                    bounded_ty: snippet(bound.bounded_ty.span),
+                    bounds: bound.bounds.iter().map(|b| snippet(b.span())).collect(),
+                })
+            }
+            hir::WherePredicateKind::RegionPredicate(region) if region.in_where_clause => {
+                Some(WherePredicate {
+                    bounded_ty: snippet(region.lifetime.ident.span),
+                    bounds: region.bounds.iter().map(|b| snippet(b.span())).collect(),
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
 fn extract_type_alias(tcx: TyCtxt<'_>, def_id: DefId) -> Option<TypeAliasInfo> {
     let name = tcx.item_name(def_id).to_string();
     let path = tcx.def_path_str(def_id);
@@ -1368,7 +2354,7 @@ fn extract_type_alias(tcx: TyCtxt<'_>, def_id: DefId) -> Option<TypeAliasInfo> {
     let ty = tcx.type_of(def_id).skip_binder();
     let ty_str = format!("{:?}", ty);
 
-    let (resolved_ty, _chain) = resolve_type_alias_chain(tcx, def_id);
+    let (resolved_ty, chain) = resolve_type_alias_chain(tcx, def_id);
 
     Some(TypeAliasInfo {
         name,
@@ -1376,12 +2362,44 @@ fn extract_type_alias(tcx: TyCtxt<'_>, def_id: DefId) -> Option<TypeAliasInfo> {
         generics,
         ty: ty_str,
         resolved_ty,
+        chain,
         visibility,
         docs,
         span,
     })
 }
 
+fn extract_trait_alias(tcx: TyCtxt<'_>, def_id: DefId) -> TraitAliasInfo {
+    // `trait Shape = Area + Debug;` is lowered to `Self: Area` and
+    // `Self: Debug` predicates on the alias
+    let self_ty = tcx.types.self_param;
+    let bounds = tcx
+        .explicit_implied_predicates_of(def_id)
+        .skip_binder()
+        .iter()
+        .filter_map(|(clause, _)| match clause.kind().skip_binder() {
+            ty::ClauseKind::Trait(pred) if pred.self_ty() == self_ty => {
+                Some(ty::print::with_no_trimmed_paths!(
+                    ty::print::PrintTraitRefExt::print_only_trait_path(pred.trait_ref).to_string()
+                ))
+            }
+            ty::ClauseKind::TypeOutlives(ty::OutlivesPredicate(ty, region)) if ty == self_ty => {
+                Some(region.to_string())
+            }
+            _ => None,
+        })
+        .collect();
+
+    TraitAliasInfo {
+        name: tcx.item_name(def_id).to_string(),
+        path: tcx.def_path_str(def_id),
+        bounds,
+        generics: extract_generics(tcx, def_id),
+        visibility: extract_visibility(tcx, def_id),
+        docs: extract_docs(tcx, def_id),
+    }
+}
+
 fn extract_module_info(tcx: TyCtxt<'_>, local_def_id: LocalDefId) -> Option<ModuleInfo> {
     let def_id = local_def_id.to_def_id();
 
@@ -1399,7 +2417,7 @@ fn extract_module_info(tcx: TyCtxt<'_>, local_def_id: LocalDefId) -> Option<Modu
     let mut items = Vec::new();
     let mut reexports = Vec::new();
 
-    for child in tcx.module_children(def_id) {
+    for child in tcx.module_children_local(local_def_id) {
         let child_name = child.ident.to_string();
 
         if child.reexport_chain.is_empty() {
@@ -1426,6 +2444,673 @@ fn extract_module_info(tcx: TyCtxt<'_>, local_def_id: LocalDefId) -> Option<Modu
     })
 }
 
+// ============================================================================
+// Generic Instantiation
+// ============================================================================
+
+/// Find a local struct, enum or union by exact or `::` suffix path.
+fn find_local_adt(tcx: TyCtxt<'_>, path: &str) -> Option<LocalDefId> {
+    find_local_item(tcx, path, &[DefKind::Struct, DefKind::Enum, DefKind::Union])
+}
+
+/// Find a local item of one of `kinds` by exact or `::` suffix path.
+fn find_local_item(tcx: TyCtxt<'_>, path: &str, kinds: &[DefKind]) -> Option<LocalDefId> {
+    let suffix = format!("::{}", path);
+    let items: Vec<(LocalDefId, String)> = tcx
+        .hir_crate_items(())
+        .free_items()
+        .map(|id| id.owner_id.def_id)
+        .filter(|&def_id| kinds.contains(&tcx.def_kind(def_id)))
+        .map(|def_id| (def_id, tcx.def_path_str(def_id)))
+        .collect();
+
+    items
+        .iter()
+        .find(|(_, item_path)| item_path == path)
+        .or_else(|| {
+            items
+                .iter()
+                .find(|(_, item_path)| item_path.ends_with(&suffix))
+        })
+        .map(|&(def_id, _)| def_id)
+}
+
+/// Split `s` on `sep`, ignoring separators nested inside `<>`, `()` or `[]`.
+fn split_top_level(s: &str, sep: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+
+    for (i, c) in s.char_indices() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            c if c == sep && depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+fn primitive_ty<'tcx>(tcx: TyCtxt<'tcx>, name: &str) -> Option<ty::Ty<'tcx>> {
+    let types = &tcx.types;
+    Some(match name {
+        "bool" => types.bool,
+        "char" => types.char,
+        "str" => types.str_,
+        "!" => types.never,
+        "i8" => types.i8,
+        "i16" => types.i16,
+        "i32" => types.i32,
+        "i64" => types.i64,
+        "i128" => types.i128,
+        "isize" => types.isize,
+        "u8" => types.u8,
+        "u16" => types.u16,
+        "u32" => types.u32,
+        "u64" => types.u64,
+        "u128" => types.u128,
+        "usize" => types.usize,
+        "f32" => types.f32,
+        "f64" => types.f64,
+        _ => return None,
+    })
+}
+
+/// Resolve a type written as source text (e.g. `u32`, `&str`, `Vec<Foo>`,
+/// `[u8; 4]`) to a `Ty`.
+///
+/// Supports primitives, references, tuples, slices, arrays, local ADTs and
+/// std types that have a diagnostic item (`String`, `Vec`, `Option`, ...).
+fn resolve_type_str<'tcx>(tcx: TyCtxt<'tcx>, s: &str) -> Result<ty::Ty<'tcx>, String> {
+    let s = s.trim();
+
+    if let Some(rest) = s.strip_prefix('&') {
+        let rest = rest.trim_start();
+        // Lifetimes are erased, so `&'a T` is just `&T`
+        let rest = match rest.strip_prefix('\'') {
+            Some(lifetime) => lifetime
+                .split_once(char::is_whitespace)
+                .map_or("", |(_, ty)| ty),
+            None => rest,
+        };
+        let (mutbl, inner) = match rest.strip_prefix("mut ") {
+            Some(inner) => (ty::Mutability::Mut, inner),
+            None => (ty::Mutability::Not, rest),
+        };
+        let inner = resolve_type_str(tcx, inner)?;
+        return Ok(ty::Ty::new_ref(tcx, tcx.lifetimes.re_erased, inner, mutbl));
+    }
+
+    if let Some(inner) = s.strip_prefix('(').and_then(|r| r.strip_suffix(')')) {
+        let elems = split_top_level(inner, ',')
+            .into_iter()
+            .filter(|elem| !elem.trim().is_empty())
+            .map(|elem| resolve_type_str(tcx, elem))
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(ty::Ty::new_tup(tcx, &elems));
+    }
+
+    if let Some(inner) = s.strip_prefix('[').and_then(|r| r.strip_suffix(']')) {
+        let parts = split_top_level(inner, ';');
+        let elem = resolve_type_str(tcx, parts[0])?;
+        return match parts.get(1) {
+            Some(len) => {
+                let len = len
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("Invalid array length in `{}`", s))?;
+                Ok(ty::Ty::new_array(tcx, elem, len))
+            }
+            None => Ok(ty::Ty::new_slice(tcx, elem)),
+        };
+    }
+
+    if let Some(prim) = primitive_ty(tcx, s) {
+        return Ok(prim);
+    }
+
+    let (path, args) = match s.find('<') {
+        Some(start) => {
+            let args = s[start + 1..]
+                .strip_suffix('>')
+                .ok_or_else(|| format!("Unbalanced generics in `{}`", s))?;
+            (s[..start].trim(), split_top_level(args, ','))
+        }
+        None => (s, Vec::new()),
+    };
+
+    let def_id = find_local_adt(tcx, path)
+        .map(LocalDefId::to_def_id)
+        .or_else(|| {
+            let name = path.rsplit("::").next()?;
+            // A few core types are lang items rather than diagnostic items
+            match name {
+                "Box" => tcx.lang_items().owned_box(),
+                "String" => tcx.lang_items().string(),
+                _ => tcx.get_diagnostic_item(rustc_span::Symbol::intern(name)),
+            }
+        })
+        .filter(|&def_id| {
+            matches!(
+                tcx.def_kind(def_id),
+                DefKind::Struct | DefKind::Enum | DefKind::Union
+            )
+        })
+        .ok_or_else(|| format!("Cannot resolve type `{}`", s))?;
+
+    let generic_args = build_generic_args(tcx, def_id, &args)?;
+    Ok(ty::Ty::new_adt(tcx, tcx.adt_def(def_id), generic_args))
+}
+
+/// Build the generic arguments of an ADT from type and const argument strings.
+///
+/// Lifetimes are erased and trailing type parameters fall back to their
+/// defaults, so `Vec<u8>` works without naming the allocator.
+fn build_generic_args<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+    args: &[&str],
+) -> Result<ty::GenericArgsRef<'tcx>, String> {
+    let generics = tcx.generics_of(def_id);
+    let mut provided = args.iter().copied().filter(|arg| !arg.trim().is_empty());
+    let mut resolved: Vec<ty::GenericArg<'tcx>> = Vec::new();
+
+    for param in &generics.own_params {
+        let arg = match param.kind {
+            ty::GenericParamDefKind::Lifetime => tcx.lifetimes.re_erased.into(),
+            ty::GenericParamDefKind::Type { has_default, .. } => match provided.next() {
+                Some(arg) => resolve_type_str(tcx, arg)?.into(),
+                None if has_default => tcx
+                    .type_of(param.def_id)
+                    .instantiate(tcx, resolved.as_slice())
+                    .into(),
+                None => return Err(format!("Missing type argument for `{}`", param.name)),
+            },
+            ty::GenericParamDefKind::Const { .. } => {
+                let arg = provided
+                    .next()
+                    .ok_or_else(|| format!("Missing const argument for `{}`", param.name))?;
+                let value: u128 = arg
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("Invalid const argument `{}`", arg))?;
+                let ty = tcx.type_of(param.def_id).instantiate_identity();
+                ty::Const::from_bits(tcx, value, TypingEnv::fully_monomorphized(), ty).into()
+            }
+        };
+        resolved.push(arg);
+    }
+
+    if provided.next().is_some() {
+        return Err(format!(
+            "Too many generic arguments for `{}`",
+            tcx.def_path_str(def_id)
+        ));
+    }
+
+    Ok(tcx.mk_args(&resolved))
+}
+
+/// Get the details of a local ADT instantiated with concrete arguments.
+///
+/// Field types are substituted and the layout is computed for the
+/// instantiation, which the generic definition alone cannot provide.
+/// A local type alias is accepted too: the arguments are substituted into
+/// the alias and the ADT it expands to is instantiated.
+fn extract_instantiated_type(
+    tcx: TyCtxt<'_>,
+    type_path: &str,
+    args: &[String],
+) -> Result<TypeDetails, String> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    if let Some(local_def_id) = find_local_adt(tcx, type_path) {
+        let generic_args = build_generic_args(tcx, local_def_id.to_def_id(), &args)?;
+        return instantiated_adt_details(tcx, local_def_id, generic_args);
+    }
+
+    let alias = find_local_item(tcx, type_path, &[DefKind::TyAlias])
+        .ok_or_else(|| format!("Type not found: {}", type_path))?;
+    let generic_args = build_generic_args(tcx, alias.to_def_id(), &args)?;
+    let ty = tcx.type_of(alias).instantiate(tcx, generic_args);
+    let ty = tcx
+        .try_normalize_erasing_regions(TypingEnv::fully_monomorphized(), ty)
+        .unwrap_or(ty);
+
+    match ty.kind() {
+        ty::Adt(adt_def, adt_args) if adt_def.did().is_local() => {
+            instantiated_adt_details(tcx, adt_def.did().expect_local(), adt_args)
+        }
+        _ => Err(format!(
+            "Type alias '{}' resolves to {:?}, which is not a type in this crate",
+            type_path, ty
+        )),
+    }
+}
+
+fn instantiated_adt_details<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    local_def_id: LocalDefId,
+    generic_args: ty::GenericArgsRef<'tcx>,
+) -> Result<TypeDetails, String> {
+    let mut details = extract_type_details(tcx, local_def_id)
+        .ok_or_else(|| format!("Type not found: {}", tcx.def_path_str(local_def_id)))?;
+    let adt_def = tcx.adt_def(local_def_id);
+
+    let instantiate_fields = |fields: &mut [FieldInfo], variant: &ty::VariantDef| {
+        for (info, field) in fields.iter_mut().zip(&variant.fields) {
+            let ty = tcx.type_of(field.did).instantiate(tcx, generic_args);
+            info.ty = format_field_type(tcx, ty);
+            info.resolved_ty = Some(get_resolved_type(tcx, ty));
+        }
+    };
+
+    if let Some(fields) = details.fields.as_mut() {
+        instantiate_fields(fields, adt_def.non_enum_variant());
+    }
+    if let Some(variants) = details.variants.as_mut() {
+        for (info, variant) in variants.iter_mut().zip(adt_def.variants()) {
+            instantiate_fields(&mut info.fields, variant);
+        }
+    }
+
+    details.layout = extract_ty_layout(tcx, ty::Ty::new_adt(tcx, adt_def, generic_args));
+
+    Ok(details)
+}
+
+/// Resolve the signature of `method_name` as the local impl of `trait_path`
+/// for `type_path` provides it, with `Self` replaced by the implementing type
+/// and associated type projections normalized.
+fn impl_method_signature(
+    tcx: TyCtxt<'_>,
+    type_path: &str,
+    trait_path: &str,
+    method_name: &str,
+) -> Result<FunctionSignature, String> {
+    let impl_def_id = tcx
+        .hir_crate_items(())
+        .free_items()
+        .map(|id| id.owner_id.to_def_id())
+        .find(|&def_id| {
+            matches!(tcx.def_kind(def_id), DefKind::Impl { of_trait: true })
+                && tcx.impl_trait_ref(def_id).is_some_and(|trait_ref| {
+                    let trait_ref = trait_ref.skip_binder();
+                    let impl_trait_path = ty::print::with_no_trimmed_paths!(
+                        ty::print::PrintTraitRefExt::print_only_trait_path(trait_ref).to_string()
+                    );
+                    bronzite_types::trait_path_matches(&impl_trait_path, trait_path)
+                        && bronzite_types::type_path_matches(
+                            &get_type_path_string(tcx, trait_ref.self_ty()),
+                            type_path,
+                        )
+                })
+        })
+        .ok_or_else(|| format!("No impl of `{}` for `{}` found", trait_path, type_path))?;
+    let trait_ref = tcx
+        .impl_trait_ref(impl_def_id)
+        .expect("trait impl")
+        .instantiate_identity();
+
+    let trait_method = tcx
+        .associated_items(trait_ref.def_id)
+        .filter_by_name_unhygienic(rustc_span::Symbol::intern(method_name))
+        .find(|item| item.is_fn())
+        .ok_or_else(|| format!("Trait `{}` has no method `{}`", trait_path, method_name))?;
+
+    // Use the impl's own definition if it has one; a default method is the
+    // trait's signature instantiated with the impl's trait arguments
+    let (fn_def_id, sig) =
+        match tcx
+            .impl_item_implementor_ids(impl_def_id)
+            .get(&trait_method.def_id)
+        {
+            Some(&impl_method) => (impl_method, tcx.fn_sig(impl_method).instantiate_identity()),
+            None => {
+                let args = ty::GenericArgs::identity_for_item(tcx, trait_method.def_id)
+                    .rebase_onto(tcx, trait_ref.def_id, trait_ref.args);
+                (
+                    trait_method.def_id,
+                    tcx.fn_sig(trait_method.def_id).instantiate(tcx, args),
+                )
+            }
+        };
+    let sig = tcx.instantiate_bound_regions_with_erased(sig);
+    let sig = tcx
+        .try_normalize_erasing_regions(TypingEnv::non_body_analysis(tcx, impl_def_id), sig)
+        .unwrap_or(sig);
+
+    // Printed rather than debug-formatted, which would show the erased
+    // lifetimes and default type arguments
+    let print = |ty: ty::Ty<'_>| ty::print::with_no_trimmed_paths!(ty.to_string());
+
+    let mut receiver = None;
+    let mut params = Vec::new();
+    for (i, ty) in sig.inputs().iter().enumerate() {
+        if i == 0 && trait_method.is_method() {
+            receiver = Some(ReceiverInfo {
+                kind: print(*ty),
+                receiver_kind: receiver_kind(tcx, *ty, Some(trait_ref.self_ty())),
+                is_mut: ty.ref_mutability().is_some_and(|m| m.is_mut()),
+                is_ref: ty.is_ref(),
+                lifetime: None,
+            });
+            continue;
+        }
+        params.push(ParamInfo {
+            name: format!("arg{}", i),
+            ty: print(*ty),
+            is_mut: false,
+        });
+    }
+    let output = sig.output();
+
+    Ok(FunctionSignature {
+        receiver,
+        params,
+        return_ty: Some(extract_return_type(tcx, output, print)),
+        generics: extract_generics(tcx, fn_def_id),
+        where_clause: extract_where_clause(tcx, fn_def_id),
+        where_predicates: extract_where_predicates(tcx, fn_def_id),
+    })
+}
+
+/// Whether a type of this crate is `Sized` with `args` for its generic
+/// parameters.
+fn check_sized(tcx: TyCtxt<'_>, type_path: &str, args: &[String]) -> Result<bool, String> {
+    let adt = find_local_adt(tcx, type_path)
+        .ok_or_else(|| format!("Type not found: {}", type_path))?
+        .to_def_id();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let ty = ty::Ty::new_adt(tcx, tcx.adt_def(adt), build_generic_args(tcx, adt, &args)?);
+    Ok(ty.is_sized(tcx, TypingEnv::fully_monomorphized()))
+}
+
+/// Classify a function's return type, printing plain types with `print`.
+fn extract_return_type<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    output: ty::Ty<'tcx>,
+    print: impl Fn(ty::Ty<'tcx>) -> String,
+) -> ReturnType {
+    match output.kind() {
+        _ if output.is_unit() => ReturnType::Unit,
+        ty::Never => ReturnType::Never,
+        ty::Alias(ty::Opaque, alias) => {
+            ReturnType::ImplTrait(opaque_bounds(tcx, alias.def_id, alias.args))
+        }
+        _ => ReturnType::Type(print(output)),
+    }
+}
+
+/// The bounds of an `impl Trait` type as written, e.g.
+/// `["std::iter::Iterator<Item = u8>", "'a"]`. The implicit `Sized` bound
+/// is left out, and associated type bindings are folded back into their
+/// trait.
+fn opaque_bounds<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+    args: ty::GenericArgsRef<'tcx>,
+) -> Vec<String> {
+    let clauses: Vec<ty::Clause<'tcx>> = tcx
+        .explicit_item_bounds(def_id)
+        .iter_instantiated_copied(tcx, args)
+        .map(|(clause, _)| clause)
+        .collect();
+    render_bounds(tcx, &clauses)
+}
+
+/// Render the bounds `clauses` put on a single type, dropping `Sized` and
+/// folding associated type bindings into their trait. A binding of a
+/// supertrait's associated type goes on the listed trait that implies it,
+/// and `Fn`, `FnMut` and `FnOnce` bounds use the `Fn(u8) -> u16` sugar.
+fn render_bounds<'tcx>(tcx: TyCtxt<'tcx>, clauses: &[ty::Clause<'tcx>]) -> Vec<String> {
+    let sized = tcx.lang_items().sized_trait();
+
+    // (trait, trait path, associated type bindings, `Fn` sugar arguments)
+    let mut traits: Vec<(DefId, String, Vec<String>, Option<Vec<String>>)> = Vec::new();
+    let mut lifetimes = Vec::new();
+    for clause in clauses {
+        match clause.kind().skip_binder() {
+            ty::ClauseKind::Trait(pred) if Some(pred.def_id()) != sized => {
+                let fn_inputs =
+                    tcx.fn_trait_kind_from_def_id(pred.def_id()).and_then(|_| {
+                        match pred.trait_ref.args.type_at(1).kind() {
+                            ty::Tuple(inputs) => Some(
+                                inputs
+                                    .iter()
+                                    .map(|input| {
+                                        ty::print::with_no_trimmed_paths!(input.to_string())
+                                    })
+                                    .collect(),
+                            ),
+                            _ => None,
+                        }
+                    });
+                let path = ty::print::with_no_trimmed_paths!(if fn_inputs.is_some() {
+                    tcx.def_path_str(pred.def_id())
+                } else {
+                    pred.trait_ref.print_only_trait_path().to_string()
+                });
+                traits.push((pred.def_id(), path, Vec::new(), fn_inputs));
+            }
+            ty::ClauseKind::TypeOutlives(outlives) => lifetimes.push(outlives.1.to_string()),
+            _ => {}
+        }
+    }
+    for clause in clauses {
+        if let ty::ClauseKind::Projection(proj) = clause.kind().skip_binder() {
+            let trait_def_id = proj.projection_term.trait_def_id(tcx);
+            let binding = ty::print::with_no_trimmed_paths!(format!(
+                "{} = {}",
+                tcx.item_name(proj.def_id()),
+                proj.term
+            ));
+            // `impl Fn(u8) -> u16` binds `FnOnce::Output`
+            let bound = traits
+                .iter_mut()
+                .find(|t| t.0 == trait_def_id)
+                .map(|t| &mut t.2);
+            let bound = match bound {
+                Some(bindings) => Some(bindings),
+                None => traits
+                    .iter_mut()
+                    .find(|t| elaborate::supertrait_def_ids(tcx, t.0).any(|id| id == trait_def_id))
+                    .map(|t| &mut t.2),
+            };
+            if let Some(bindings) = bound {
+                bindings.push(binding);
+            }
+        }
+    }
+
+    traits
+        .into_iter()
+        .map(|(_, path, bindings, fn_inputs)| match fn_inputs {
+            Some(inputs) => {
+                let output = bindings
+                    .iter()
+                    .find_map(|b| b.strip_prefix("Output = "))
+                    .filter(|output| *output != "()");
+                let mut bound = format!("{}({})", path, inputs.join(", "));
+                if let Some(output) = output {
+                    bound = format!("{} -> {}", bound, output);
+                }
+                bronzite_types::tidy_const_args(&bound)
+            }
+            None => bronzite_types::render_trait_bound(&path, &bindings),
+        })
+        .chain(lifetimes)
+        .collect()
+}
+
+/// Resolve a span reported by an earlier query back to the source it
+/// covers. Columns count characters, as in [`extract_span_info`].
+fn span_text(tcx: TyCtxt<'_>, span: &SpanInfo) -> Result<String, String> {
+    let source_map = tcx.sess.source_map();
+    let file = source_map
+        .files()
+        .iter()
+        .find(|file| file.name.prefer_remapped_unconditionally().to_string() == span.file)
+        .cloned()
+        .ok_or_else(|| format!("Source file not found: {}", span.file))?;
+
+    let pos = |line: u32, col: u32| -> Result<BytePos, String> {
+        let index = (line as usize)
+            .checked_sub(1)
+            .filter(|&index| index < file.count_lines())
+            .ok_or_else(|| format!("Line {} is out of range in {}", line, span.file))?;
+        let text = file
+            .get_line(index)
+            .ok_or_else(|| format!("Source of {} is not available", span.file))?;
+        let offset = text
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .chain(std::iter::once(text.len()))
+            .nth(col as usize)
+            .ok_or_else(|| format!("Column {} is out of range on line {}", col, line))?;
+        Ok(file.line_bounds(index).start + BytePos(offset as u32))
+    };
+
+    let lo = pos(span.start_line, span.start_col)?;
+    let hi = pos(span.end_line, span.end_col)?;
+    if hi < lo {
+        return Err("Span ends before it starts".to_string());
+    }
+    source_map
+        .span_to_snippet(Span::with_root_ctxt(lo, hi))
+        .map_err(|e| format!("Could not read span: {:?}", e))
+}
+
+/// Check whether `type_path` instantiated with `type_args` implements
+/// `trait_path`, letting the trait solver decide the where clauses of
+/// conditional impls. Also returns the local impl providing it, if any.
+fn check_impl_for(
+    tcx: TyCtxt<'_>,
+    type_path: &str,
+    type_args: &[String],
+    trait_path: &str,
+) -> Result<(bool, Option<TraitImplDetails>), String> {
+    let adt = find_local_adt(tcx, type_path)
+        .ok_or_else(|| format!("Type not found: {}", type_path))?
+        .to_def_id();
+    let args: Vec<&str> = type_args.iter().map(String::as_str).collect();
+    let self_ty = ty::Ty::new_adt(tcx, tcx.adt_def(adt), build_generic_args(tcx, adt, &args)?);
+
+    // `From<u32>` names the trait and its arguments after `Self`
+    let (trait_name, trait_args) = match trait_path.find('<') {
+        Some(start) => {
+            let args = trait_path[start + 1..]
+                .strip_suffix('>')
+                .ok_or_else(|| format!("Unbalanced generics in `{}`", trait_path))?;
+            (trait_path[..start].trim(), split_top_level(args, ','))
+        }
+        None => (trait_path, Vec::new()),
+    };
+    let trait_def_id = find_local_item(tcx, trait_name, &[DefKind::Trait])
+        .map(LocalDefId::to_def_id)
+        .or_else(|| {
+            let name = trait_name.rsplit("::").next()?;
+            tcx.get_diagnostic_item(rustc_span::Symbol::intern(name))
+        })
+        .filter(|&def_id| tcx.def_kind(def_id) == DefKind::Trait)
+        .ok_or_else(|| format!("Cannot resolve trait `{}`", trait_path))?;
+
+    // Trait parameters left out fall back to their defaults, which may
+    // mention `Self` (`PartialEq<Rhs = Self>`)
+    let mut provided = trait_args.into_iter().filter(|arg| !arg.trim().is_empty());
+    let mut params: Vec<ty::GenericArg<'_>> = vec![self_ty.into()];
+    for param in tcx.generics_of(trait_def_id).own_params.iter().skip(1) {
+        let arg = match param.kind {
+            ty::GenericParamDefKind::Type { has_default, .. } => match provided.next() {
+                Some(arg) => resolve_type_str(tcx, arg)?.into(),
+                None if has_default => tcx
+                    .type_of(param.def_id)
+                    .instantiate(tcx, params.as_slice())
+                    .into(),
+                None => return Err(format!("Missing type argument for `{}`", param.name)),
+            },
+            _ => {
+                return Err(format!(
+                    "Unsupported parameter `{}` of `{}`",
+                    param.name, trait_path
+                ));
+            }
+        };
+        params.push(arg);
+    }
+    if provided.next().is_some() {
+        return Err(format!("Too many generic arguments for `{}`", trait_path));
+    }
+
+    let infcx = tcx.infer_ctxt().build(ty::TypingMode::non_body_analysis());
+    let implements = infcx
+        .type_implements_trait(trait_def_id, params, ty::ParamEnv::empty())
+        .must_apply_modulo_regions();
+
+    let impl_info = implements
+        .then(|| {
+            let impls = tcx.all_local_trait_impls(()).get(&trait_def_id)?;
+            impls.iter().find_map(|&impl_def_id| {
+                let trait_ref = tcx.impl_trait_ref(impl_def_id)?.instantiate_identity();
+                let same_type = trait_ref.self_ty().ty_adt_def()?.did() == adt;
+                let impl_trait_path = ty::print::with_no_trimmed_paths!(
+                    ty::print::PrintTraitRefExt::print_only_trait_path(trait_ref).to_string()
+                );
+                (same_type && bronzite_types::trait_path_matches(&impl_trait_path, trait_path))
+                    .then(|| {
+                        extract_trait_impl_details(
+                            tcx,
+                            impl_def_id.to_def_id(),
+                            &ExtractOptions::default(),
+                        )
+                    })?
+            })
+        })
+        .flatten();
+
+    Ok((implements, impl_info))
+}
+
+/// Check whether `from` converts into `into` through `Into`, returning the
+/// local `From` impl that provides the conversion when there is one.
+fn check_convertible(
+    tcx: TyCtxt<'_>,
+    from: &str,
+    into: &str,
+) -> Result<(bool, Option<TraitImplDetails>), String> {
+    let from_ty = resolve_type_str(tcx, from)?;
+    let into_ty = resolve_type_str(tcx, into)?;
+
+    let into_trait = tcx
+        .get_diagnostic_item(sym::Into)
+        .ok_or("`Into` trait not found")?;
+    let infcx = tcx.infer_ctxt().build(ty::TypingMode::non_body_analysis());
+    let convertible = infcx
+        .type_implements_trait(into_trait, [from_ty, into_ty], ty::ParamEnv::empty())
+        .must_apply_modulo_regions();
+
+    // Only concrete impls are matched; the blanket `impl<T> From<T> for T`
+    // and generic local impls have no single `From` impl to report
+    let impl_info = tcx.get_diagnostic_item(sym::From).and_then(|from_trait| {
+        let impls = tcx.all_local_trait_impls(()).get(&from_trait)?;
+        impls.iter().find_map(|&impl_def_id| {
+            let trait_ref = tcx.impl_trait_ref(impl_def_id)?.instantiate_identity();
+            let matches = tcx.erase_regions(trait_ref.self_ty()) == into_ty
+                && tcx.erase_regions(trait_ref.args.type_at(1)) == from_ty;
+            matches.then(|| {
+                extract_trait_impl_details(tcx, impl_def_id.to_def_id(), &ExtractOptions::default())
+            })?
+        })
+    });
+
+    Ok((convertible, impl_info))
+}
+
 // ============================================================================
 // Output Functions
 // ============================================================================
@@ -1453,13 +3138,35 @@ fn output_query_result(result: &QueryResult) {
 // ============================================================================
 
 fn parse_query(query_str: &str) -> Query {
+    // The daemon sends queries as JSON, which also covers queries whose
+    // arguments don't fit the `name:arg` form
+    if query_str.trim_start().starts_with('{') {
+        match serde_json::from_str(query_str) {
+            Ok(query) => return query,
+            Err(e) => {
+                eprintln!("Invalid JSON query: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
     let parts: Vec<&str> = query_str.split(':').collect();
 
     match parts[0] {
         "list_items" => Query::ListItems,
+        "get_crate_info" => Query::GetCrateInfo,
+        "get_crate_meta" => Query::GetCrateMeta,
+        "version" => Query::Version,
+        "list_crates" => Query::ListCachedCrates,
         "get_type" if parts.len() >= 2 => Query::GetType {
             path: parts[1].to_string(),
         },
+        "get_type_full" if parts.len() >= 2 => Query::GetTypeFull {
+            path: parts[1].to_string(),
+        },
+        "get_type_by_id" if parts.len() >= 2 => Query::GetTypeById {
+            id: parts[1].to_string(),
+        },
         "get_trait_impls" if parts.len() >= 2 => Query::GetTraitImpls {
             type_path: parts[1].to_string(),
         },
@@ -1476,8 +3183,49 @@ fn parse_query(query_str: &str) -> Query {
         "get_trait" if parts.len() >= 2 => Query::GetTrait {
             path: parts[1].to_string(),
         },
+        "get_trait_alias" if parts.len() >= 2 => Query::GetTraitAlias {
+            path: parts[1].to_string(),
+        },
+        "get_module_tree" => Query::GetModuleTree,
+        "get_function" if parts.len() >= 2 => Query::GetFunction {
+            path: parts[1].to_string(),
+        },
+        "get_macro" if parts.len() >= 2 => Query::GetMacro {
+            path: parts[1].to_string(),
+        },
+        "get_const" if parts.len() >= 2 => Query::GetConst {
+            path: parts[1].to_string(),
+        },
+        "get_impl_method_signature" if parts.len() >= 4 => Query::GetImplMethodSignature {
+            type_path: parts[1].to_string(),
+            trait_path: parts[2].to_string(),
+            method_name: parts[3].to_string(),
+        },
+        "find_functions" if parts.len() >= 2 => Query::FindFunctions {
+            pattern: parts[1].to_string(),
+            options: Default::default(),
+        },
+        "get_type_aliases" if parts.len() >= 2 => Query::GetTypeAliases {
+            pattern: parts[1].to_string(),
+        },
+        "find_methods" if parts.len() >= 2 => Query::FindMethods {
+            pattern: parts[1].to_string(),
+            include_trait_impls: false,
+        },
+        "find_traits_by_supertrait" if parts.len() >= 2 => Query::FindTraitsBySupertrait {
+            supertrait: parts[1].to_string(),
+        },
+        "find_types_using_type" if parts.len() >= 2 => Query::FindTypesUsingType {
+            type_path: parts[1].to_string(),
+        },
         "find_types" if parts.len() >= 2 => Query::FindTypes {
             pattern: parts[1].to_string(),
+            options: Default::default(),
+        },
+        "get_variant_field" if parts.len() >= 4 => Query::GetVariantField {
+            enum_path: parts[1].to_string(),
+            variant: parts[2].to_string(),
+            field: parts[3].to_string(),
         },
         "resolve_alias" if parts.len() >= 2 => Query::ResolveAlias {
             path: parts[1].to_string(),
@@ -1489,34 +3237,145 @@ fn parse_query(query_str: &str) -> Query {
         "get_implementors" if parts.len() >= 2 => Query::GetImplementors {
             trait_path: parts[1].to_string(),
         },
+        "get_all_impls_of_trait" if parts.len() >= 2 => Query::GetAllImplsOfTrait {
+            trait_path: parts[1].to_string(),
+        },
+        "resolve_method" if parts.len() >= 3 => Query::ResolveMethod {
+            type_path: parts[1].to_string(),
+            method_name: parts[2].to_string(),
+        },
+        // Type strings contain `::`, so take everything after the command
+        "describe_type" if parts.len() >= 2 => Query::DescribeType {
+            type_path: parts[1..].join(":"),
+        },
         _ => {
             eprintln!("Unknown query: {}", query_str);
             eprintln!("Available queries:");
             eprintln!("  list_items");
+            eprintln!("  version");
             eprintln!("  get_type:<path>");
             eprintln!("  get_trait_impls:<type_path>");
             eprintln!("  get_inherent_impls:<type_path>");
             eprintln!("  get_fields:<type_path>");
+            eprintln!("  get_variant_field:<enum_path>:<variant>:<field>");
             eprintln!("  get_layout:<type_path>");
             eprintln!("  get_traits");
             eprintln!("  get_trait:<path>");
+            eprintln!("  get_trait_alias:<path>");
+            eprintln!("  find_traits_by_supertrait:<supertrait>");
             eprintln!("  find_types:<pattern>");
+            eprintln!("  find_types_using_type:<type_path>");
+            eprintln!("  get_module_tree");
+            eprintln!("  get_function:<path>");
+            eprintln!("  find_functions:<pattern>");
+            eprintln!("  find_methods:<pattern>");
+            eprintln!("  get_macro:<path>");
+            eprintln!("  get_const:<path>");
             eprintln!("  resolve_alias:<path>");
             eprintln!("  check_impl:<type_path>:<trait_path>");
             eprintln!("  get_implementors:<trait_path>");
+            eprintln!("  get_all_impls_of_trait:<trait_path>");
+            eprintln!("  resolve_method:<type_path>:<method_name>");
+            eprintln!("  get_impl_method_signature:<type_path>:<trait_path>:<method_name>");
+            eprintln!("  describe_type:<type>");
+            eprintln!("  <JSON-encoded query>");
             std::process::exit(1);
         }
     }
 }
 
 fn execute_query(tcx: TyCtxt<'_>, query: &Query) -> QueryResult {
-    let info = extract_crate_info(tcx);
+    // These need the live type context rather than the extraction
+    if let Query::GetTypeInstantiated { type_path, args } = query {
+        return match extract_instantiated_type(tcx, type_path, args) {
+            Ok(details) => QueryResult::Success {
+                data: QueryData::TypeInfo(details),
+            },
+            Err(message) => QueryResult::Error { message },
+        };
+    }
+    if let Query::CheckConvertible { from, into } = query {
+        return match check_convertible(tcx, from, into) {
+            Ok((convertible, impl_info)) => QueryResult::Success {
+                data: QueryData::ConvertibleCheck {
+                    convertible,
+                    impl_info,
+                },
+            },
+            Err(message) => QueryResult::Error { message },
+        };
+    }
+    if let Query::CheckImplFor {
+        type_path,
+        type_args,
+        trait_path,
+    } = query
+    {
+        return match check_impl_for(tcx, type_path, type_args, trait_path) {
+            Ok((implements, impl_info)) => QueryResult::Success {
+                data: QueryData::ImplCheck {
+                    implements,
+                    impl_info,
+                },
+            },
+            Err(message) => QueryResult::Error { message },
+        };
+    }
+    if let Query::CheckSized { type_path, args } = query {
+        return match check_sized(tcx, type_path, args) {
+            Ok(is_sized) => QueryResult::Success {
+                data: QueryData::SizedCheck { is_sized },
+            },
+            Err(message) => QueryResult::Error { message },
+        };
+    }
+    if let Query::GetSpanText { span } = query {
+        return match span_text(tcx, span) {
+            Ok(text) => QueryResult::Success {
+                data: QueryData::SpanText { text },
+            },
+            Err(message) => QueryResult::Error { message },
+        };
+    }
+    if let Query::GetImplMethodSignature {
+        type_path,
+        trait_path,
+        method_name,
+    } = query
+    {
+        return match impl_method_signature(tcx, type_path, trait_path, method_name) {
+            Ok(signature) => QueryResult::Success {
+                data: QueryData::MethodSignature(signature),
+            },
+            Err(message) => QueryResult::Error { message },
+        };
+    }
+
+    let info = extract_crate_info(tcx, &ExtractOptions::default());
 
     match query {
-        Query::ListItems => QueryResult::Success {
-            data: QueryData::Items { items: info.items },
+        Query::GetCrateInfo => QueryResult::Success {
+            data: QueryData::CrateInfo(Box::new(info)),
+        },
+
+        Query::GetCrateMeta => QueryResult::Success {
+            data: QueryData::CrateMeta {
+                edition: info.edition,
+                rustc_version: info.rustc_version,
+            },
         },
 
+        Query::ListItems => {
+            let mut items = info.items;
+            bronzite_types::sort_by_path(&mut items, |item| &item.path);
+            QueryResult::Success {
+                data: QueryData::Items {
+                    items,
+                    warnings: info.warnings,
+                },
+            }
+        }
+
         Query::GetType { path } => match info.types.get(path) {
             Some(type_details) => QueryResult::Success {
                 data: QueryData::TypeInfo(type_details.clone()),
@@ -1526,6 +3385,46 @@ fn execute_query(tcx: TyCtxt<'_>, query: &Query) -> QueryResult {
             },
         },
 
+        Query::GetTypeFull { path } => match info.types.get(path) {
+            Some(type_details) => {
+                let is_self =
+                    |key: &String| key == path || key.split('<').next() == Some(path.as_str());
+                let trait_impls = info
+                    .trait_impls
+                    .iter()
+                    .filter(|(key, _)| is_self(key))
+                    .flat_map(|(_, impls)| impls.iter().cloned())
+                    .collect();
+                let inherent_impls = info
+                    .inherent_impls
+                    .iter()
+                    .filter(|(key, _)| is_self(key))
+                    .flat_map(|(_, impls)| impls.iter().cloned())
+                    .collect();
+                QueryResult::Success {
+                    data: QueryData::TypeFull {
+                        details: type_details.clone(),
+                        fields: type_details.fields.clone().unwrap_or_default(),
+                        trait_impls,
+                        inherent_impls,
+                        layout: info.layouts.get(path).cloned(),
+                    },
+                }
+            }
+            None => QueryResult::Error {
+                message: format!("Type not found: {}", path),
+            },
+        },
+
+        Query::GetTypeById { id } => match info.types.values().find(|t| t.stable_id == *id) {
+            Some(type_details) => QueryResult::Success {
+                data: QueryData::TypeInfo(type_details.clone()),
+            },
+            None => QueryResult::Error {
+                message: format!("No type with id: {}", id),
+            },
+        },
+
         Query::GetTraitImpls { type_path } => {
             let impls = info.trait_impls.get(type_path).cloned().unwrap_or_default();
             QueryResult::Success {
@@ -1555,9 +3454,20 @@ fn execute_query(tcx: TyCtxt<'_>, query: &Query) -> QueryResult {
             }
         }
 
-        Query::GetLayout { type_path } => match info.layouts.get(type_path) {
+        Query::GetVariantField {
+            enum_path,
+            variant,
+            field,
+        } => match info.variant_field(enum_path, variant, field) {
+            Ok(field) => QueryResult::Success {
+                data: QueryData::Field(field.clone()),
+            },
+            Err(message) => QueryResult::Error { message },
+        },
+
+        Query::GetLayout { type_path } => match info.type_layout(type_path) {
             Some(layout) => QueryResult::Success {
-                data: QueryData::Layout(layout.clone()),
+                data: QueryData::Layout(layout),
             },
             None => QueryResult::Error {
                 message: format!("Layout not available for: {}", type_path),
@@ -1565,23 +3475,24 @@ fn execute_query(tcx: TyCtxt<'_>, query: &Query) -> QueryResult {
         },
 
         Query::GetTraits => {
-            let traits: Vec<TraitInfo> = info
-                .traits
-                .values()
-                .map(|t| TraitInfo {
-                    name: t.name.clone(),
-                    path: t.path.clone(),
-                    generics: t.generics.clone(),
-                    required_methods: t.methods.iter().filter(|m| !m.has_default).count(),
-                    provided_methods: t.methods.iter().filter(|m| m.has_default).count(),
-                    supertraits: t.supertraits.clone(),
-                })
-                .collect();
+            let traits: Vec<TraitInfo> = info.traits.values().map(trait_info).collect();
             QueryResult::Success {
-                data: QueryData::Traits { traits },
+                data: QueryData::Traits {
+                    traits,
+                    warnings: info.warnings.clone(),
+                },
             }
         }
 
+        Query::GetTraitAlias { path } => match info.trait_aliases.get(path) {
+            Some(alias) => QueryResult::Success {
+                data: QueryData::TraitAlias(alias.clone()),
+            },
+            None => QueryResult::Error {
+                message: format!("Trait alias not found: {}", path),
+            },
+        },
+
         Query::GetTrait { path } => match info.traits.get(path) {
             Some(trait_details) => QueryResult::Success {
                 data: QueryData::TraitDetails(trait_details.clone()),
@@ -1591,29 +3502,123 @@ fn execute_query(tcx: TyCtxt<'_>, query: &Query) -> QueryResult {
             },
         },
 
-        Query::FindTypes { pattern } => {
-            let types: Vec<TypeSummary> = info
+        Query::FindTypesUsingType { type_path } => QueryResult::Success {
+            data: QueryData::TypeUsages {
+                usages: info.types_using_type(type_path),
+                warnings: info.warnings.clone(),
+            },
+        },
+
+        Query::FindTraitsBySupertrait { supertrait } => {
+            let traits: Vec<TraitInfo> = info
+                .traits
+                .values()
+                .filter(|t| {
+                    t.all_supertraits
+                        .iter()
+                        .any(|s| bronzite_types::trait_path_matches(s, supertrait))
+                })
+                .map(trait_info)
+                .collect();
+            QueryResult::Success {
+                data: QueryData::Traits {
+                    traits,
+                    warnings: info.warnings.clone(),
+                },
+            }
+        }
+
+        // Splitting a stream into individual responses is up to the daemon
+        Query::FindTypes { pattern, options } | Query::StreamTypes { pattern, options } => {
+            let mut types: Vec<TypeSummary> = info
                 .types
                 .values()
-                .filter(|t| bronzite_types::path_matches_pattern(&t.path, pattern))
+                .filter(|t| bronzite_types::path_matches_pattern_with(&t.path, pattern, options))
                 .map(|t| TypeSummary {
                     name: t.name.clone(),
                     path: t.path.clone(),
                     kind: t.kind.clone(),
                     generics: t.generics.clone(),
+                    crate_name: Some(info.crate_name.clone()),
                 })
                 .collect();
+            bronzite_types::sort_by_path(&mut types, |t| &t.path);
+            QueryResult::Success {
+                data: QueryData::Types {
+                    types,
+                    warnings: info.warnings.clone(),
+                },
+            }
+        }
+
+        Query::GetModuleTree => QueryResult::Success {
+            data: QueryData::ModuleTree(ModuleTree::build(
+                &info.crate_name,
+                &info.modules,
+                &info.items,
+            )),
+        },
+
+        Query::GetFunction { path } => match info.functions.get(path) {
+            Some(function) => QueryResult::Success {
+                data: QueryData::FunctionDetails(function.clone()),
+            },
+            None => QueryResult::Error {
+                message: format!("Function not found: {}", path),
+            },
+        },
+
+        Query::GetMacro { path } => match info.macros.get(path) {
+            Some(macro_details) => QueryResult::Success {
+                data: QueryData::MacroDetails(macro_details.clone()),
+            },
+            None => QueryResult::Error {
+                message: format!("Macro not found: {}", path),
+            },
+        },
+
+        Query::GetConst { path } => match info.consts.get(path) {
+            Some(const_details) => QueryResult::Success {
+                data: QueryData::ConstDetails(const_details.clone()),
+            },
+            None => QueryResult::Error {
+                message: format!("Const not found: {}", path),
+            },
+        },
+
+        Query::FindFunctions { pattern, options } => {
+            let mut functions: Vec<FunctionDetails> = info
+                .functions
+                .values()
+                .filter(|f| bronzite_types::path_matches_pattern_with(&f.path, pattern, options))
+                .cloned()
+                .collect();
+            bronzite_types::sort_by_path(&mut functions, |f| &f.path);
             QueryResult::Success {
-                data: QueryData::Types { types },
+                data: QueryData::Functions {
+                    functions,
+                    warnings: info.warnings.clone(),
+                },
             }
         }
 
+        Query::FindMethods {
+            pattern,
+            include_trait_impls,
+        } => QueryResult::Success {
+            data: QueryData::Methods {
+                methods: info.find_methods(pattern, *include_trait_impls),
+                warnings: info.warnings.clone(),
+            },
+        },
+
         Query::ResolveAlias { path } => match info.type_aliases.get(path) {
             Some(alias) => QueryResult::Success {
                 data: QueryData::ResolvedType {
                     original: alias.ty.clone(),
                     resolved: alias.resolved_ty.clone(),
-                    chain: vec![alias.path.clone(), alias.resolved_ty.clone()],
+                    chain: alias.chain.clone(),
+                    generics: alias.generics.clone(),
                 },
             },
             None => QueryResult::Error {
@@ -1621,12 +3626,33 @@ fn execute_query(tcx: TyCtxt<'_>, query: &Query) -> QueryResult {
             },
         },
 
+        Query::GetTypeAliases { pattern } => {
+            let mut aliases: Vec<TypeAliasInfo> = info
+                .type_aliases
+                .values()
+                .filter(|a| bronzite_types::path_matches_pattern(&a.path, pattern))
+                .cloned()
+                .collect();
+            bronzite_types::sort_by_path(&mut aliases, |a| &a.path);
+            QueryResult::Success {
+                data: QueryData::TypeAliases {
+                    aliases,
+                    warnings: info.warnings.clone(),
+                },
+            }
+        }
+
         Query::CheckImpl {
             type_path,
             trait_path,
         } => {
-            let impls = info.trait_impls.get(type_path).cloned().unwrap_or_default();
-            let impl_info = impls.into_iter().find(|i| i.trait_path == *trait_path);
+            let impl_info = info
+                .trait_impls
+                .iter()
+                .filter(|(key, _)| bronzite_types::type_path_matches(key, type_path))
+                .flat_map(|(_, impls)| impls)
+                .find(|i| bronzite_types::trait_path_matches(&i.trait_path, trait_path))
+                .cloned();
             QueryResult::Success {
                 data: QueryData::ImplCheck {
                     implements: impl_info.is_some(),
@@ -1649,6 +3675,7 @@ fn execute_query(tcx: TyCtxt<'_>, query: &Query) -> QueryResult {
                                 path: t.path.clone(),
                                 kind: t.kind.clone(),
                                 generics: t.generics.clone(),
+                                crate_name: Some(info.crate_name.clone()),
                             })
                     })
                     .collect();
@@ -1661,12 +3688,116 @@ fn execute_query(tcx: TyCtxt<'_>, query: &Query) -> QueryResult {
             },
         },
 
+        Query::GetAllImplsOfTrait { trait_path } => {
+            let mut impls: Vec<TraitImplDetails> = info
+                .trait_impls
+                .values()
+                .flatten()
+                .filter(|impl_| bronzite_types::trait_path_matches(&impl_.trait_path, trait_path))
+                .cloned()
+                .collect();
+            bronzite_types::sort_by_path(&mut impls, |impl_| &impl_.self_ty);
+            QueryResult::Success {
+                data: QueryData::TraitImpls { impls },
+            }
+        }
+
+        Query::ResolveMethod {
+            type_path,
+            method_name,
+        } => {
+            let inherent = info
+                .inherent_impls
+                .get(type_path)
+                .into_iter()
+                .flatten()
+                .flat_map(|impl_| impl_.methods.iter().map(|m| (None, m)));
+            let from_traits = info
+                .trait_impls
+                .get(type_path)
+                .into_iter()
+                .flatten()
+                .flat_map(|impl_| {
+                    impl_
+                        .methods
+                        .iter()
+                        .map(|m| (Some(impl_.trait_path.clone()), m))
+                });
+            let candidates = inherent
+                .chain(from_traits)
+                .filter(|(_, m)| m.name == *method_name)
+                .map(|(trait_path, m)| MethodCandidate {
+                    trait_path,
+                    method: m.clone(),
+                })
+                .collect();
+            QueryResult::Success {
+                data: QueryData::MethodCandidates { candidates },
+            }
+        }
+
+        Query::DescribeType { type_path } => QueryResult::Success {
+            data: QueryData::TypeStructure(info.describe_type(type_path)),
+        },
+
+        #[cfg(feature = "regex")]
+        Query::FindTypesRegex { regex } => match info.find_types_regex(regex) {
+            Ok(types) => QueryResult::Success {
+                data: QueryData::Types {
+                    types,
+                    warnings: info.warnings.clone(),
+                },
+            },
+            Err(e) => QueryResult::Error {
+                message: format!("Invalid regex '{}': {}", regex, e),
+            },
+        },
+        #[cfg(not(feature = "regex"))]
+        Query::FindTypesRegex { .. } => QueryResult::Error {
+            message: "bronzite-query was built without the `regex` feature".to_string(),
+        },
+
+        Query::GetTypeInstantiated { .. }
+        | Query::CheckConvertible { .. }
+        | Query::CheckImplFor { .. }
+        | Query::CheckSized { .. }
+        | Query::GetSpanText { .. }
+        | Query::GetImplMethodSignature { .. } => unreachable!("handled above"),
+
         Query::Ping => QueryResult::Success {
             data: QueryData::Pong,
         },
 
+        Query::Version => QueryResult::Success {
+            data: QueryData::Version {
+                daemon_version: env!("CARGO_PKG_VERSION").to_string(),
+                protocol_version: bronzite_types::PROTOCOL_VERSION,
+                toolchain: rustc_interface::util::rustc_version_str()
+                    .unwrap_or("unknown")
+                    .to_string(),
+            },
+        },
+
         Query::Shutdown => QueryResult::Success {
             data: QueryData::ShuttingDown,
         },
+
+        // Only the crate being compiled is known here
+        Query::ListCachedCrates => QueryResult::Success {
+            data: QueryData::Crates {
+                crates: vec![tcx.crate_name(LOCAL_CRATE).to_string()],
+            },
+        },
+    }
+}
+
+fn trait_info(t: &TraitDetails) -> TraitInfo {
+    TraitInfo {
+        name: t.name.clone(),
+        path: t.path.clone(),
+        generics: t.generics.clone(),
+        required_methods: t.methods.iter().filter(|m| !m.has_default).count(),
+        provided_methods: t.methods.iter().filter(|m| m.has_default).count(),
+        supertraits: t.supertraits.clone(),
     }
 }
@@ -0,0 +1,502 @@
+//! Extraction tests that run the plugin on the fixture crate in `example/`.
+
+use std::path::Path;
+use std::process::Command;
+use std::sync::OnceLock;
+
+use bronzite_types::{
+    CrateTypeInfo, GenericParamKind, IndirectTraitImpl, MethodDetails, Query, QueryData,
+    QueryResult, ReceiverKind, ReturnType, TypeStructure, Visibility, WherePredicate,
+};
+
+/// The example crate's extracted type information, shared by all tests.
+fn example() -> &'static CrateTypeInfo {
+    static INFO: OnceLock<CrateTypeInfo> = OnceLock::new();
+    INFO.get_or_init(|| extract(&[]))
+}
+
+/// Run `cargo bronzite-query --extract` on the example crate.
+fn extract(args: &[&str]) -> CrateTypeInfo {
+    run_extract(
+        Command::new(env!("CARGO_BIN_EXE_cargo-bronzite-query")),
+        "example",
+        args,
+    )
+}
+
+/// Run the extraction with `--cfg test`, so `#[cfg(test)]` items are compiled in.
+///
+/// The plugin drives `cargo check` on the lib target only, never `--test`, so
+/// `#[test]` functions are stripped before it sees them; `--cfg test` is the
+/// way test-only items reach it.
+fn extract_cfg_test(args: &[&str]) -> CrateTypeInfo {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_cargo-bronzite-query"));
+    command.env("RUSTFLAGS", "--cfg test");
+    run_extract(command, "example-cfg-test", args)
+}
+
+/// Answer `query` from a single compile of the example crate, the way the
+/// daemon does for queries it doesn't serve from its cache.
+fn query(query: Query) -> QueryResult {
+    let query = serde_json::to_string(&query).unwrap();
+    let output = run_plugin(
+        Command::new(env!("CARGO_BIN_EXE_cargo-bronzite-query")),
+        "example",
+        &["--query", &query],
+    );
+    serde_json::from_slice(&output).expect("query output isn't a query result")
+}
+
+fn run_extract(command: Command, target_subdir: &str, args: &[&str]) -> CrateTypeInfo {
+    let args = [&["--extract"], args].concat();
+    let output = run_plugin(command, target_subdir, &args);
+    serde_json::from_slice(&output).expect("extraction output isn't crate info")
+}
+
+fn run_plugin(mut command: Command, target_subdir: &str, args: &[&str]) -> Vec<u8> {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let output = command
+        .arg("bronzite-query")
+        .args(args)
+        .current_dir(manifest_dir.join("../../example"))
+        .env(
+            "CARGO_TARGET_DIR",
+            Path::new(env!("CARGO_TARGET_TMPDIR")).join(target_subdir),
+        )
+        .output()
+        .expect("failed to run cargo-bronzite-query");
+    assert!(
+        output.status.success(),
+        "bronzite-query failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output.stdout
+}
+
+#[test]
+fn test_indirect_trait_impls() {
+    let foo = &example().types["Foo"];
+    assert!(foo.trait_impls.iter().any(|t| t == "MyTrait"));
+    assert!(foo.trait_impls.iter().all(|t| !t.contains(" for ")));
+    assert_eq!(
+        foo.indirect_trait_impls,
+        [
+            IndirectTraitImpl {
+                trait_path: "MyTrait".to_string(),
+                self_ty: "&Foo".to_string(),
+            },
+            IndirectTraitImpl {
+                trait_path: "Consume".to_string(),
+                self_ty: "std::boxed::Box<Foo>".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_union_field_layout() {
+    let info = example();
+    let layout = &info.layouts["Bits"];
+    assert_eq!(layout.size, 8);
+    let fields: Vec<_> = layout
+        .field_offsets
+        .iter()
+        .flatten()
+        .map(|f| (f.name.as_deref().unwrap(), f.offset, f.size))
+        .collect();
+    assert_eq!(fields, [("byte", 0, 1), ("word", 0, 4), ("wide", 0, 8)]);
+
+    // As `UnionDef::fields_with_layout` applies them
+    let mut fields = info.types["Bits"].fields.clone().unwrap();
+    layout.apply_to_fields(&mut fields);
+    assert!(fields.iter().all(|f| f.offset == Some(0)));
+}
+
+#[test]
+fn test_array_fields() {
+    let info = example();
+    let fields: Vec<_> = info.types["Packet"]
+        .fields
+        .iter()
+        .flatten()
+        .map(|f| (f.name.as_deref().unwrap(), f.ty.as_str()))
+        .collect();
+    // The length expression is evaluated
+    assert_eq!(fields, [("header", "[u8; 4]"), ("checksum", "[u16; 4]")]);
+
+    let layout = &info.layouts["Packet"];
+    assert_eq!((layout.size, layout.align), (12, 2));
+    let offsets: Vec<_> = layout
+        .field_offsets
+        .iter()
+        .flatten()
+        .map(|f| (f.offset, f.size))
+        .collect();
+    assert_eq!(offsets, [(0, 4), (4, 8)]);
+}
+
+#[test]
+fn test_impl_trait_argument() {
+    let sum_bytes = &example().functions["sum_bytes"];
+    let params = &sum_bytes.parsed_signature.params;
+    assert_eq!(params.len(), 1);
+    assert_eq!(params[0].ty, "impl std::iter::Iterator<Item = u8>");
+    // The synthetic parameter isn't listed as a generic
+    assert!(sum_bytes.parsed_signature.generics.is_empty());
+}
+
+#[test]
+fn test_const_param_names() {
+    let grid = &example().types["Grid"];
+    assert_eq!(grid.generics.len(), 1);
+    assert_eq!(grid.generics[0].name, "W");
+    assert_eq!(
+        grid.generics[0].kind,
+        GenericParamKind::Const {
+            ty: "usize".to_string()
+        }
+    );
+    let cells = &grid.fields.as_ref().unwrap()[0];
+    assert_eq!(cells.ty, "[[u8; W]; W]");
+}
+
+#[test]
+fn test_alias_fields() {
+    let info = example();
+    let fields = info.types["Account"].fields.as_ref().unwrap();
+    let types: Vec<_> = fields
+        .iter()
+        .map(|f| (f.name.as_deref().unwrap(), f.ty.as_str()))
+        .collect();
+    // Aliases are expanded, so `Field::type_def` finds `Bar` directly
+    assert_eq!(types, [("id", "u64"), ("owner", "Bar")]);
+    assert_eq!(info.type_aliases["UserId"].resolved_ty, "u64");
+    assert_eq!(info.type_aliases["Owner"].chain, ["Owner", "Bar"]);
+}
+
+#[test]
+fn test_test_items_absent_by_default() {
+    let info = example();
+    assert!(!info.items.iter().any(|i| i.name == "sample_foo"));
+    assert!(!info.items.iter().any(|i| i.name == "tests"));
+    assert!(!info.items.iter().any(|i| i.name == "test_foo"));
+}
+
+#[test]
+fn test_exclude_tests() {
+    let info = extract_cfg_test(&[]);
+    let sample_foo = info
+        .items
+        .iter()
+        .find(|i| i.name == "sample_foo")
+        .expect("cfg(test) item missing under --cfg test");
+    assert!(sample_foo.is_test);
+    assert!(info.items.iter().any(|i| i.name == "tests" && i.is_test));
+    assert!(!info.items.iter().any(|i| i.name == "Foo" && i.is_test));
+
+    let info = extract_cfg_test(&["--exclude-tests"]);
+    assert!(!info.items.iter().any(|i| i.is_test));
+    assert!(!info.items.iter().any(|i| i.name == "sample_foo"));
+    assert!(info.items.iter().any(|i| i.name == "Foo"));
+}
+
+#[test]
+fn test_method_visibility() {
+    let info = example();
+    // A private fn in an impl at the crate root is visible to the whole crate.
+    let summaries: Vec<_> = info.types["Bar"]
+        .inherent_methods
+        .iter()
+        .map(|m| (m.name.as_str(), &m.visibility))
+        .collect();
+    assert!(summaries.contains(&("get_name", &Visibility::Public)));
+    assert!(summaries.contains(&("normalize", &Visibility::Crate)));
+
+    let methods: Vec<_> = info.inherent_impls["Bar"]
+        .iter()
+        .flat_map(|i| &i.methods)
+        .map(|m| (m.name.as_str(), &m.visibility))
+        .collect();
+    assert!(methods.contains(&("get_name", &Visibility::Public)));
+    assert!(methods.contains(&("normalize", &Visibility::Crate)));
+}
+
+#[test]
+fn test_type_visibility() {
+    let info = example();
+    let visibility = |path: &str| &info.types[path].visibility;
+    // Private at the crate root is the same as `pub(crate)`
+    assert_eq!(visibility("RootPrivate"), &Visibility::Crate);
+    assert_eq!(visibility("access::CrateVisible"), &Visibility::Crate);
+    assert_eq!(visibility("access::inner::Hidden"), &Visibility::Private);
+
+    let access = Visibility::Restricted {
+        path: "access".to_string(),
+    };
+    assert_eq!(visibility("access::inner::ParentVisible"), &access);
+    assert_eq!(visibility("access::inner::AccessVisible"), &access);
+}
+
+#[test]
+fn test_async_trait_methods() {
+    let info = example();
+    let fetch = &info.traits["Fetcher"].methods[0];
+    assert_eq!(fetch.name, "fetch");
+    assert!(fetch.is_async);
+    assert!(info.traits["MyTrait"].methods.iter().all(|m| !m.is_async));
+
+    let fetcher_impl = info.trait_impls["Foo"]
+        .iter()
+        .find(|i| i.trait_path == "Fetcher")
+        .expect("Foo should implement Fetcher");
+    assert!(fetcher_impl.methods.iter().all(|m| m.is_async));
+}
+
+#[test]
+fn test_method_where_predicates() {
+    let methods = &example().traits["AnotherTrait"].methods;
+    let into_output = methods.iter().find(|m| m.name == "into_output").unwrap();
+    assert_eq!(
+        into_output.parsed_signature.where_predicates,
+        [WherePredicate {
+            bounded_ty: "Self".to_string(),
+            bounds: vec!["Sized".to_string()],
+        }]
+    );
+    let transform = methods.iter().find(|m| m.name == "transform").unwrap();
+    assert!(transform.parsed_signature.where_predicates.is_empty());
+}
+
+#[test]
+fn test_file_edition() {
+    let tmp = Path::new(env!("CARGO_TARGET_TMPDIR"));
+    let file = tmp.join("single_file.rs");
+    std::fs::write(&file, "pub struct Single;\n").unwrap();
+    let file = file.to_str().unwrap();
+
+    let default = run_extract(
+        Command::new(env!("CARGO_BIN_EXE_cargo-bronzite-query")),
+        "single-file",
+        &["--file", file],
+    );
+    assert_eq!(default.edition, "2021");
+    assert!(default.types.contains_key("Single"));
+
+    let info = run_extract(
+        Command::new(env!("CARGO_BIN_EXE_cargo-bronzite-query")),
+        "single-file",
+        &["--file", file, "--edition", "2024"],
+    );
+    assert_eq!(info.edition, "2024");
+}
+
+#[test]
+fn test_param_types_resolve_through_wrappers() {
+    let params: Vec<_> = example().inherent_impls["Registry"]
+        .iter()
+        .flat_map(|i| &i.methods)
+        .map(|m| (m.name.as_str(), &m.parsed_signature.params))
+        .collect();
+    let param_ty = |name, index: usize| {
+        let (_, params) = params.iter().find(|(n, _)| *n == name).unwrap();
+        TypeStructure::parse(&params[index].ty)
+    };
+
+    let user = param_ty("rename", 0);
+    assert!(matches!(
+        user,
+        TypeStructure::Reference { mutable: true, .. }
+    ));
+    assert_eq!(user.wrapped_paths(), ["User"]);
+
+    let users = param_ty("add_all", 0);
+    assert_eq!(users.wrapped_paths(), ["std::vec::Vec", "User"]);
+}
+
+#[test]
+fn test_impl_const_values() {
+    let version = |ty: &str| {
+        example().trait_impls[ty]
+            .iter()
+            .find(|i| i.trait_path == "Versioned")
+            .map(|i| i.assoc_consts[0].value.clone())
+            .unwrap()
+    };
+    assert_eq!(version("Foo").as_deref(), Some("3"));
+    // Evaluating it panics, which mustn't fail the extraction
+    assert_eq!(version("Bar"), None);
+}
+
+#[test]
+fn test_const_and_static_init_source() {
+    let info = example();
+    let words = &info.consts["CHECKSUM_WORDS"];
+    assert!(!words.is_static);
+    assert_eq!(words.init_source.as_deref(), Some("2"));
+
+    let packets = &info.consts["MAX_PACKETS"];
+    assert!(packets.is_static);
+    assert_eq!(packets.ty, "usize");
+    assert_eq!(packets.init_source.as_deref(), Some("16 * 4"));
+    assert_eq!(packets.value, None);
+}
+
+#[test]
+fn test_build_script_type() {
+    let target = &example().types["BuildTarget"];
+    let fields: Vec<_> = target
+        .fields
+        .iter()
+        .flatten()
+        .map(|f| (f.name.as_deref().unwrap(), f.ty.as_str()))
+        .collect();
+    assert_eq!(fields, [("triple", "&'static str")]);
+    assert_eq!(example().consts["BUILD_TARGET"].ty, "BuildTarget");
+}
+
+#[test]
+fn test_struct_const_value() {
+    let greeting = &example().consts["GREETING"];
+    // Braces inside the string are kept as written
+    assert_eq!(
+        greeting.value.as_deref(),
+        Some(r#"Template { source: "Hello, {{ name }}!" }"#)
+    );
+}
+
+#[test]
+fn test_types_using_type() {
+    let users = |type_path| {
+        example()
+            .types_using_type(type_path)
+            .into_iter()
+            .map(|u| (u.owner.path, u.fields))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        users("User"),
+        [("Registry".to_string(), vec!["users".to_string()])]
+    );
+    // `Account::owner` is declared through the `Owner` alias
+    assert!(
+        users("Bar")
+            .iter()
+            .any(|(owner, fields)| owner == "Account" && fields == &["owner"])
+    );
+}
+
+#[test]
+fn test_impl_method_signature() {
+    let signature = |method_name: &str| match query(Query::GetImplMethodSignature {
+        type_path: "i32".to_string(),
+        trait_path: "AnotherTrait".to_string(),
+        method_name: method_name.to_string(),
+    }) {
+        QueryResult::Success {
+            data: QueryData::MethodSignature(signature),
+        } => signature,
+        other => panic!("unexpected result: {:?}", other),
+    };
+
+    // `&self -> Self::Output`, with `Self` and the projection resolved
+    let transform = signature("transform");
+    let receiver = transform.receiver.unwrap();
+    assert_eq!(receiver.kind, "&i32");
+    assert_eq!(receiver.receiver_kind, ReceiverKind::Ref);
+    assert_eq!(
+        transform.return_ty,
+        Some(ReturnType::Type("std::string::String".to_string()))
+    );
+
+    // A default method the impl doesn't override
+    let into_output = signature("into_output");
+    assert_eq!(into_output.receiver.unwrap().kind, "i32");
+    assert_eq!(into_output.return_ty, transform.return_ty);
+}
+
+#[test]
+fn test_check_sized() {
+    let is_sized = |args: &[&str]| match query(Query::CheckSized {
+        type_path: "Labelled".to_string(),
+        args: args.iter().map(|a| a.to_string()).collect(),
+    }) {
+        QueryResult::Success {
+            data: QueryData::SizedCheck { is_sized },
+        } => is_sized,
+        other => panic!("unexpected result: {:?}", other),
+    };
+    assert!(is_sized(&["u32"]));
+    assert!(!is_sized(&["str"]));
+    assert!(!is_sized(&["[u8]"]));
+}
+
+#[test]
+fn test_receiver_kinds() {
+    let receivers = |methods: &[MethodDetails]| -> Vec<ReceiverKind> {
+        methods
+            .iter()
+            .map(|m| m.parsed_signature.receiver.clone().unwrap().receiver_kind)
+            .collect()
+    };
+    let pinned = ReceiverKind::Pin(Box::new(ReceiverKind::RefMut));
+    let expected = [ReceiverKind::Owned, ReceiverKind::Box, pinned];
+
+    let declared: Vec<_> = example().traits["Consume"]
+        .methods
+        .iter()
+        .map(|m| m.parsed_signature.receiver.clone().unwrap().receiver_kind)
+        .collect();
+    assert_eq!(declared, expected);
+
+    // In `impl Consume for Box<Foo>`, `self` is the box itself
+    for (wrapper, inner) in [("std::boxed::Box", "Foo"), ("std::rc::Rc", "Bar")] {
+        let consume = example().trait_impls[wrapper]
+            .iter()
+            .find(|i| i.trait_path == "Consume" && i.self_ty_args[0] == inner)
+            .unwrap();
+        assert_eq!(
+            receivers(&consume.methods),
+            expected,
+            "{}<{}>",
+            wrapper,
+            inner
+        );
+    }
+}
+
+#[test]
+fn test_impl_trait_bounds() {
+    let signature = |name: &str| &example().functions[name].parsed_signature;
+    let impl_trait = |bound: &str| Some(ReturnType::ImplTrait(vec![bound.to_string()]));
+
+    assert_eq!(signature("halt").return_ty, Some(ReturnType::Never));
+    assert_eq!(
+        signature("evens").return_ty,
+        impl_trait("std::iter::Iterator<Item = u8>")
+    );
+    // `Item` belongs to the `Iterator` supertrait
+    assert_eq!(
+        signature("digits").return_ty,
+        impl_trait("std::iter::DoubleEndedIterator<Item = u8>")
+    );
+    // `Output` belongs to `FnOnce`
+    assert_eq!(
+        signature("adder").return_ty,
+        impl_trait("std::ops::Fn(u8) -> u16")
+    );
+    assert_eq!(
+        signature("repeat").params[1].ty,
+        "impl std::ops::FnMut(u8) -> bool"
+    );
+}
+
+#[test]
+fn test_span_text() {
+    let span = example().types["Account"].span.clone().unwrap();
+    match query(Query::GetSpanText { span }) {
+        QueryResult::Success {
+            data: QueryData::SpanText { text },
+        } => assert_eq!(text, "pub struct Account"),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
@@ -11,6 +11,15 @@ use serde::{Deserialize, Serialize};
 // Requests and Responses
 // ============================================================================
 
+/// Version of the query protocol defined in this crate. Bumped whenever a
+/// change to the request or response types breaks compatibility between a
+/// client and a daemon built from different versions.
+pub const PROTOCOL_VERSION: u32 = 3;
+
+/// The nightly toolchain the daemon compiles crates with. The query plugin
+/// links against this compiler's internals, so no other toolchain works.
+pub const TOOLCHAIN: &str = "nightly-2025-08-20";
+
 /// A request sent from a client to the Bronzite daemon.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Request {
@@ -20,6 +29,25 @@ pub struct Request {
     pub crate_name: String,
     /// The query to execute
     pub query: Query,
+    /// Whether the daemon may extract without method bodies.
+    ///
+    /// Signature-only consumers can set this for a faster, smaller extraction.
+    #[serde(default)]
+    pub skip_bodies: bool,
+    /// Whether non-`pub` items, fields and methods are included in the
+    /// response. Consumers that only care about the public API can turn
+    /// this off; macros working inside the crate itself need it on.
+    #[serde(default = "include_private_default")]
+    pub include_private: bool,
+    /// Recompile the crate before answering, instead of serving cached
+    /// results. Guards against edits the file watcher hasn't picked up yet,
+    /// at the cost of a compile.
+    #[serde(default)]
+    pub fresh: bool,
+}
+
+fn include_private_default() -> bool {
+    true
 }
 
 /// Available queries for type system introspection.
@@ -29,9 +57,20 @@ pub enum Query {
     /// List all items in the crate
     ListItems,
 
+    /// Get everything extracted from the crate in one response, e.g. to save
+    /// a snapshot to compare later with `bronzite_client::diff`
+    GetCrateInfo,
+
+    /// Get the crate's edition and the rustc version analyzing it
+    GetCrateMeta,
+
     /// Get detailed information about a specific type
     GetType { path: String },
 
+    /// Get a type by the `stable_id` reported for it in `ListItems` or
+    /// `GetType`. See [`ItemInfo::stable_id`] for when the id changes
+    GetTypeById { id: String },
+
     /// Get all trait implementations for a type
     GetTraitImpls { type_path: String },
 
@@ -41,33 +80,173 @@ pub enum Query {
     /// Get all fields of a struct or enum variant
     GetFields { type_path: String },
 
+    /// Get one field of an enum variant, by name or, for tuple variants, by
+    /// index (`"0"`)
+    GetVariantField {
+        enum_path: String,
+        variant: String,
+        field: String,
+    },
+
     /// Get memory layout information for a type
     GetLayout { type_path: String },
 
+    /// Get a type together with its fields, impls and layout in one round trip
+    GetTypeFull { path: String },
+
     /// Get all traits defined in the crate
     GetTraits,
 
     /// Get detailed information about a trait
     GetTrait { path: String },
 
+    /// Get a trait alias (`trait Shape = Area + Debug;`)
+    GetTraitAlias { path: String },
+
+    /// Find traits that have `supertrait` among their direct or indirect
+    /// supertraits
+    FindTraitsBySupertrait { supertrait: String },
+
+    /// Find the structs, enums and unions with a field whose type mentions
+    /// `type_path`, directly or as a generic argument (`Vec<LegacyId>`)
+    FindTypesUsingType { type_path: String },
+
+    /// Get the crate's modules as a tree, with the items each one defines
+    GetModuleTree,
+
     /// Find types matching a path pattern (e.g., "bevy::prelude::*")
-    FindTypes { pattern: String },
+    FindTypes {
+        pattern: String,
+        #[serde(default)]
+        options: MatchOptions,
+    },
+
+    /// Like `FindTypes`, but every match is sent as its own `TypeItem`
+    /// response, followed by a `StreamEnd`. All of them carry the request's
+    /// id, so a client can process matches one at a time.
+    StreamTypes {
+        pattern: String,
+        #[serde(default)]
+        options: MatchOptions,
+    },
+
+    /// Find types whose full path matches a regular expression. Answered
+    /// with `Types`; requires a daemon built with the `regex` feature
+    FindTypesRegex { regex: String },
+
+    /// Get detailed information about a free function
+    GetFunction { path: String },
+
+    /// Get a `macro_rules!` or procedural macro definition
+    GetMacro { path: String },
+
+    /// Get a top-level `const` or `static` item with its type and value
+    GetConst { path: String },
+
+    /// Find free functions matching a path pattern
+    FindFunctions {
+        pattern: String,
+        #[serde(default)]
+        options: MatchOptions,
+    },
+
+    /// Find methods of inherent impls, across all types, whose path (e.g.
+    /// `User::new`) matches a pattern. With `include_trait_impls`, trait
+    /// impl methods (`<User as Greet>::hello`) are searched too
+    FindMethods {
+        pattern: String,
+        #[serde(default)]
+        include_trait_impls: bool,
+    },
 
     /// Resolve a type alias to its underlying type
     ResolveAlias { path: String },
 
-    /// Check if a type implements a trait
+    /// Get every type alias whose path matches a pattern, with its resolved
+    /// type
+    GetTypeAliases { pattern: String },
+
+    /// Check if a type implements a trait. `trait_path` may name the trait
+    /// alone (`From`) or with generic arguments (`From<String>`)
     CheckImpl {
         type_path: String,
         trait_path: String,
     },
 
+    /// Check if a generic type instantiated with `type_args` (e.g. `Wrapper`
+    /// with `["String"]`) implements a trait, asking the trait solver so that
+    /// conditional impls (`impl<T: Clone> Clone for Wrapper<T>`) are decided
+    /// for those arguments. Answered with `ImplCheck`
+    CheckImplFor {
+        type_path: String,
+        type_args: Vec<String>,
+        trait_path: String,
+    },
+
+    /// Check if a generic type instantiated with `args` is `Sized`, e.g.
+    /// `Wrapper` with `["str"]` isn't when its last field is a `T`.
+    /// Answered with `SizedCheck`
+    CheckSized {
+        type_path: String,
+        args: Vec<String>,
+    },
+
+    /// Get the source text a span covers, e.g. the `span` of a type or
+    /// field from an earlier query. Answered with `SpanText`
+    GetSpanText { span: SpanInfo },
+
     /// Get all types that implement a specific trait
     GetImplementors { trait_path: String },
 
+    /// Get every impl of a trait in the crate, across all self types.
+    /// Answered with `TraitImpls`
+    GetAllImplsOfTrait { trait_path: String },
+
+    /// Get a generic type with concrete arguments substituted for its generic
+    /// parameters (e.g. `Wrapper` with `["u32"]`), so field types and layout
+    /// describe that instantiation
+    GetTypeInstantiated {
+        type_path: String,
+        args: Vec<String>,
+    },
+
+    /// Check if a value of type `from` converts into `into` (`from: Into<into>`),
+    /// with types written as Rust source (e.g. `&str`, `Vec<u8>`)
+    CheckConvertible { from: String, into: String },
+
+    /// Get the signature of a method as the impl of `trait_path` for
+    /// `type_path` provides it: `Self` is replaced by the implementing type
+    /// and associated type projections are normalized, so `fn id(&self) ->
+    /// Self::Id` becomes `fn(&User) -> u64`. Works for default methods the
+    /// impl doesn't override too
+    GetImplMethodSignature {
+        type_path: String,
+        trait_path: String,
+        method_name: String,
+    },
+
+    /// Find the inherent and trait impl methods named `method_name` on a
+    /// type, to see which one a call resolves to or whether it's ambiguous
+    ResolveMethod {
+        type_path: String,
+        method_name: String,
+    },
+
+    /// Break a type written as a string (e.g. a field type such as
+    /// `(u32, [u8; 4])`) down into its structure
+    DescribeType { type_path: String },
+
     /// Ping to check if daemon is alive
     Ping,
 
+    /// Get the daemon's version, protocol version and toolchain
+    Version,
+
+    /// List the crates the daemon knows: workspace members and anything it
+    /// has cached. Names use `_`, as rustc does. The request's crate name is
+    /// ignored.
+    ListCachedCrates,
+
     /// Request the daemon to shut down
     Shutdown,
 }
@@ -96,12 +275,28 @@ pub enum QueryResult {
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum QueryData {
     /// Response to ListItems
-    Items { items: Vec<ItemInfo> },
+    Items {
+        items: Vec<ItemInfo>,
+        /// Items that failed to extract; non-empty means the list is incomplete
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        warnings: Vec<String>,
+    },
 
-    /// Response to GetType
+    /// Response to GetCrateInfo. Failed items are listed in its `warnings`
+    CrateInfo(Box<CrateTypeInfo>),
+
+    /// Response to GetCrateMeta
+    CrateMeta {
+        /// e.g. `2021`
+        edition: String,
+        /// e.g. `1.91.0-nightly (05f5a58e8 2025-08-19)`
+        rustc_version: String,
+    },
+
+    /// Response to GetType and GetTypeInstantiated
     TypeInfo(TypeDetails),
 
-    /// Response to GetTraitImpls
+    /// Response to GetTraitImpls and GetAllImplsOfTrait
     TraitImpls { impls: Vec<TraitImplDetails> },
 
     /// Response to GetInherentImpls
@@ -110,39 +305,159 @@ pub enum QueryData {
     /// Response to GetFields
     Fields { fields: Vec<FieldInfo> },
 
+    /// Response to GetVariantField
+    Field(FieldInfo),
+
     /// Response to GetLayout
-    Layout(LayoutInfo),
+    Layout(TypeLayout),
+
+    /// Response to GetTypeFull
+    TypeFull {
+        details: TypeDetails,
+        fields: Vec<FieldInfo>,
+        trait_impls: Vec<TraitImplDetails>,
+        inherent_impls: Vec<InherentImplDetails>,
+        /// `None` when no layout could be computed, e.g. for generic types
+        layout: Option<LayoutInfo>,
+    },
 
-    /// Response to GetTraits
-    Traits { traits: Vec<TraitInfo> },
+    /// Response to GetTraits and FindTraitsBySupertrait
+    Traits {
+        traits: Vec<TraitInfo>,
+        /// Items that failed to extract; non-empty means the list is incomplete
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        warnings: Vec<String>,
+    },
+
+    /// Response to FindTypesUsingType, sorted by the owning type's path
+    TypeUsages {
+        usages: Vec<TypeUsage>,
+        /// Items that failed to extract; non-empty means the list is incomplete
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        warnings: Vec<String>,
+    },
 
     /// Response to GetTrait
     TraitDetails(TraitDetails),
 
-    /// Response to FindTypes
-    Types { types: Vec<TypeSummary> },
+    /// Response to GetTraitAlias
+    TraitAlias(TraitAliasInfo),
+
+    /// Response to FindTypes and FindTypesRegex
+    Types {
+        types: Vec<TypeSummary>,
+        /// Items that failed to extract; non-empty means the list is incomplete
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        warnings: Vec<String>,
+    },
+
+    /// One match of a StreamTypes query
+    TypeItem(TypeSummary),
+
+    /// Ends the responses to a StreamTypes query
+    StreamEnd {
+        /// How many `TypeItem`s were sent
+        count: usize,
+        /// Items that failed to extract; non-empty means the stream is incomplete
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        warnings: Vec<String>,
+    },
+
+    /// Response to GetModuleTree
+    ModuleTree(ModuleTree),
+
+    /// Response to GetFunction
+    FunctionDetails(FunctionDetails),
+
+    /// Response to GetMacro
+    MacroDetails(MacroDetails),
+
+    /// Response to GetConst
+    ConstDetails(ConstDetails),
+
+    /// Response to GetImplMethodSignature
+    MethodSignature(FunctionSignature),
+
+    /// Response to FindMethods
+    Methods {
+        methods: Vec<MethodSummary>,
+        /// Items that failed to extract; non-empty means the list is incomplete
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        warnings: Vec<String>,
+    },
+
+    /// Response to FindFunctions
+    Functions {
+        functions: Vec<FunctionDetails>,
+        /// Items that failed to extract; non-empty means the list is incomplete
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        warnings: Vec<String>,
+    },
+
+    /// Response to GetTypeAliases
+    TypeAliases {
+        aliases: Vec<TypeAliasInfo>,
+        /// Items that failed to extract; non-empty means the list is incomplete
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        warnings: Vec<String>,
+    },
 
     /// Response to ResolveAlias
     ResolvedType {
         original: String,
         resolved: String,
         chain: Vec<String>,
+        /// Generic parameters declared on the alias
+        #[serde(default)]
+        generics: Vec<GenericParam>,
     },
 
-    /// Response to CheckImpl
+    /// Response to CheckImpl and CheckImplFor
     ImplCheck {
         implements: bool,
         impl_info: Option<TraitImplDetails>,
     },
 
+    /// Response to CheckSized
+    SizedCheck { is_sized: bool },
+
+    /// Response to GetSpanText
+    SpanText { text: String },
+
     /// Response to GetImplementors
     Implementors { types: Vec<TypeSummary> },
 
+    /// Response to CheckConvertible. `impl_info` is the crate's own
+    /// `From` impl providing the conversion, if there is one.
+    ConvertibleCheck {
+        convertible: bool,
+        impl_info: Option<TraitImplDetails>,
+    },
+
+    /// Response to ResolveMethod. Inherent methods come first, as they take
+    /// precedence over trait methods in method resolution.
+    MethodCandidates { candidates: Vec<MethodCandidate> },
+
+    /// Response to DescribeType
+    TypeStructure(TypeStructure),
+
     /// Response to Ping
     Pong,
 
+    /// Response to Version
+    Version {
+        daemon_version: String,
+        /// The daemon's [`PROTOCOL_VERSION`]
+        protocol_version: u32,
+        /// The rustc toolchain used to compile and analyze crates
+        toolchain: String,
+    },
+
     /// Response to Shutdown
     ShuttingDown,
+
+    /// Response to ListCachedCrates, sorted by name
+    Crates { crates: Vec<String> },
 }
 
 // ============================================================================
@@ -162,6 +477,18 @@ pub struct ItemInfo {
     pub visibility: Visibility,
     /// Span information (file, line, column)
     pub span: Option<SpanInfo>,
+    /// Whether the item only exists in test builds: a `#[test]` function or
+    /// anything under `#[cfg(test)]`. Extraction checks the library target,
+    /// so such items are only present when `--cfg test` is passed (e.g.
+    /// through `RUSTFLAGS`); `#[test]` functions never are.
+    #[serde(default)]
+    pub is_test: bool,
+    /// Identifier derived from the item's def path hash. It is the same
+    /// across recompiles and however the item is re-exported, but the def
+    /// path is where the item is defined, so moving or renaming the item
+    /// changes it, and so does a different compiler version.
+    #[serde(default)]
+    pub stable_id: String,
 }
 
 /// The kind of an item.
@@ -210,12 +537,259 @@ pub struct SpanInfo {
 // ============================================================================
 
 /// Summary information about a type (for listings).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TypeSummary {
     pub name: String,
     pub path: String,
     pub kind: TypeKind,
     pub generics: Vec<GenericParam>,
+    /// Name of the crate that defines the type, when known
+    #[serde(default)]
+    pub crate_name: Option<String>,
+}
+
+/// A type with fields of some other type, see `Query::FindTypesUsingType`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeUsage {
+    pub owner: TypeSummary,
+    /// The fields using the type, by name or index; fields of enum variants
+    /// as `Variant.field`
+    pub fields: Vec<String>,
+}
+
+impl TypeSummary {
+    /// Whether the type takes type or const parameters, i.e. can't be named
+    /// without generic arguments. Lifetime parameters don't count, as they
+    /// can be elided.
+    pub fn is_generic(&self) -> bool {
+        is_generic(&self.generics)
+    }
+
+    /// Whether the type has any type parameters.
+    pub fn has_type_params(&self) -> bool {
+        has_type_params(&self.generics)
+    }
+
+    /// Whether the type has any lifetime parameters.
+    pub fn has_lifetimes(&self) -> bool {
+        has_lifetimes(&self.generics)
+    }
+
+    /// Whether the type has any const parameters.
+    pub fn has_const_params(&self) -> bool {
+        has_const_params(&self.generics)
+    }
+}
+
+/// The structure of a type, as written in a type string.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TypeStructure {
+    /// `(A, B)`; the unit type is a tuple with no elements
+    Tuple { elements: Vec<TypeStructure> },
+    /// `[T; N]`
+    Array {
+        element: Box<TypeStructure>,
+        /// The length as written, e.g. `32` or a const parameter `N`
+        len: String,
+    },
+    /// `[T]`
+    Slice { element: Box<TypeStructure> },
+    /// `&T`, `&'a mut T`
+    Reference {
+        target: Box<TypeStructure>,
+        mutable: bool,
+        lifetime: Option<String>,
+    },
+    /// `*const T`, `*mut T`
+    Pointer {
+        target: Box<TypeStructure>,
+        mutable: bool,
+    },
+    /// A type named by a path, e.g. `u32` or `std::vec::Vec<u8>`
+    Named {
+        path: String,
+        /// Generic type and const arguments (lifetimes are left out)
+        args: Vec<TypeStructure>,
+        /// Set when the type is defined in the queried crate
+        summary: Option<TypeSummary>,
+    },
+    /// Anything else (`dyn Trait`, `impl Trait`, function pointers,
+    /// qualified paths), kept as written
+    Other { ty: String },
+}
+
+impl TypeStructure {
+    /// Parse a type string. This never fails: anything that isn't
+    /// recognized becomes [`TypeStructure::Other`].
+    pub fn parse(ty: &str) -> Self {
+        let ty = ty.trim();
+
+        if let Some(inner) = strip_delimiters(ty, '(', ')') {
+            let elements = split_top_level(inner, ',')
+                .into_iter()
+                .filter(|element| !element.trim().is_empty())
+                .map(Self::parse)
+                .collect();
+            return Self::Tuple { elements };
+        }
+
+        if let Some(inner) = strip_delimiters(ty, '[', ']') {
+            return match split_top_level(inner, ';').as_slice() {
+                [element, len] => Self::Array {
+                    element: Box::new(Self::parse(element)),
+                    len: len.trim().to_string(),
+                },
+                _ => Self::Slice {
+                    element: Box::new(Self::parse(inner)),
+                },
+            };
+        }
+
+        if let Some(rest) = ty.strip_prefix('&') {
+            let rest = rest.trim_start();
+            let (lifetime, rest) = if rest.starts_with('\'') {
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                (Some(rest[..end].to_string()), rest[end..].trim_start())
+            } else {
+                (None, rest)
+            };
+            let (mutable, rest) = match rest.strip_prefix("mut ") {
+                Some(rest) => (true, rest),
+                None => (false, rest),
+            };
+            return Self::Reference {
+                target: Box::new(Self::parse(rest)),
+                mutable,
+                lifetime,
+            };
+        }
+
+        for (prefix, mutable) in [("*const ", false), ("*mut ", true)] {
+            if let Some(rest) = ty.strip_prefix(prefix) {
+                return Self::Pointer {
+                    target: Box::new(Self::parse(rest)),
+                    mutable,
+                };
+            }
+        }
+
+        let (path, args) = match ty.find('<') {
+            Some(open) if ty.ends_with('>') => (&ty[..open], &ty[open + 1..ty.len() - 1]),
+            Some(_) => return Self::Other { ty: ty.to_string() },
+            None => (ty, ""),
+        };
+        let is_path = !path.is_empty()
+            && path
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == ':');
+        if !is_path {
+            return Self::Other { ty: ty.to_string() };
+        }
+
+        let args = split_top_level(args, ',')
+            .into_iter()
+            .map(str::trim)
+            .filter(|arg| !arg.is_empty() && !arg.starts_with('\''))
+            .map(Self::parse)
+            .collect();
+        Self::Named {
+            path: path.to_string(),
+            args,
+            summary: None,
+        }
+    }
+
+    /// The paths this type may name a crate type by, outermost first,
+    /// looking through references and the first type argument of wrappers.
+    /// For `&mut Vec<User>` that is `std::vec::Vec`, then `User`.
+    pub fn wrapped_paths(&self) -> Vec<&str> {
+        let mut paths = Vec::new();
+        let mut ty = self;
+        loop {
+            match ty {
+                Self::Reference { target, .. } => ty = target,
+                Self::Named { path, args, .. } => {
+                    paths.push(path.as_str());
+                    match args.first() {
+                        Some(arg) => ty = arg,
+                        None => return paths,
+                    }
+                }
+                _ => return paths,
+            }
+        }
+    }
+
+    /// Fill in the summary of every named type `lookup` knows about.
+    fn attach_summaries(&mut self, lookup: &impl Fn(&str) -> Option<TypeSummary>) {
+        match self {
+            Self::Tuple { elements } => {
+                for element in elements {
+                    element.attach_summaries(lookup);
+                }
+            }
+            Self::Array { element, .. } | Self::Slice { element } => {
+                element.attach_summaries(lookup);
+            }
+            Self::Reference { target, .. } | Self::Pointer { target, .. } => {
+                target.attach_summaries(lookup);
+            }
+            Self::Named {
+                path,
+                args,
+                summary,
+            } => {
+                *summary = lookup(path);
+                for arg in args {
+                    arg.attach_summaries(lookup);
+                }
+            }
+            Self::Other { .. } => {}
+        }
+    }
+}
+
+/// Return the inside of `s` if it is wrapped in one matching `open`/`close`
+/// pair, e.g. `(A, B)` but not `(A) -> (B)`.
+fn strip_delimiters(s: &str, open: char, close: char) -> Option<&str> {
+    let inner = s.strip_prefix(open)?.strip_suffix(close)?;
+    let mut depth = 0;
+    for c in inner.chars() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth -= 1;
+            if depth < 0 {
+                return None;
+            }
+        }
+    }
+    Some(inner)
+}
+
+/// Split `s` on `sep` wherever it isn't nested inside brackets.
+fn split_top_level(s: &str, sep: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    let mut prev = ' ';
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' | '[' | '<' => depth += 1,
+            ')' | ']' => depth -= 1,
+            // `->` in a function type doesn't close anything
+            '>' if prev != '-' => depth -= 1,
+            _ if c == sep && depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+        prev = c;
+    }
+    parts.push(&s[start..]);
+    parts
 }
 
 /// Detailed information about a type.
@@ -225,7 +799,19 @@ pub struct TypeDetails {
     pub path: String,
     pub kind: TypeKind,
     pub visibility: Visibility,
+    /// Whether the type is defined in the reflected crate. Together with
+    /// `is_fundamental`, this decides which foreign traits the orphan rules
+    /// allow implementing for it.
+    pub is_local: bool,
+    /// Whether the type is `#[fundamental]` (like `Box` or `Pin`), making
+    /// e.g. `Box<Local>` count as local for the orphan rules
+    pub is_fundamental: bool,
     pub generics: Vec<GenericParam>,
+    /// Variance of each generic parameter, in the order of `generics`:
+    /// `covariant`, `contravariant`, `invariant` or `bivariant`. This is
+    /// the variance rustc infers, so it may change with the compiler
+    #[serde(default)]
+    pub variances: Vec<String>,
     pub where_clause: Option<String>,
     /// Doc comments
     pub docs: Option<String>,
@@ -235,8 +821,13 @@ pub struct TypeDetails {
     pub fields: Option<Vec<FieldInfo>>,
     /// For enums: variants
     pub variants: Option<Vec<EnumVariantInfo>>,
-    /// All trait implementations
+    /// Paths of the traits the crate implements for this type, including
+    /// blanket impls that apply to it
     pub trait_impls: Vec<String>,
+    /// Impls for the type behind a reference or single-argument wrapper,
+    /// such as `impl Trait for &Type` or `impl Trait for Box<Type>`
+    #[serde(default)]
+    pub indirect_trait_impls: Vec<IndirectTraitImpl>,
     /// Inherent methods
     pub inherent_methods: Vec<MethodSummary>,
     /// Layout information (if available)
@@ -244,6 +835,18 @@ pub struct TypeDetails {
     /// Original source code (if available)
     pub source: Option<String>,
     pub span: Option<SpanInfo>,
+    /// See [`ItemInfo::stable_id`]
+    #[serde(default)]
+    pub stable_id: String,
+}
+
+/// A trait impl whose self type wraps a type rather than being the type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndirectTraitImpl {
+    /// Path of the implemented trait
+    pub trait_path: String,
+    /// The impl's self type, e.g. `&Foo` or `std::boxed::Box<Foo>`
+    pub self_ty: String,
 }
 
 /// The kind of a type.
@@ -267,7 +870,7 @@ pub enum TypeKind {
 }
 
 /// A generic parameter (lifetime, type, or const).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GenericParam {
     pub name: String,
     pub kind: GenericParamKind,
@@ -285,6 +888,31 @@ pub enum GenericParamKind {
     Const { ty: String },
 }
 
+/// Whether `generics` has type or const parameters, which an item can't be
+/// named without. Lifetime parameters don't count, as they can be elided.
+pub fn is_generic(generics: &[GenericParam]) -> bool {
+    has_type_params(generics) || has_const_params(generics)
+}
+
+/// Whether `generics` has any type parameters.
+pub fn has_type_params(generics: &[GenericParam]) -> bool {
+    generics.iter().any(|g| g.kind == GenericParamKind::Type)
+}
+
+/// Whether `generics` has any lifetime parameters.
+pub fn has_lifetimes(generics: &[GenericParam]) -> bool {
+    generics
+        .iter()
+        .any(|g| g.kind == GenericParamKind::Lifetime)
+}
+
+/// Whether `generics` has any const parameters.
+pub fn has_const_params(generics: &[GenericParam]) -> bool {
+    generics
+        .iter()
+        .any(|g| matches!(g.kind, GenericParamKind::Const { .. }))
+}
+
 // ============================================================================
 // Field Information
 // ============================================================================
@@ -311,6 +939,10 @@ pub struct FieldInfo {
     /// Size in bytes (if layout is known)
     pub size: Option<usize>,
     pub span: Option<SpanInfo>,
+    /// Crate defining the field's type, if it is a named (ADT or foreign)
+    /// type. `None` for primitives, references, tuples, arrays and the like.
+    #[serde(default)]
+    pub defined_in_crate: Option<String>,
 }
 
 /// Information about an enum variant.
@@ -320,7 +952,8 @@ pub struct EnumVariantInfo {
     pub index: usize,
     /// Fields of this variant
     pub fields: Vec<FieldInfo>,
-    /// Discriminant value (if specified)
+    /// Discriminant value as a decimal integer, whether explicit (`A = 4`)
+    /// or implied by the previous variant
     pub discriminant: Option<String>,
     /// Doc comments
     pub docs: Option<String>,
@@ -329,6 +962,14 @@ pub struct EnumVariantInfo {
     pub span: Option<SpanInfo>,
 }
 
+impl EnumVariantInfo {
+    /// The discriminant as an integer, or `None` if it is unknown or
+    /// doesn't fit in an `i128` (a `u128` discriminant above `i128::MAX`).
+    pub fn discriminant_value(&self) -> Option<i128> {
+        self.discriminant.as_deref()?.trim().parse().ok()
+    }
+}
+
 // ============================================================================
 // Layout Information
 // ============================================================================
@@ -340,7 +981,7 @@ pub struct LayoutInfo {
     pub size: usize,
     /// Alignment in bytes
     pub align: usize,
-    /// Field offsets (for structs)
+    /// Field offsets (for structs and unions, where every field is at 0)
     pub field_offsets: Option<Vec<FieldLayoutInfo>>,
     /// Variant layouts (for enums)
     pub variants: Option<Vec<VariantLayoutInfo>>,
@@ -354,6 +995,32 @@ pub struct LayoutInfo {
     pub is_sync: bool,
 }
 
+impl LayoutInfo {
+    /// Fill in the `offset` and `size` of fields of this type from
+    /// `field_offsets`, matching them by index.
+    pub fn apply_to_fields(&self, fields: &mut [FieldInfo]) {
+        for field_layout in self.field_offsets.iter().flatten() {
+            if let Some(field) = fields.iter_mut().find(|f| f.index == field_layout.index) {
+                field.offset = Some(field_layout.offset);
+                field.size = Some(field_layout.size);
+            }
+        }
+    }
+}
+
+/// The layout of a type, or why it doesn't have a fixed one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum TypeLayout {
+    /// The layout of a sized type without type or const parameters
+    Computed(LayoutInfo),
+    /// The type is dynamically sized (e.g. it ends in a slice), so its size
+    /// is only known at runtime
+    Unsized,
+    /// The layout depends on arguments for these type and const parameters
+    Generic { params: Vec<String> },
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FieldLayoutInfo {
     pub name: Option<String>,
@@ -378,8 +1045,18 @@ pub struct VariantLayoutInfo {
 pub struct TraitImplDetails {
     /// The implementing type
     pub self_ty: String,
-    /// The trait being implemented
+    /// Generic arguments of the implementing type (e.g. `["u8"]` for
+    /// `impl Serialize for Wrapper<u8>`, `["T"]` for `impl<T> Clone for
+    /// Wrapper<T>`)
+    #[serde(default)]
+    pub self_ty_args: Vec<String>,
+    /// The trait being implemented, with its generic arguments (e.g.
+    /// `std::convert::From<std::string::String>`)
     pub trait_path: String,
+    /// The trait's generic arguments, excluding `Self` (e.g.
+    /// `["std::string::String"]` for the impl above)
+    #[serde(default)]
+    pub trait_args: Vec<String>,
     /// Generic parameters on the impl
     pub generics: Vec<GenericParam>,
     /// Where clause
@@ -397,13 +1074,38 @@ pub struct TraitImplDetails {
     /// The full source code of the impl block
     pub source: Option<String>,
     pub span: Option<SpanInfo>,
+    /// Doc comments on the impl block
+    #[serde(default)]
+    pub docs: Option<String>,
+    /// Attributes on the impl block, e.g. `#[automatically_derived]` on
+    /// impls generated by `#[derive]`
+    #[serde(default)]
+    pub attributes: Vec<String>,
+}
+
+impl TraitImplDetails {
+    /// Whether the implementing type is instantiated with exactly `args`.
+    /// Arguments compare with `std`, `core` and `alloc` module paths optional,
+    /// so `String` matches `std::string::String`.
+    pub fn self_ty_args_match(&self, args: &[&str]) -> bool {
+        self.self_ty_args.len() == args.len()
+            && self
+                .self_ty_args
+                .iter()
+                .zip(args)
+                .all(|(arg, query)| strip_std_paths(arg) == strip_std_paths(query))
+    }
 }
 
 /// Detailed information about an inherent impl block.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InherentImplDetails {
-    /// The type this impl is for
+    /// The type this impl is for, as its base path without generic
+    /// arguments (e.g. `Baz` for `impl<T> Baz<T>`)
     pub self_ty: String,
+    /// Generic arguments of the self type (e.g. `["T"]` for
+    /// `impl<T> Baz<T>`, `["u32"]` for `impl Baz<u32>`)
+    pub self_ty_args: Vec<String>,
     /// Generic parameters
     pub generics: Vec<GenericParam>,
     /// Where clause
@@ -419,6 +1121,12 @@ pub struct InherentImplDetails {
     /// The full source code
     pub source: Option<String>,
     pub span: Option<SpanInfo>,
+    /// Doc comments on the impl block
+    #[serde(default)]
+    pub docs: Option<String>,
+    /// Attributes on the impl block
+    #[serde(default)]
+    pub attributes: Vec<String>,
 }
 
 /// Summary of a method (for listings).
@@ -427,9 +1135,14 @@ pub struct MethodSummary {
     pub name: String,
     pub path: String,
     pub signature: String,
+    pub visibility: Visibility,
+    /// Whether this takes a `self` receiver (false for associated functions)
+    pub is_method: bool,
     pub is_unsafe: bool,
     pub is_const: bool,
     pub is_async: bool,
+    #[serde(default)]
+    pub attributes: Vec<String>,
 }
 
 /// Detailed information about a method.
@@ -441,6 +1154,14 @@ pub struct MethodDetails {
     pub signature: String,
     /// Parsed signature components
     pub parsed_signature: FunctionSignature,
+    /// Visibility of the method
+    pub visibility: Visibility,
+    /// Whether this takes a `self` receiver (false for associated functions
+    /// like `User::new`)
+    pub is_method: bool,
+    /// Whether this trait impl method replaces a default implementation
+    /// provided by the trait (false for required and inherent methods)
+    pub overrides_default: bool,
     /// Whether this has a default implementation
     pub has_body: bool,
     /// Method body source code (if available)
@@ -460,6 +1181,99 @@ pub struct MethodDetails {
     pub span: Option<SpanInfo>,
 }
 
+/// Detailed information about a free (non-associated) function.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionDetails {
+    pub name: String,
+    pub path: String,
+    /// Full signature as a string
+    pub signature: String,
+    /// Parsed signature components
+    pub parsed_signature: FunctionSignature,
+    pub visibility: Visibility,
+    /// Function body source code (if available)
+    pub body_source: Option<String>,
+    /// Body as tokens (simplified AST)
+    pub body_tokens: Option<Vec<Token>>,
+    pub is_unsafe: bool,
+    pub is_const: bool,
+    pub is_async: bool,
+    /// Doc comments
+    pub docs: Option<String>,
+    /// Attributes
+    pub attributes: Vec<String>,
+    pub span: Option<SpanInfo>,
+}
+
+/// Detailed information about a top-level `const` or `static` item.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConstDetails {
+    pub name: String,
+    pub path: String,
+    /// Whether the item is a `static` (or `static mut`) rather than a `const`
+    #[serde(default)]
+    pub is_static: bool,
+    /// The declared type, e.g. `[u8; 4]`
+    pub ty: String,
+    /// The evaluated value, e.g. `4` or `"v1"`. `None` for statics, which
+    /// aren't evaluated, and for constants that couldn't be evaluated.
+    pub value: Option<String>,
+    /// The initializer expression as written, e.g. `2 * 2` for
+    /// `const MAX: u8 = 2 * 2;`
+    #[serde(default)]
+    pub init_source: Option<String>,
+    /// Doc comments
+    pub docs: Option<String>,
+    pub visibility: Visibility,
+    /// The full source of the item, e.g. `pub const MAX: u8 = 2 * 2;`
+    pub source: Option<String>,
+    pub span: Option<SpanInfo>,
+}
+
+/// Detailed information about a macro defined in the crate.
+///
+/// Covers `macro_rules!` macros and, in proc-macro crates, the functions
+/// marked `#[proc_macro]`, `#[proc_macro_derive]` or `#[proc_macro_attribute]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MacroDetails {
+    /// The name the macro is invoked by. For derive macros this is the name
+    /// given in `#[proc_macro_derive(...)]`, not the function name.
+    pub name: String,
+    pub path: String,
+    pub macro_kind: MacroKind,
+    /// For derive macros, the trait being derived
+    pub derive_trait: Option<String>,
+    /// For derive macros, the helper attributes they accept
+    pub helper_attributes: Vec<String>,
+    pub visibility: Visibility,
+    /// Doc comments
+    pub docs: Option<String>,
+    /// The macro's source: the whole `macro_rules!` definition, or the
+    /// implementing function of a procedural macro
+    pub source: Option<String>,
+    pub span: Option<SpanInfo>,
+}
+
+/// How a macro is invoked.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MacroKind {
+    /// `name!(...)`
+    Bang,
+    /// `#[derive(Name)]`
+    Derive,
+    /// `#[name]`
+    Attribute,
+}
+
+/// A method that a method call on a type may resolve to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MethodCandidate {
+    /// The trait whose impl provides the method (None for inherent methods)
+    pub trait_path: Option<String>,
+    pub method: MethodDetails,
+}
+
 /// Parsed function signature.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionSignature {
@@ -467,18 +1281,34 @@ pub struct FunctionSignature {
     pub receiver: Option<ReceiverInfo>,
     /// Parameters (excluding self)
     pub params: Vec<ParamInfo>,
-    /// Return type
-    pub return_ty: Option<String>,
+    /// Return type, `None` if it isn't known
+    pub return_ty: Option<ReturnType>,
     /// Generic parameters
     pub generics: Vec<GenericParam>,
     /// Where clause
     pub where_clause: Option<String>,
+    /// Predicates of the function's own `where` clause as written, without
+    /// those inherited from the enclosing trait or impl
+    #[serde(default)]
+    pub where_predicates: Vec<WherePredicate>,
+}
+
+/// A single `where` clause predicate, such as `Self: Sized` or `'a: 'b`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WherePredicate {
+    /// The constrained type or lifetime (`Self`, `T::Item`, `'a`)
+    pub bounded_ty: String,
+    /// Its bounds as written (`Sized`, `Iterator<Item = u8>`, `'static`)
+    pub bounds: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReceiverInfo {
     /// "self", "&self", "&mut self", "self: Pin<&mut Self>", etc.
     pub kind: String,
+    /// The receiver's shape, for matching on rather than parsing `kind`
+    #[serde(default)]
+    pub receiver_kind: ReceiverKind,
     /// Whether it's mutable
     pub is_mut: bool,
     /// Whether it's a reference
@@ -487,6 +1317,108 @@ pub struct ReceiverInfo {
     pub lifetime: Option<String>,
 }
 
+/// What a function returns.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReturnType {
+    /// No return type, or `-> ()`
+    Unit,
+    /// Any other type, e.g. `Option<u8>` or `Self`
+    Type(String),
+    /// `-> !`
+    Never,
+    /// `-> impl Iterator<Item = u8> + Send`, with one entry per bound
+    ImplTrait(Vec<String>),
+}
+
+impl ReturnType {
+    /// The returned type, if it's a nameable type other than `()`.
+    pub fn ty(&self) -> Option<&str> {
+        match self {
+            ReturnType::Type(ty) => Some(ty),
+            _ => None,
+        }
+    }
+
+    pub fn is_unit(&self) -> bool {
+        matches!(self, ReturnType::Unit)
+    }
+}
+
+/// How a method takes `self`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiverKind {
+    /// `self`
+    #[default]
+    Owned,
+    /// `&self`
+    Ref,
+    /// `&mut self`
+    RefMut,
+    /// `self: Box<Self>`
+    Box,
+    /// `self: Rc<Self>`
+    Rc,
+    /// `self: Arc<Self>`
+    Arc,
+    /// `self: Pin<P>`, with the kind of the pinned pointer `P` (e.g.
+    /// `Pin(RefMut)` for `self: Pin<&mut Self>`)
+    Pin(Box<ReceiverKind>),
+}
+
+/// What a method is for, guessed from its name and signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MethodRole {
+    /// `fn name(&self) -> T`
+    Getter,
+    /// `fn set_name(&mut self, value: T)`
+    Setter,
+    /// `fn new(..) -> Self`, without a receiver
+    Constructor,
+    Other,
+}
+
+impl MethodRole {
+    /// Classify the method at `path` (e.g. `User::set_name`) by its
+    /// signature. Constructors must return `Self` itself, so `Option<Self>`
+    /// or `Result<Self, E>` doesn't count.
+    pub fn classify(path: &str, signature: &FunctionSignature) -> Self {
+        let (owner, name) = path.rsplit_once("::").unwrap_or(("", path));
+        let receiver = signature.receiver.as_ref().map(|r| &r.receiver_kind);
+
+        let ret = signature.return_ty.as_ref();
+        match (receiver, signature.params.len(), ret) {
+            (Some(ReceiverKind::Ref), 0, Some(ret)) if !ret.is_unit() => MethodRole::Getter,
+            (Some(ReceiverKind::RefMut), 1, Some(ReturnType::Unit)) if name.starts_with("set_") => {
+                MethodRole::Setter
+            }
+            (None, _, Some(ReturnType::Type(ret)))
+                if ret == "Self" || strip_generics(ret) == self_type(owner) =>
+            {
+                MethodRole::Constructor
+            }
+            _ => MethodRole::Other,
+        }
+    }
+}
+
+/// The implementing type of a method path's owner: `Pair` for `Pair::<T>`
+/// and `Leaf` for `<Leaf as Default>`.
+fn self_type(owner: &str) -> &str {
+    let owner = match owner.strip_prefix('<').and_then(|o| o.split_once(" as ")) {
+        Some((self_ty, _)) => self_ty,
+        None => owner,
+    };
+    strip_generics(owner).trim_end_matches("::")
+}
+
+/// A type path without its generic arguments.
+fn strip_generics(ty: &str) -> &str {
+    ty.split('<').next().unwrap_or(ty)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParamInfo {
     pub name: String,
@@ -513,8 +1445,14 @@ pub struct AssocTypeInfo {
 pub struct AssocConstInfo {
     pub name: String,
     pub ty: String,
-    /// The value (if specified)
+    /// The evaluated value, for constants of impls whose value doesn't
+    /// depend on generic parameters (e.g. `3` for `const VERSION: u32 = 3;`)
     pub value: Option<String>,
+    /// The initializer expression as written (e.g. `4 * 1024` for
+    /// `const SIZE: usize = 4 * 1024;`), also for values that can't be
+    /// evaluated. `None` for a trait constant without a default
+    #[serde(default)]
+    pub init_source: Option<String>,
     pub docs: Option<String>,
     pub span: Option<SpanInfo>,
 }
@@ -551,6 +1489,12 @@ pub struct TraitDetails {
     pub is_unsafe: bool,
     /// Supertraits
     pub supertraits: Vec<String>,
+    /// Transitive closure of the supertraits, including those of foreign
+    /// supertraits (e.g. `Clone` for `trait Foo: Copy`)
+    pub all_supertraits: Vec<String>,
+    /// Best-effort guess at whether the trait is sealed: true when some
+    /// supertrait is not nameable outside its crate
+    pub is_sealed: bool,
     /// Methods defined in this trait
     pub methods: Vec<TraitMethodInfo>,
     /// Associated types
@@ -578,7 +1522,19 @@ pub struct TraitMethodInfo {
     pub has_default: bool,
     /// Default implementation source
     pub default_body: Option<String>,
+    /// Default implementation as tokens (simplified AST)
+    pub default_body_tokens: Option<Vec<Token>>,
     pub is_unsafe: bool,
+    /// Whether this is a `const fn`
+    pub is_const: bool,
+    /// Whether this is an `async fn`
+    pub is_async: bool,
+    /// Whether this method can be called through a `dyn Trait` object.
+    /// False for methods bounded by `Self: Sized` and for methods that are
+    /// generic, take `self` by an unsupported receiver, or mention `Self`
+    /// outside the receiver.
+    #[serde(default)]
+    pub is_dyn_compatible: bool,
     pub docs: Option<String>,
     pub attributes: Vec<String>,
     pub span: Option<SpanInfo>,
@@ -695,6 +1651,12 @@ pub struct CrateTypeInfo {
     pub crate_name: String,
     /// Crate version (if known)
     pub crate_version: Option<String>,
+    /// Edition the crate is compiled with, e.g. `2021`
+    #[serde(default)]
+    pub edition: String,
+    /// Version of the rustc that extracted this, e.g. `1.91.0-nightly (05f5a58e8 2025-08-19)`
+    #[serde(default)]
+    pub rustc_version: String,
 
     /// All items in the crate
     pub items: Vec<ItemInfo>,
@@ -711,14 +1673,272 @@ pub struct CrateTypeInfo {
     /// Inherent impls (keyed by type)
     pub inherent_impls: HashMap<String, Vec<InherentImplDetails>>,
 
+    /// Free functions (keyed by path)
+    #[serde(default)]
+    pub functions: HashMap<String, FunctionDetails>,
+
+    /// Macros (keyed by path)
+    #[serde(default)]
+    pub macros: HashMap<String, MacroDetails>,
+
+    /// Top-level constants (keyed by path)
+    #[serde(default)]
+    pub consts: HashMap<String, ConstDetails>,
+
     /// Type aliases (path -> resolved type)
     pub type_aliases: HashMap<String, TypeAliasInfo>,
 
+    /// Trait aliases (keyed by path)
+    #[serde(default)]
+    pub trait_aliases: HashMap<String, TraitAliasInfo>,
+
     /// Layout information (keyed by type path)
     pub layouts: HashMap<String, LayoutInfo>,
 
     /// Module tree for path matching
     pub modules: HashMap<String, ModuleInfo>,
+
+    /// Whether method bodies (`body_source`, `body_tokens`, default trait
+    /// method bodies) were extracted
+    #[serde(default)]
+    pub bodies_included: bool,
+
+    /// Items that could not be extracted (e.g. because rustc panicked on
+    /// them); if non-empty, the maps above are incomplete
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+impl CrateTypeInfo {
+    /// Find the types whose full path matches `regex`, sorted by path.
+    ///
+    /// Returns the compile error if `regex` is invalid.
+    #[cfg(feature = "regex")]
+    pub fn find_types_regex(&self, regex: &str) -> Result<Vec<TypeSummary>, String> {
+        let regex = regex::Regex::new(regex).map_err(|e| e.to_string())?;
+        let mut types: Vec<TypeSummary> = self
+            .types
+            .values()
+            .filter(|t| regex.is_match(&t.path))
+            .map(|t| TypeSummary {
+                name: t.name.clone(),
+                path: t.path.clone(),
+                kind: t.kind.clone(),
+                generics: t.generics.clone(),
+                crate_name: Some(self.crate_name.clone()),
+            })
+            .collect();
+        sort_by_path(&mut types, |t| &t.path);
+        Ok(types)
+    }
+
+    /// Find the impl methods whose path matches `pattern`, sorted by path.
+    ///
+    /// Inherent methods are always searched; trait impl methods only with
+    /// `include_trait_impls`.
+    pub fn find_methods(&self, pattern: &str, include_trait_impls: bool) -> Vec<MethodSummary> {
+        let inherent = self.inherent_impls.values().flatten().map(|i| &i.methods);
+        let trait_impls = self
+            .trait_impls
+            .values()
+            .flatten()
+            .filter(|_| include_trait_impls)
+            .map(|i| &i.methods);
+
+        let mut methods: Vec<MethodSummary> = inherent
+            .chain(trait_impls)
+            .flatten()
+            .filter(|m| path_matches_pattern(&m.path, pattern))
+            .map(|m| MethodSummary {
+                name: m.name.clone(),
+                path: m.path.clone(),
+                signature: m.signature.clone(),
+                visibility: m.visibility.clone(),
+                is_method: m.is_method,
+                is_unsafe: m.is_unsafe,
+                is_const: m.is_const,
+                is_async: m.is_async,
+                attributes: m.attributes.clone(),
+            })
+            .collect();
+        sort_by_path(&mut methods, |m| &m.path);
+        methods
+    }
+
+    /// The types with a field whose (alias-resolved) type mentions
+    /// `type_path`, sorted by path.
+    pub fn types_using_type(&self, type_path: &str) -> Vec<TypeUsage> {
+        let uses_type = |f: &FieldInfo| {
+            let ty = f.resolved_ty.as_deref().unwrap_or(&f.ty);
+            ty.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+                .filter(|path| !path.is_empty())
+                .any(|path| type_path_matches(path, type_path))
+        };
+        let field_name = |f: &FieldInfo| f.name.clone().unwrap_or_else(|| f.index.to_string());
+
+        let mut usages: Vec<TypeUsage> = self
+            .types
+            .values()
+            .filter_map(|t| {
+                let mut fields: Vec<String> = t
+                    .fields
+                    .iter()
+                    .flatten()
+                    .filter(|f| uses_type(f))
+                    .map(field_name)
+                    .collect();
+                for variant in t.variants.iter().flatten() {
+                    fields.extend(
+                        variant
+                            .fields
+                            .iter()
+                            .filter(|f| uses_type(f))
+                            .map(|f| format!("{}.{}", variant.name, field_name(f))),
+                    );
+                }
+                (!fields.is_empty()).then(|| TypeUsage {
+                    owner: TypeSummary {
+                        name: t.name.clone(),
+                        path: t.path.clone(),
+                        kind: t.kind.clone(),
+                        generics: t.generics.clone(),
+                        crate_name: Some(self.crate_name.clone()),
+                    },
+                    fields,
+                })
+            })
+            .collect();
+        sort_by_path(&mut usages, |u| &u.owner.path);
+        usages
+    }
+
+    /// The field `field` of variant `variant` of the enum at `enum_path`
+    /// (or ending in `::enum_path`). Tuple variant fields are named by their
+    /// index.
+    pub fn variant_field(
+        &self,
+        enum_path: &str,
+        variant: &str,
+        field: &str,
+    ) -> Result<&FieldInfo, String> {
+        let details = self
+            .types
+            .get(enum_path)
+            .or_else(|| {
+                self.types
+                    .values()
+                    .find(|t| t.path.ends_with(&format!("::{}", enum_path)))
+            })
+            .filter(|t| t.kind == TypeKind::Enum)
+            .ok_or_else(|| format!("Enum '{}' not found", enum_path))?;
+        let variant_info = details
+            .variants
+            .iter()
+            .flatten()
+            .find(|v| v.name == variant)
+            .ok_or_else(|| format!("Enum '{}' has no variant '{}'", details.path, variant))?;
+        variant_info
+            .fields
+            .iter()
+            .find(|f| match &f.name {
+                Some(name) => name == field,
+                None => f.index.to_string() == field,
+            })
+            .ok_or_else(|| {
+                format!(
+                    "Variant '{}::{}' has no field '{}'",
+                    details.path, variant, field
+                )
+            })
+    }
+
+    /// The layout of the type at `path`, or why it has none. `None` if the
+    /// type isn't known or its layout couldn't be computed.
+    pub fn type_layout(&self, path: &str) -> Option<TypeLayout> {
+        if let Some(layout) = self.layouts.get(path) {
+            return Some(if layout.is_sized {
+                TypeLayout::Computed(layout.clone())
+            } else {
+                TypeLayout::Unsized
+            });
+        }
+
+        let params: Vec<String> = self
+            .types
+            .get(path)?
+            .generics
+            .iter()
+            .filter(|g| !matches!(g.kind, GenericParamKind::Lifetime))
+            .map(|g| g.name.clone())
+            .collect();
+        (!params.is_empty()).then_some(TypeLayout::Generic { params })
+    }
+
+    /// Describe the structure of a type string, with a summary attached to
+    /// every named type defined in this crate.
+    pub fn describe_type(&self, ty: &str) -> TypeStructure {
+        let mut structure = TypeStructure::parse(ty);
+        structure.attach_summaries(&|path| {
+            let details = self.types.get(path).or_else(|| {
+                self.types
+                    .values()
+                    .find(|t| t.path.ends_with(&format!("::{}", path)))
+            })?;
+            Some(TypeSummary {
+                name: details.name.clone(),
+                path: details.path.clone(),
+                kind: details.kind.clone(),
+                generics: details.generics.clone(),
+                crate_name: Some(self.crate_name.clone()),
+            })
+        });
+        structure
+    }
+
+    /// Drop everything that isn't `pub`, leaving only the public API surface.
+    ///
+    /// Non-public items, fields and inherent methods are removed, along with
+    /// the impls and layouts of the removed types. Trait impl methods are
+    /// kept, as they are as visible as the trait and type they belong to.
+    pub fn retain_public(&mut self) {
+        let is_public = |visibility: &Visibility| *visibility == Visibility::Public;
+
+        let private_types: Vec<String> = self
+            .types
+            .iter()
+            .filter(|(_, t)| !is_public(&t.visibility))
+            .map(|(path, _)| path.clone())
+            .collect();
+        for path in &private_types {
+            self.types.remove(path);
+            self.layouts.remove(path);
+            self.trait_impls.remove(path);
+            self.inherent_impls.remove(path);
+        }
+
+        self.items.retain(|item| is_public(&item.visibility));
+        self.traits.retain(|_, t| is_public(&t.visibility));
+        self.functions.retain(|_, f| is_public(&f.visibility));
+        self.macros.retain(|_, m| is_public(&m.visibility));
+        self.consts.retain(|_, c| is_public(&c.visibility));
+        self.type_aliases.retain(|_, a| is_public(&a.visibility));
+        self.trait_aliases.retain(|_, a| is_public(&a.visibility));
+        self.modules.retain(|_, m| is_public(&m.visibility));
+
+        for details in self.types.values_mut() {
+            if let Some(fields) = details.fields.as_mut() {
+                fields.retain(|f| is_public(&f.visibility));
+            }
+            details
+                .inherent_methods
+                .retain(|m| is_public(&m.visibility));
+        }
+        for impls in self.inherent_impls.values_mut() {
+            for impl_ in impls {
+                impl_.methods.retain(|m| is_public(&m.visibility));
+            }
+        }
+    }
 }
 
 /// Information about a type alias.
@@ -731,11 +1951,28 @@ pub struct TypeAliasInfo {
     pub ty: String,
     /// Fully resolved type (following all aliases)
     pub resolved_ty: String,
+    /// Every hop of the resolution: this alias's path, the path of each
+    /// alias it is defined in terms of, then the fully expanded type
+    #[serde(default)]
+    pub chain: Vec<String>,
     pub visibility: Visibility,
     pub docs: Option<String>,
     pub span: Option<SpanInfo>,
 }
 
+/// Information about a trait alias.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraitAliasInfo {
+    pub name: String,
+    pub path: String,
+    /// The traits (with their generic arguments) and lifetimes the alias
+    /// stands for, e.g. `["Area", "std::fmt::Debug"]`
+    pub bounds: Vec<String>,
+    pub generics: Vec<GenericParam>,
+    pub visibility: Visibility,
+    pub docs: Option<String>,
+}
+
 /// Information about a module.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModuleInfo {
@@ -748,6 +1985,66 @@ pub struct ModuleInfo {
     pub reexports: Vec<ReexportInfo>,
 }
 
+/// A module with its submodules and the items defined directly in it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleTree {
+    pub name: String,
+    /// Path of the module; empty for the crate root
+    pub path: String,
+    pub submodules: Vec<ModuleTree>,
+    /// Items defined in this module, excluding submodules, impls and `use`s
+    pub items: Vec<ItemInfo>,
+}
+
+impl ModuleTree {
+    /// Assemble the tree rooted at the crate root from the flat module map
+    /// and item index of a `CrateTypeInfo`.
+    ///
+    /// Submodules and items are sorted by path.
+    pub fn build(
+        crate_name: &str,
+        modules: &HashMap<String, ModuleInfo>,
+        items: &[ItemInfo],
+    ) -> Self {
+        fn parent(path: &str) -> &str {
+            path.rsplit_once("::").map_or("", |(parent, _)| parent)
+        }
+
+        fn subtree(
+            name: &str,
+            path: &str,
+            modules: &HashMap<String, ModuleInfo>,
+            items: &[ItemInfo],
+        ) -> ModuleTree {
+            let mut submodules: Vec<ModuleTree> = modules
+                .values()
+                .filter(|module| parent(&module.path) == path)
+                .map(|module| subtree(&module.name, &module.path, modules, items))
+                .collect();
+            sort_by_path(&mut submodules, |module| &module.path);
+
+            let mut own_items: Vec<ItemInfo> = items
+                .iter()
+                .filter(|item| {
+                    !matches!(item.kind, ItemKind::Mod | ItemKind::Impl | ItemKind::Use)
+                        && parent(&item.path) == path
+                })
+                .cloned()
+                .collect();
+            sort_by_path(&mut own_items, |item| &item.path);
+
+            ModuleTree {
+                name: name.to_string(),
+                path: path.to_string(),
+                submodules,
+                items: own_items,
+            }
+        }
+
+        subtree(crate_name, "", modules, items)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReexportInfo {
     /// The name as exported
@@ -761,16 +2058,37 @@ pub struct ReexportInfo {
 // Utility Functions
 // ============================================================================
 
-/// Default socket path for the Bronzite daemon.
+/// Environment variable that overrides the daemon socket path.
+///
+/// The client and the daemon both resolve their socket through
+/// [`default_socket_path`] or [`socket_path_for_workspace`], so setting it
+/// for a whole job keeps them pointed at the same, private daemon.
+pub const SOCKET_ENV_VAR: &str = "BRONZITE_SOCKET";
+
+/// The socket path set through [`SOCKET_ENV_VAR`], if any.
+fn socket_path_override() -> Option<std::path::PathBuf> {
+    std::env::var_os(SOCKET_ENV_VAR)
+        .filter(|path| !path.is_empty())
+        .map(std::path::PathBuf::from)
+}
+
+/// Default socket path for the Bronzite daemon: `$BRONZITE_SOCKET` if set,
+/// otherwise `bronzite.sock` in the temp directory.
 pub fn default_socket_path() -> std::path::PathBuf {
-    std::env::temp_dir().join("bronzite.sock")
+    socket_path_override().unwrap_or_else(|| std::env::temp_dir().join("bronzite.sock"))
 }
 
 /// Socket path for a specific crate/workspace.
+///
+/// `$BRONZITE_SOCKET` takes precedence over the per-workspace path.
 pub fn socket_path_for_workspace(workspace_root: &std::path::Path) -> std::path::PathBuf {
     use std::collections::hash_map::DefaultHasher;
     use std::hash::{Hash, Hasher};
 
+    if let Some(path) = socket_path_override() {
+        return path;
+    }
+
     let mut hasher = DefaultHasher::new();
     workspace_root.hash(&mut hasher);
     let hash = hasher.finish();
@@ -778,6 +2096,103 @@ pub fn socket_path_for_workspace(workspace_root: &std::path::Path) -> std::path:
     std::env::temp_dir().join(format!("bronzite-{:x}.sock", hash))
 }
 
+/// The name rustc gives a crate: cargo package names may use `-`, but the
+/// compiled crate always uses `_` (`my-types` becomes `my_types`).
+pub fn normalize_crate_name(name: &str) -> String {
+    name.replace('-', "_")
+}
+
+/// Sort listing results by path.
+///
+/// Details are stored in hash maps, so without this the same query could
+/// return its results in a different order on every run.
+pub fn sort_by_path<T>(items: &mut [T], path: impl Fn(&T) -> &str) {
+    items.sort_by(|a, b| path(a).cmp(path(b)));
+}
+
+/// Clean up constant arguments in a type rendered by the compiler.
+///
+/// Integer constants lose their type suffix and const parameters lose their
+/// index, so `[u8; 32_usize]` becomes `[u8; 32]` and `[u8; N/#0]` becomes
+/// `[u8; N]`.
+pub fn tidy_const_args(ty: &str) -> String {
+    const INT_SUFFIXES: [&str; 12] = [
+        "_usize", "_isize", "_u128", "_i128", "_u64", "_i64", "_u32", "_i32", "_u16", "_i16",
+        "_u8", "_i8",
+    ];
+    let is_ident_char = |c: char| c.is_alphanumeric() || c == '_';
+
+    let mut out = String::with_capacity(ty.len());
+    let mut rest = ty;
+    while let Some(c) = rest.chars().next() {
+        if c.is_ascii_digit() && !out.ends_with(is_ident_char) {
+            let end = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            out.push_str(&rest[..end]);
+            rest = &rest[end..];
+            if let Some(suffix) = INT_SUFFIXES
+                .iter()
+                .find(|s| rest.starts_with(**s) && !rest[s.len()..].starts_with(is_ident_char))
+            {
+                rest = &rest[suffix.len()..];
+            }
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix("/#") {
+            let end = after
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(after.len());
+            if end > 0 {
+                rest = &after[end..];
+                continue;
+            }
+        }
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+/// Render a trait bound with its associated type bindings folded into the
+/// trait's arguments, so `std::iter::Iterator` bound with `Item = u8`
+/// becomes `std::iter::Iterator<Item = u8>`.
+pub fn render_trait_bound(trait_path: &str, bindings: &[String]) -> String {
+    let bound = if bindings.is_empty() {
+        trait_path.to_string()
+    } else if let Some(path) = trait_path.strip_suffix('>') {
+        format!("{}, {}>", path, bindings.join(", "))
+    } else {
+        format!("{}<{}>", trait_path, bindings.join(", "))
+    };
+    tidy_const_args(&bound)
+}
+
+/// Spell out the `impl Trait` arguments in a rendered parameter type.
+///
+/// rustc turns each `impl Trait` argument into a generic parameter named
+/// after its source text (`impl Iterator<Item = u8>`). `impl_traits` pairs
+/// those names with the rendered bounds of each, which replace them in
+/// `ty`. An argument bounded only by the implicit `Sized` reads
+/// `impl std::marker::Sized`.
+pub fn render_impl_trait_args(ty: &str, impl_traits: &[(String, Vec<String>)]) -> String {
+    // Longer names go first, so a name that contains another isn't
+    // clobbered by it
+    let mut impl_traits: Vec<&(String, Vec<String>)> = impl_traits.iter().collect();
+    impl_traits.sort_by_key(|(name, _)| std::cmp::Reverse(name.len()));
+
+    let mut ty = ty.to_string();
+    for (name, bounds) in impl_traits {
+        let bounds = if bounds.is_empty() {
+            "std::marker::Sized".to_string()
+        } else {
+            bounds.join(" + ")
+        };
+        ty = ty.replace(name.as_str(), &format!("impl {}", bounds));
+    }
+    ty
+}
+
 // ============================================================================
 // Pattern Matching for FindTypes
 // ============================================================================
@@ -787,8 +2202,66 @@ pub fn socket_path_for_workspace(workspace_root: &std::path::Path) -> std::path:
 /// - Exact match: "foo::Bar"
 /// - Glob suffix: "foo::*"
 /// - Recursive glob: "foo::**"
-/// - Wildcards: "foo::Bar*"
+/// - Wildcards, any number of them: "foo::Bar*", "*Event", "foo::Get*Request"
 pub fn path_matches_pattern(path: &str, pattern: &str) -> bool {
+    path_matches_pattern_with(path, pattern, &MatchOptions::default())
+}
+
+/// Check that a pattern is one [`path_matches_pattern`] understands, so a
+/// malformed pattern can be reported instead of silently matching nothing.
+///
+/// Returns why the pattern is unsupported.
+pub fn validate_pattern(pattern: &str) -> Result<(), String> {
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return Ok(());
+    }
+
+    let segments: Vec<&str> = pattern.split("::").collect();
+    for (i, segment) in segments.iter().enumerate() {
+        if segment.is_empty() {
+            return Err("empty path segment".to_string());
+        }
+        if segment.contains(':') {
+            return Err(format!("stray ':' in `{}`, paths use `::`", segment));
+        }
+        if segment.contains("**") && (*segment != "**" || i + 1 != segments.len()) {
+            return Err("`**` is only supported as the last segment, as in `foo::**`".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Check that `regex` compiles, as [`CrateTypeInfo::find_types_regex`]
+/// needs it to.
+///
+/// Returns the compile error.
+#[cfg(feature = "regex")]
+pub fn validate_regex(regex: &str) -> Result<(), String> {
+    regex::Regex::new(regex)
+        .map(drop)
+        .map_err(|e| e.to_string())
+}
+
+/// Options that relax how [`path_matches_pattern_with`] compares paths.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchOptions {
+    /// Ignore case, so `user` matches `User`
+    #[serde(default)]
+    pub case_insensitive: bool,
+}
+
+/// Check if a path matches a pattern, as [`path_matches_pattern`] does, with
+/// the given options.
+pub fn path_matches_pattern_with(path: &str, pattern: &str, options: &MatchOptions) -> bool {
+    if options.case_insensitive {
+        let (path, pattern) = (path.to_lowercase(), pattern.to_lowercase());
+        return path_matches_pattern_exact_case(&path, &pattern);
+    }
+    path_matches_pattern_exact_case(path, pattern)
+}
+
+fn path_matches_pattern_exact_case(path: &str, pattern: &str) -> bool {
     let pattern = pattern.trim();
     let path = path.trim();
 
@@ -808,22 +2281,332 @@ pub fn path_matches_pattern(path: &str, pattern: &str) -> bool {
         return !suffix.contains("::");
     }
 
-    // Wildcard in name: foo::Bar* matches foo::BarBaz
+    // Wildcards: foo::Bar* matches foo::BarBaz, foo::Get*Request matches
+    // foo::GetUserRequest
     if pattern.contains('*') {
-        let parts: Vec<&str> = pattern.split('*').collect();
-        if parts.len() != 2 {
-            return false; // Only support single wildcard for now
-        }
-        return path.starts_with(parts[0]) && path.ends_with(parts[1]);
+        return glob_matches(path, pattern);
     }
 
     // Exact match
     path == pattern
 }
 
+/// Match `text` against a pattern in which each `*` stands for any run of
+/// characters.
+fn glob_matches(text: &str, pattern: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let Some(mut rest) = text.strip_prefix(parts.next().unwrap_or("")) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+
+    // Taking the leftmost occurrence of each middle part leaves the most
+    // room for the parts after it
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// Check if an impl's trait path matches a queried trait path.
+///
+/// Impl trait paths carry their generic arguments, fully qualified (e.g.
+/// `std::convert::From<std::string::String>`). A query without arguments
+/// matches the trait by exact or `::` suffix path; a query with arguments
+/// must match them too, with `std`, `core` and `alloc` module paths optional
+/// (`From<String>`). Other module paths must be spelled out.
+pub fn trait_path_matches(impl_trait_path: &str, query: &str) -> bool {
+    let query = query.trim();
+    let is_suffix = |path: &str| path == query || path.ends_with(&format!("::{}", query));
+
+    if query.contains('<') {
+        is_suffix(impl_trait_path) || strip_std_paths(impl_trait_path) == strip_std_paths(query)
+    } else {
+        is_suffix(impl_trait_path.split('<').next().unwrap_or(impl_trait_path))
+    }
+}
+
+/// Check if a type path used as a cache key (e.g. the self type of an impl)
+/// matches a queried type path.
+///
+/// Generic arguments are ignored on both sides, so `Baz`, `Baz<i32>` and
+/// `example::Baz<T>` all name the same type. Otherwise the query matches by
+/// exact or `::` suffix path.
+pub fn type_path_matches(key: &str, query: &str) -> bool {
+    let base = |path: &str| path.split('<').next().unwrap_or(path).trim().to_string();
+    let (key, query) = (base(key), base(query));
+    key == query || key.ends_with(&format!("::{}", query))
+}
+
+/// Drop the module part of every `std`, `core` or `alloc` path in a type
+/// string and remove whitespace, e.g. `std::convert::From<std::string::String>`
+/// becomes `From<String>`. Other paths are kept whole, so a crate's own
+/// `foo::From` isn't mistaken for the standard library trait.
+fn strip_std_paths(path: &str) -> String {
+    let is_path_char = |c: char| c.is_alphanumeric() || c == '_' || c == ':';
+    let mut out = String::with_capacity(path.len());
+    let mut rest = path;
+
+    while let Some(start) = rest.find(|c: char| c.is_alphanumeric() || c == '_') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest.find(|c| !is_path_char(c)).unwrap_or(rest.len());
+        let segment = &rest[..end];
+        match segment.split_once("::") {
+            Some(("std" | "core" | "alloc", _)) => {
+                out.push_str(segment.rsplit("::").next().unwrap_or(segment));
+            }
+            _ => out.push_str(segment),
+        }
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    out.retain(|c| !c.is_whitespace());
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fixtures::*;
+
+    /// Builders for the protocol types the tests construct, with every
+    /// field not named in the arguments left empty. Tests adjust the rest
+    /// with struct-update syntax, e.g. `MethodDetails { is_method: true,
+    /// ..method("User::age") }`.
+    mod fixtures {
+        use super::*;
+
+        fn last_segment(path: &str) -> String {
+            path.rsplit("::").next().unwrap().to_string()
+        }
+
+        /// A public, local type with no generics, fields or variants.
+        pub fn type_details(path: &str, kind: TypeKind) -> TypeDetails {
+            TypeDetails {
+                name: last_segment(path),
+                path: path.to_string(),
+                kind,
+                visibility: Visibility::Public,
+                is_local: true,
+                is_fundamental: false,
+                generics: Vec::new(),
+                variances: Vec::new(),
+                where_clause: None,
+                docs: None,
+                attributes: Vec::new(),
+                fields: None,
+                variants: None,
+                trait_impls: Vec::new(),
+                indirect_trait_impls: Vec::new(),
+                inherent_methods: Vec::new(),
+                layout: None,
+                source: None,
+                span: None,
+                stable_id: String::new(),
+            }
+        }
+
+        /// A local struct with these fields.
+        pub fn struct_details(path: &str, fields: Vec<FieldInfo>) -> TypeDetails {
+            TypeDetails {
+                fields: Some(fields),
+                ..type_details(path, TypeKind::Struct)
+            }
+        }
+
+        /// A local enum with these variants.
+        pub fn enum_details(path: &str, variants: Vec<EnumVariantInfo>) -> TypeDetails {
+            TypeDetails {
+                variants: Some(variants),
+                ..type_details(path, TypeKind::Enum)
+            }
+        }
+
+        /// A public field of type `ty`, resolved to itself; `name` is
+        /// `None` for tuple fields.
+        pub fn field(name: Option<&str>, index: usize, ty: &str) -> FieldInfo {
+            FieldInfo {
+                name: name.map(str::to_string),
+                index,
+                ty: ty.to_string(),
+                resolved_ty: Some(ty.to_string()),
+                visibility: Visibility::Public,
+                docs: None,
+                attributes: Vec::new(),
+                offset: None,
+                size: None,
+                span: None,
+                defined_in_crate: None,
+            }
+        }
+
+        pub fn variant(name: &str, index: usize, fields: Vec<FieldInfo>) -> EnumVariantInfo {
+            EnumVariantInfo {
+                name: name.to_string(),
+                index,
+                fields,
+                discriminant: None,
+                docs: None,
+                attributes: Vec::new(),
+                span: None,
+            }
+        }
+
+        /// A signature without receiver, params, generics or return type.
+        pub fn signature() -> FunctionSignature {
+            FunctionSignature {
+                receiver: None,
+                params: Vec::new(),
+                return_ty: None,
+                generics: Vec::new(),
+                where_clause: None,
+                where_predicates: Vec::new(),
+            }
+        }
+
+        /// A signature taking `receiver` (by reference, unless `Owned`)
+        /// and one unnamed parameter per type in `params`, returning `ret`
+        /// or `()`.
+        pub fn method_signature(
+            receiver: Option<ReceiverKind>,
+            params: &[&str],
+            ret: Option<&str>,
+        ) -> FunctionSignature {
+            FunctionSignature {
+                receiver: receiver.map(|receiver_kind| ReceiverInfo {
+                    kind: String::new(),
+                    is_ref: receiver_kind != ReceiverKind::Owned,
+                    is_mut: receiver_kind == ReceiverKind::RefMut,
+                    receiver_kind,
+                    lifetime: None,
+                }),
+                params: params
+                    .iter()
+                    .enumerate()
+                    .map(|(i, ty)| ParamInfo {
+                        name: format!("arg{}", i + 1),
+                        ty: ty.to_string(),
+                        is_mut: false,
+                    })
+                    .collect(),
+                return_ty: Some(match ret {
+                    Some(ty) => ReturnType::Type(ty.to_string()),
+                    None => ReturnType::Unit,
+                }),
+                ..signature()
+            }
+        }
+
+        /// A public associated function without a body.
+        pub fn method(path: &str) -> MethodDetails {
+            MethodDetails {
+                name: last_segment(path),
+                path: path.to_string(),
+                signature: String::new(),
+                parsed_signature: signature(),
+                visibility: Visibility::Public,
+                is_method: false,
+                overrides_default: false,
+                has_body: false,
+                body_source: None,
+                body_tokens: None,
+                is_unsafe: false,
+                is_const: false,
+                is_async: false,
+                docs: None,
+                attributes: Vec::new(),
+                span: None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_normalize_crate_name() {
+        // Reflecting on the package name and the crate name hits the same crate
+        for requested in ["my-types", "my_types"] {
+            assert_eq!(normalize_crate_name(requested), "my_types");
+        }
+        assert_eq!(normalize_crate_name("serde"), "serde");
+    }
+
+    #[test]
+    fn test_tidy_const_args() {
+        assert_eq!(tidy_const_args("[u8; 32_usize]"), "[u8; 32]");
+        assert_eq!(tidy_const_args("[u8; N/#0]"), "[u8; N]");
+        assert_eq!(
+            tidy_const_args("[[u16; 2_usize]; 3_usize]"),
+            "[[u16; 2]; 3]"
+        );
+        assert_eq!(
+            tidy_const_args("example::Buffer<4_u8, M/#1>"),
+            "example::Buffer<4, M>"
+        );
+        // `cells: [[u8; W]; W]` in `Grid<const W: usize>` keeps the parameter
+        // name at every level
+        assert_eq!(tidy_const_args("[[u8; W/#0]; W/#0]"), "[[u8; W]; W]");
+        assert_eq!(
+            tidy_const_args("std::option::Option<[u16; W/#0]>"),
+            "std::option::Option<[u16; W]>"
+        );
+        // Digits inside identifiers are left alone
+        assert_eq!(tidy_const_args("u128"), "u128");
+        assert_eq!(tidy_const_args("Vec2_usize"), "Vec2_usize");
+    }
+
+    #[test]
+    fn test_render_impl_trait_args() {
+        let strings = |s: &[&str]| s.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            render_trait_bound("std::iter::Iterator", &strings(&["Item = u8"])),
+            "std::iter::Iterator<Item = u8>"
+        );
+        assert_eq!(
+            render_trait_bound("example::Codec<u8>", &strings(&["Output = [u8; 4_usize]"])),
+            "example::Codec<u8, Output = [u8; 4]>"
+        );
+        assert_eq!(
+            render_trait_bound("std::marker::Send", &[]),
+            "std::marker::Send"
+        );
+
+        // `fn sum_bytes(bytes: impl Iterator<Item = u8>)`
+        let iter = (
+            "impl Iterator<Item = u8>".to_string(),
+            strings(&["std::iter::Iterator<Item = u8>"]),
+        );
+        assert_eq!(
+            render_impl_trait_args("impl Iterator<Item = u8>", std::slice::from_ref(&iter)),
+            "impl std::iter::Iterator<Item = u8>"
+        );
+
+        // One name containing another, behind a reference, plus a bare
+        // `impl Sized`
+        let impl_traits = [
+            ("impl Display".to_string(), strings(&["std::fmt::Display"])),
+            (
+                "impl Display + Send".to_string(),
+                strings(&["std::fmt::Display", "std::marker::Send"]),
+            ),
+            ("impl Sized".to_string(), Vec::new()),
+        ];
+        assert_eq!(
+            render_impl_trait_args("(&impl Display, impl Display + Send)", &impl_traits),
+            "(&impl std::fmt::Display, impl std::fmt::Display + std::marker::Send)"
+        );
+        assert_eq!(
+            render_impl_trait_args("impl Sized", &impl_traits),
+            "impl std::marker::Sized"
+        );
+        assert_eq!(render_impl_trait_args("u8", &impl_traits), "u8");
+    }
 
     #[test]
     fn test_path_matching() {
@@ -846,6 +2629,387 @@ mod tests {
         assert!(path_matches_pattern("foo::BarBaz", "foo::Bar*"));
         assert!(path_matches_pattern("foo::Bar", "foo::Bar*"));
         assert!(!path_matches_pattern("foo::Baz", "foo::Bar*"));
+
+        // Multiple wildcards
+        assert!(path_matches_pattern(
+            "foo::GetUserRequest",
+            "foo::Get*Request"
+        ));
+        assert!(path_matches_pattern("foo::GetRequest", "foo::Get*Request"));
+        assert!(!path_matches_pattern(
+            "foo::GetUserResponse",
+            "foo::Get*Request"
+        ));
+        assert!(path_matches_pattern("ui::ClickEvent", "*Event"));
+        assert!(!path_matches_pattern("ui::EventQueue", "*Event"));
+        assert!(path_matches_pattern("ui::OnSizeChanged", "ui::On*Changed"));
+        assert!(path_matches_pattern("ui::OnChanged", "*On*Changed*"));
+        assert!(!path_matches_pattern("ui::OnChange", "ui::On*Changed"));
+        assert!(path_matches_pattern(
+            "a::GetXRequestRequest",
+            "a::Get*Request"
+        ));
+    }
+
+    #[test]
+    fn test_validate_pattern() {
+        for pattern in [
+            "",
+            "foo::Bar",
+            "foo::*",
+            "foo::**",
+            "**",
+            "*Event",
+            "foo::Get*Request",
+        ] {
+            assert_eq!(validate_pattern(pattern), Ok(()), "{}", pattern);
+        }
+        for pattern in [
+            "foo::**::Bar",
+            "**::Bar",
+            "foo::Bar**",
+            "foo::",
+            "foo:::Bar",
+            "foo:Bar",
+        ] {
+            assert!(validate_pattern(pattern).is_err(), "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn test_case_insensitive_matching() {
+        let options = MatchOptions {
+            case_insensitive: true,
+        };
+
+        // Exact
+        assert!(path_matches_pattern_with(
+            "models::User",
+            "models::user",
+            &options
+        ));
+        assert!(!path_matches_pattern("models::User", "models::user"));
+        assert!(!path_matches_pattern_with(
+            "models::Users",
+            "models::user",
+            &options
+        ));
+
+        // Wildcards and globs
+        assert!(path_matches_pattern_with(
+            "models::UserId",
+            "MODELS::user*",
+            &options
+        ));
+        assert!(path_matches_pattern_with(
+            "ui::ClickEvent",
+            "*event",
+            &options
+        ));
+        assert!(path_matches_pattern_with(
+            "Models::Admin",
+            "models::*",
+            &options
+        ));
+        assert!(!path_matches_pattern("Models::Admin", "models::*"));
+    }
+
+    #[test]
+    fn test_trait_path_matching() {
+        let from_string = "std::convert::From<std::string::String>";
+
+        // Bare trait names match any instantiation
+        assert!(trait_path_matches(from_string, "From"));
+        assert!(trait_path_matches(from_string, "std::convert::From"));
+        assert!(trait_path_matches("MyTrait", "MyTrait"));
+        assert!(!trait_path_matches(from_string, "Into"));
+        assert!(!trait_path_matches("my::Fromage", "From"));
+
+        // Generic args must match, with or without module paths
+        assert!(trait_path_matches(from_string, from_string));
+        assert!(trait_path_matches(from_string, "From<String>"));
+        assert!(trait_path_matches(
+            from_string,
+            "std::convert::From<String>"
+        ));
+        assert!(!trait_path_matches(from_string, "From<u64>"));
+
+        // Only standard library paths are optional
+        assert!(!trait_path_matches(
+            "foo::From<std::string::String>",
+            "From<String>"
+        ));
+        assert!(trait_path_matches(
+            "foo::From<std::string::String>",
+            "foo::From<String>"
+        ));
+        assert!(!trait_path_matches(
+            "std::convert::From<models::User>",
+            "From<User>"
+        ));
+        assert!(trait_path_matches(
+            "std::convert::From<(u8, u8)>",
+            "From<(u8,u8)>"
+        ));
+    }
+
+    #[test]
+    fn test_sort_by_path_is_deterministic() {
+        let summary = |path: &str| TypeSummary {
+            name: path.rsplit("::").next().unwrap().to_string(),
+            path: path.to_string(),
+            kind: TypeKind::Struct,
+            generics: Vec::new(),
+            crate_name: None,
+        };
+        let paths = ["b::Beta", "a::Alpha", "c::Gamma", "a::Delta"];
+
+        // Two calls seeing the same types in different map iteration orders
+        let mut first: Vec<TypeSummary> = paths.iter().map(|p| summary(p)).collect();
+        let mut second: Vec<TypeSummary> = paths.iter().rev().map(|p| summary(p)).collect();
+        sort_by_path(&mut first, |t| &t.path);
+        sort_by_path(&mut second, |t| &t.path);
+
+        let order =
+            |types: &[TypeSummary]| types.iter().map(|t| t.path.clone()).collect::<Vec<_>>();
+        assert_eq!(order(&first), order(&second));
+        assert_eq!(
+            order(&first),
+            ["a::Alpha", "a::Delta", "b::Beta", "c::Gamma"]
+        );
+    }
+
+    #[test]
+    fn test_module_tree() {
+        let item = |path: &str, kind: ItemKind| ItemInfo {
+            name: path.rsplit("::").next().unwrap().to_string(),
+            path: path.to_string(),
+            kind,
+            visibility: Visibility::Public,
+            span: None,
+            is_test: false,
+            stable_id: String::new(),
+        };
+        let module = |path: &str| ModuleInfo {
+            name: path.rsplit("::").next().unwrap().to_string(),
+            path: path.to_string(),
+            visibility: Visibility::Public,
+            items: Vec::new(),
+            reexports: Vec::new(),
+        };
+
+        let modules: HashMap<String, ModuleInfo> = ["net", "net::tcp", "io"]
+            .into_iter()
+            .map(|path| (path.to_string(), module(path)))
+            .collect();
+        let items = vec![
+            item("Config", ItemKind::Struct),
+            item("net", ItemKind::Mod),
+            item("net::connect", ItemKind::Function),
+            item("net::tcp", ItemKind::Mod),
+            item("net::tcp::Stream", ItemKind::Struct),
+            item("io", ItemKind::Mod),
+            item("{use#0}", ItemKind::Use),
+        ];
+
+        let tree = ModuleTree::build("app", &modules, &items);
+        assert_eq!(tree.name, "app");
+        assert_eq!(tree.path, "");
+        let paths = |items: &[ItemInfo]| items.iter().map(|i| i.path.clone()).collect::<Vec<_>>();
+        assert_eq!(paths(&tree.items), ["Config"]);
+
+        let names: Vec<&str> = tree.submodules.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["io", "net"]);
+        let net = &tree.submodules[1];
+        assert_eq!(paths(&net.items), ["net::connect"]);
+        assert_eq!(net.submodules.len(), 1);
+        assert_eq!(paths(&net.submodules[0].items), ["net::tcp::Stream"]);
+        assert!(tree.submodules[0].items.is_empty());
+    }
+
+    #[test]
+    fn test_generic_type_path_matching() {
+        // Impls on the generic `Baz<T>` are keyed by its base path
+        assert!(type_path_matches("Baz", "Baz"));
+        assert!(type_path_matches("Baz", "Baz<i32>"));
+        assert!(type_path_matches("Baz<T>", "Baz"));
+        assert!(type_path_matches(
+            "example::Baz",
+            "Baz<std::string::String>"
+        ));
+        assert!(!type_path_matches("Baz", "example::Baz"));
+        assert!(!type_path_matches("MyBaz", "Baz<i32>"));
+        assert!(!type_path_matches("Bar", "Baz"));
+    }
+
+    #[test]
+    fn test_type_summary_generics() {
+        let param = |name: &str, kind: GenericParamKind| GenericParam {
+            name: name.to_string(),
+            kind,
+            bounds: Vec::new(),
+            default: None,
+        };
+        let summary = |generics: Vec<GenericParam>| TypeSummary {
+            name: "Foo".to_string(),
+            path: "Foo".to_string(),
+            kind: TypeKind::Struct,
+            generics,
+            crate_name: None,
+        };
+
+        let plain = summary(Vec::new());
+        assert!(!plain.is_generic() && !plain.has_lifetimes());
+
+        let borrowed = summary(vec![param("'a", GenericParamKind::Lifetime)]);
+        assert!(borrowed.has_lifetimes());
+        assert!(!borrowed.is_generic());
+
+        let buffer = summary(vec![
+            param("T", GenericParamKind::Type),
+            param(
+                "N",
+                GenericParamKind::Const {
+                    ty: "usize".to_string(),
+                },
+            ),
+        ]);
+        assert!(buffer.is_generic());
+        assert!(buffer.has_type_params() && buffer.has_const_params());
+        assert!(!buffer.has_lifetimes());
+
+        let sized = summary(vec![param(
+            "N",
+            GenericParamKind::Const {
+                ty: "usize".to_string(),
+            },
+        )]);
+        assert!(sized.is_generic() && !sized.has_type_params());
+    }
+
+    #[test]
+    fn test_discriminant_value() {
+        let variant = |discriminant: Option<&str>| EnumVariantInfo {
+            discriminant: discriminant.map(str::to_string),
+            ..variant("A", 0, Vec::new())
+        };
+
+        assert_eq!(variant(Some("4")).discriminant_value(), Some(4));
+        assert_eq!(variant(Some("-1")).discriminant_value(), Some(-1));
+        assert_eq!(variant(None).discriminant_value(), None);
+        assert_eq!(
+            variant(Some(&u128::MAX.to_string())).discriminant_value(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_retain_public() {
+        let type_details = |path: &str, visibility: Visibility| TypeDetails {
+            visibility,
+            fields: Some(vec![
+                field(Some("id"), 0, "u32"),
+                FieldInfo {
+                    visibility: Visibility::Private,
+                    ..field(Some("secret"), 1, "u32")
+                },
+            ]),
+            ..type_details(path, TypeKind::Struct)
+        };
+
+        let mut info = CrateTypeInfo::default();
+        for (path, visibility) in [("User", Visibility::Public), ("Cache", Visibility::Crate)] {
+            info.types
+                .insert(path.to_string(), type_details(path, visibility.clone()));
+            info.items.push(ItemInfo {
+                name: path.to_string(),
+                path: path.to_string(),
+                kind: ItemKind::Struct,
+                visibility,
+                span: None,
+                is_test: false,
+                stable_id: String::new(),
+            });
+            info.trait_impls.insert(path.to_string(), Vec::new());
+        }
+        for (path, visibility) in [("MAX", Visibility::Public), ("SALT", Visibility::Private)] {
+            info.consts.insert(
+                path.to_string(),
+                ConstDetails {
+                    name: path.to_string(),
+                    path: path.to_string(),
+                    is_static: false,
+                    ty: "u32".to_string(),
+                    value: Some("3".to_string()),
+                    init_source: Some("3".to_string()),
+                    docs: None,
+                    visibility,
+                    source: None,
+                    span: None,
+                },
+            );
+        }
+
+        info.retain_public();
+
+        assert_eq!(info.items.len(), 1);
+        assert!(info.types.contains_key("User"));
+        assert!(!info.types.contains_key("Cache"));
+        assert!(!info.trait_impls.contains_key("Cache"));
+        assert!(info.consts.contains_key("MAX"));
+        assert!(!info.consts.contains_key("SALT"));
+        let fields = info.types["User"].fields.as_ref().unwrap();
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].name.as_deref(), Some("id"));
+    }
+
+    #[test]
+    fn test_type_structure() {
+        let named = |path: &str, args: Vec<TypeStructure>| TypeStructure::Named {
+            path: path.to_string(),
+            args,
+            summary: None,
+        };
+
+        assert_eq!(
+            TypeStructure::parse("(u32, [u8; 4])"),
+            TypeStructure::Tuple {
+                elements: vec![
+                    named("u32", Vec::new()),
+                    TypeStructure::Array {
+                        element: Box::new(named("u8", Vec::new())),
+                        len: "4".to_string(),
+                    },
+                ],
+            }
+        );
+        assert_eq!(
+            TypeStructure::parse("&'a mut [std::vec::Vec<u8, std::alloc::Global>]"),
+            TypeStructure::Reference {
+                target: Box::new(TypeStructure::Slice {
+                    element: Box::new(named(
+                        "std::vec::Vec",
+                        vec![
+                            named("u8", Vec::new()),
+                            named("std::alloc::Global", Vec::new())
+                        ],
+                    )),
+                }),
+                mutable: true,
+                lifetime: Some("'a".to_string()),
+            }
+        );
+        assert_eq!(
+            TypeStructure::parse("()"),
+            TypeStructure::Tuple {
+                elements: Vec::new()
+            }
+        );
+        assert!(matches!(
+            TypeStructure::parse("std::boxed::Box<dyn Fn(u8) -> u8>"),
+            TypeStructure::Named { ref args, .. }
+                if matches!(args.as_slice(), [TypeStructure::Other { .. }])
+        ));
     }
 
     #[test]
@@ -885,4 +3049,290 @@ mod tests {
 
         assert_eq!(parsed.id, 42);
     }
+
+    #[test]
+    fn test_where_predicates_default() {
+        // Older responses without the field still parse
+        let legacy =
+            r#"{"receiver":null,"params":[],"return_ty":null,"generics":[],"where_clause":null}"#;
+        let parsed: FunctionSignature = serde_json::from_str(legacy).unwrap();
+        assert!(parsed.where_predicates.is_empty());
+    }
+
+    #[test]
+    fn test_self_ty_args_match() {
+        // `impl Serialize for Wrapper<std::string::String>`
+        let details = TraitImplDetails {
+            self_ty: "Wrapper".to_string(),
+            self_ty_args: vec!["std::string::String".to_string()],
+            trait_path: "Serialize".to_string(),
+            trait_args: Vec::new(),
+            generics: Vec::new(),
+            where_clause: None,
+            is_negative: false,
+            is_unsafe: false,
+            methods: Vec::new(),
+            assoc_types: Vec::new(),
+            assoc_consts: Vec::new(),
+            source: None,
+            span: None,
+            docs: None,
+            attributes: Vec::new(),
+        };
+
+        assert!(details.self_ty_args_match(&["std::string::String"]));
+        assert!(details.self_ty_args_match(&["String"]));
+        assert!(!details.self_ty_args_match(&["u8"]));
+        assert!(!details.self_ty_args_match(&[]));
+        assert!(!details.self_ty_args_match(&["String", "u8"]));
+    }
+
+    #[test]
+    fn test_return_type() {
+        let cases = [
+            (ReturnType::Unit, r#""unit""#),
+            (ReturnType::Never, r#""never""#),
+            (ReturnType::Type("u8".to_string()), r#"{"type":"u8"}"#),
+            (
+                ReturnType::ImplTrait(vec!["Iterator<Item = u8>".to_string(), "Send".to_string()]),
+                r#"{"impl_trait":["Iterator<Item = u8>","Send"]}"#,
+            ),
+        ];
+        for (ret, json) in cases {
+            assert_eq!(serde_json::to_string(&ret).unwrap(), json);
+            assert_eq!(serde_json::from_str::<ReturnType>(json).unwrap(), ret);
+        }
+
+        assert_eq!(ReturnType::Type("u8".to_string()).ty(), Some("u8"));
+        assert_eq!(ReturnType::ImplTrait(Vec::new()).ty(), None);
+        assert!(ReturnType::Unit.is_unit());
+    }
+
+    #[test]
+    fn test_find_methods() {
+        let method = |path: &str, attributes: &[&str]| MethodDetails {
+            is_method: true,
+            has_body: true,
+            attributes: attributes.iter().map(|a| a.to_string()).collect(),
+            ..method(path)
+        };
+
+        let mut info = CrateTypeInfo::default();
+        info.inherent_impls.insert(
+            "User".to_string(),
+            vec![InherentImplDetails {
+                self_ty: "User".to_string(),
+                self_ty_args: Vec::new(),
+                generics: Vec::new(),
+                where_clause: None,
+                is_unsafe: false,
+                methods: vec![
+                    method("User::run_login", &["#[command]"]),
+                    method("User::new", &[]),
+                ],
+                assoc_consts: Vec::new(),
+                assoc_types: Vec::new(),
+                source: None,
+                span: None,
+                docs: None,
+                attributes: Vec::new(),
+            }],
+        );
+        info.trait_impls.insert(
+            "User".to_string(),
+            vec![TraitImplDetails {
+                self_ty: "User".to_string(),
+                self_ty_args: Vec::new(),
+                trait_path: "Runner".to_string(),
+                trait_args: Vec::new(),
+                generics: Vec::new(),
+                where_clause: None,
+                is_negative: false,
+                is_unsafe: false,
+                methods: vec![method("<User as Runner>::run_all", &[])],
+                assoc_types: Vec::new(),
+                assoc_consts: Vec::new(),
+                source: None,
+                span: None,
+                docs: None,
+                attributes: Vec::new(),
+            }],
+        );
+
+        let methods = info.find_methods("*::run_*", false);
+        assert_eq!(methods.len(), 1);
+        assert_eq!(methods[0].path, "User::run_login");
+        assert_eq!(methods[0].attributes, vec!["#[command]"]);
+
+        let paths: Vec<String> = info
+            .find_methods("*run_*", true)
+            .into_iter()
+            .map(|m| m.path)
+            .collect();
+        assert_eq!(paths, vec!["<User as Runner>::run_all", "User::run_login"]);
+    }
+
+    #[test]
+    fn test_method_role() {
+        let role = MethodRole::classify;
+
+        let getter = method_signature(Some(ReceiverKind::Ref), &[], Some("u8"));
+        assert_eq!(role("User::age", &getter), MethodRole::Getter);
+
+        let setter = method_signature(Some(ReceiverKind::RefMut), &["u8"], None);
+        assert_eq!(role("User::set_age", &setter), MethodRole::Setter);
+        assert_eq!(role("User::push", &setter), MethodRole::Other);
+
+        let new = method_signature(None, &["u8"], Some("model::User"));
+        assert_eq!(role("model::User::new", &new), MethodRole::Constructor);
+        let generic = method_signature(None, &[], Some("Pair<T>"));
+        assert_eq!(role("Pair::<T>::empty", &generic), MethodRole::Constructor);
+        let default = method_signature(None, &[], Some("Self"));
+        assert_eq!(
+            role("<User as Default>::default", &default),
+            MethodRole::Constructor
+        );
+        let fallible = method_signature(None, &[], Some("std::option::Option<User>"));
+        assert_eq!(role("User::try_new", &fallible), MethodRole::Other);
+
+        let unit = method_signature(Some(ReceiverKind::Ref), &[], None);
+        assert_eq!(role("User::log", &unit), MethodRole::Other);
+    }
+
+    #[test]
+    fn test_type_layout() {
+        let layout = |is_sized: bool| LayoutInfo {
+            size: 8,
+            align: 8,
+            field_offsets: None,
+            variants: None,
+            is_sized,
+            is_copy: false,
+            is_send: true,
+            is_sync: true,
+        };
+        let param = |name: &str, kind: GenericParamKind| GenericParam {
+            name: name.to_string(),
+            kind,
+            bounds: Vec::new(),
+            default: None,
+        };
+        let type_details = |path: &str, generics: Vec<GenericParam>| TypeDetails {
+            generics,
+            fields: Some(Vec::new()),
+            ..type_details(path, TypeKind::Struct)
+        };
+
+        let mut info = CrateTypeInfo::default();
+        info.layouts.insert("Header".to_string(), layout(true));
+        info.layouts.insert("Packet".to_string(), layout(false));
+        info.types.insert(
+            "Buffer".to_string(),
+            type_details(
+                "Buffer",
+                vec![
+                    param("'a", GenericParamKind::Lifetime),
+                    param("T", GenericParamKind::Type),
+                ],
+            ),
+        );
+        info.types
+            .insert("Broken".to_string(), type_details("Broken", Vec::new()));
+
+        assert!(matches!(
+            info.type_layout("Header"),
+            Some(TypeLayout::Computed(l)) if l.size == 8
+        ));
+        assert!(matches!(
+            info.type_layout("Packet"),
+            Some(TypeLayout::Unsized)
+        ));
+        // Lifetimes don't affect the layout
+        assert!(matches!(
+            info.type_layout("Buffer"),
+            Some(TypeLayout::Generic { params }) if params == ["T"]
+        ));
+        assert!(info.type_layout("Broken").is_none());
+        assert!(info.type_layout("Missing").is_none());
+
+        // The layout status shares the response object with its type tag
+        let data = QueryData::Layout(TypeLayout::Generic {
+            params: vec!["T".to_string()],
+        });
+        let json = serde_json::to_string(&data).unwrap();
+        assert!(matches!(
+            serde_json::from_str(&json).unwrap(),
+            QueryData::Layout(TypeLayout::Generic { .. })
+        ));
+    }
+
+    #[test]
+    fn test_variant_field() {
+        // enum Shape { Circle { radius: f64 }, Rect(u32, u64) }
+        let mut info = CrateTypeInfo::default();
+        info.types.insert(
+            "geo::Shape".to_string(),
+            enum_details(
+                "geo::Shape",
+                vec![
+                    variant("Circle", 0, vec![field(Some("radius"), 0, "f64")]),
+                    variant(
+                        "Rect",
+                        1,
+                        vec![field(None, 0, "u32"), field(None, 1, "u64")],
+                    ),
+                ],
+            ),
+        );
+
+        let radius = info
+            .variant_field("geo::Shape", "Circle", "radius")
+            .unwrap();
+        assert_eq!(radius.ty, "f64");
+        let height = info.variant_field("Shape", "Rect", "1").unwrap();
+        assert_eq!(height.ty, "u64");
+
+        assert!(info.variant_field("Shape", "Rect", "2").is_err());
+        assert!(info.variant_field("Shape", "Circle", "0").is_err());
+        assert!(info.variant_field("Shape", "Triangle", "0").is_err());
+        assert!(info.variant_field("Color", "Red", "0").is_err());
+    }
+
+    #[test]
+    fn test_types_using_type() {
+        let mut info = CrateTypeInfo::default();
+        let types = [
+            struct_details(
+                "db::User",
+                vec![
+                    field(Some("id"), 0, "ids::LegacyId"),
+                    field(Some("name"), 1, "std::string::String"),
+                    field(Some("friends"), 2, "std::vec::Vec<ids::LegacyId>"),
+                ],
+            ),
+            enum_details(
+                "db::Key",
+                vec![variant("Old", 0, vec![field(None, 0, "ids::LegacyId")])],
+            ),
+            // A longer name ending the same way isn't the same type
+            struct_details("db::Other", vec![field(Some("id"), 0, "ids::NotLegacyId")]),
+        ];
+        for t in types {
+            info.types.insert(t.path.clone(), t);
+        }
+
+        let usages = info.types_using_type("LegacyId");
+        let found: Vec<(&str, Vec<String>)> = usages
+            .iter()
+            .map(|u| (u.owner.path.as_str(), u.fields.clone()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("db::Key", vec!["Old.0".to_string()]),
+                ("db::User", vec!["id".to_string(), "friends".to_string()]),
+            ]
+        );
+        assert!(info.types_using_type("Missing").is_empty());
+    }
 }
@@ -0,0 +1,25 @@
+//! Generates `BuildTarget` and `BUILD_TARGET` into `OUT_DIR`, so the crate has a type that only
+//! exists once the build script has run.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    let target = env::var("TARGET").unwrap_or_default();
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+
+    let source = format!(
+        r#"/// A target triple, generated by `build.rs`.
+pub struct BuildTarget {{
+    pub triple: &'static str,
+}}
+
+/// The target this crate was built for.
+pub const BUILD_TARGET: BuildTarget = BuildTarget {{ triple: {:?} }};
+"#,
+        target
+    );
+    fs::write(Path::new(&out_dir).join("build_target.rs"), source).unwrap();
+    println!("cargo:rerun-if-changed=build.rs");
+}
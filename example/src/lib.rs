@@ -3,9 +3,17 @@
 //! This crate defines some types and traits that can be introspected
 //! using Bronzite's daemon and macros.
 
+// `BuildTarget` and `BUILD_TARGET`, written by build.rs
+include!(concat!(env!("OUT_DIR"), "/build_target.rs"));
+
 /// A simple trait for demonstration.
 pub trait MyTrait {
     fn do_something(&self) -> String;
+
+    /// Default method that wraps `do_something`.
+    fn describe(&self) -> String {
+        format!("<{}>", self.do_something())
+    }
 }
 
 /// A struct that implements MyTrait.
@@ -17,6 +25,23 @@ impl MyTrait for Foo {
     fn do_something(&self) -> String {
         format!("Foo does something with value: {}", self.value)
     }
+
+    fn describe(&self) -> String {
+        format!("Foo({})", self.value)
+    }
+}
+
+/// Lets a borrowed `Foo` stand in wherever `MyTrait` is expected.
+impl MyTrait for &Foo {
+    fn do_something(&self) -> String {
+        Foo::do_something(self)
+    }
+}
+
+impl From<i32> for Foo {
+    fn from(value: i32) -> Self {
+        Foo { value }
+    }
 }
 
 /// A struct with inherent methods.
@@ -26,7 +51,9 @@ pub struct Bar {
 
 impl Bar {
     pub fn new(name: String) -> Self {
-        Self { name }
+        Self {
+            name: Self::normalize(name),
+        }
     }
 
     pub fn get_name(&self) -> &str {
@@ -34,7 +61,11 @@ impl Bar {
     }
 
     pub fn set_name(&mut self, name: String) {
-        self.name = name;
+        self.name = Self::normalize(name);
+    }
+
+    fn normalize(name: String) -> String {
+        name.trim().to_string()
     }
 }
 
@@ -53,11 +84,90 @@ impl<T> Baz<T> {
     }
 }
 
+impl<T: std::fmt::Debug> MyTrait for Baz<T> {
+    fn do_something(&self) -> String {
+        format!("Baz holding {:?}", self.data)
+    }
+}
+
+/// Identifies an account's user.
+pub type UserId = u64;
+
+/// The `Bar` that owns an account.
+pub type Owner = Bar;
+
+/// A struct whose fields are spelled through type aliases.
+pub struct Account {
+    pub id: UserId,
+    pub owner: Owner,
+}
+
+/// A square grid whose side length is a const parameter.
+pub struct Grid<const W: usize> {
+    pub cells: [[u8; W]; W],
+}
+
+/// A labelled value that may be unsized, like `Labelled<str>`.
+pub struct Labelled<T: ?Sized> {
+    pub label: u8,
+    pub value: T,
+}
+
+/// Number of checksum words in a [`Packet`].
+pub const CHECKSUM_WORDS: usize = 2;
+
+/// Largest number of packets buffered at once.
+pub static MAX_PACKETS: usize = 16 * 4;
+
+/// A text template with `{{ name }}` placeholders.
+pub struct Template {
+    pub source: &'static str,
+}
+
+/// The template used to greet new users.
+pub const GREETING: Template = Template {
+    source: "Hello, {{ name }}!",
+};
+
+/// A fixed-size packet with array fields.
+#[repr(C)]
+pub struct Packet {
+    pub header: [u8; 4],
+    pub checksum: [u16; CHECKSUM_WORDS * 2],
+}
+
+/// A union whose fields overlap at offset 0.
+pub union Bits {
+    pub byte: u8,
+    pub word: u32,
+    pub wide: u64,
+}
+
 /// Another trait with an associated type.
 pub trait AnotherTrait {
     type Output;
 
     fn transform(&self) -> Self::Output;
+
+    /// Consume the value; only callable on sized implementors.
+    fn into_output(self) -> Self::Output
+    where
+        Self: Sized,
+    {
+        self.transform()
+    }
+}
+
+/// A trait with an async method.
+#[allow(async_fn_in_trait)]
+pub trait Fetcher {
+    async fn fetch(&self) -> u32;
+}
+
+impl Fetcher for Foo {
+    async fn fetch(&self) -> u32 {
+        self.value as u32
+    }
 }
 
 impl AnotherTrait for i32 {
@@ -68,13 +178,128 @@ impl AnotherTrait for i32 {
     }
 }
 
+/// Add up a stream of bytes.
+pub fn sum_bytes(bytes: impl Iterator<Item = u8>) -> u32 {
+    bytes.map(u32::from).sum()
+}
+
+/// Something that can be used up, taking `self` in different ways.
+pub trait Consume {
+    fn consume(self);
+
+    fn consume_boxed(self: Box<Self>);
+
+    fn consume_pinned(self: std::pin::Pin<&mut Self>);
+}
+
+impl Consume for Box<Foo> {
+    fn consume(self) {}
+
+    fn consume_boxed(self: Box<Self>) {}
+
+    fn consume_pinned(self: std::pin::Pin<&mut Self>) {}
+}
+
+impl Consume for std::rc::Rc<Bar> {
+    fn consume(self) {}
+
+    fn consume_boxed(self: Box<Self>) {}
+
+    fn consume_pinned(self: std::pin::Pin<&mut Self>) {}
+}
+
+/// Make a function adding `n` to its argument.
+pub fn adder(n: u8) -> impl Fn(u8) -> u16 {
+    move |x| u16::from(x) + u16::from(n)
+}
+
+/// Call `f` on every number up to `n`.
+pub fn repeat(n: u8, mut f: impl FnMut(u8) -> bool) {
+    for i in 0..n {
+        f(i);
+    }
+}
+
+/// The even numbers below 10.
+pub fn evens() -> impl Iterator<Item = u8> {
+    (0..10).filter(|n| n % 2 == 0)
+}
+
+/// The numbers below 10, from either end.
+pub fn digits() -> impl DoubleEndedIterator<Item = u8> {
+    0..10
+}
+
+/// Stop the program.
+pub fn halt() -> ! {
+    panic!("halted")
+}
+
+/// Types with a format version.
+pub trait Versioned {
+    const VERSION: u32;
+}
+
+impl Versioned for Foo {
+    const VERSION: u32 = 3;
+}
+
+/// Never used, so rustc never evaluates it and the crate builds.
+impl Versioned for Bar {
+    const VERSION: u32 = { panic!("Bar has no version yet") };
+}
+
+/// Someone listed in a [`Registry`].
+pub struct User {
+    pub name: String,
+}
+
+/// Keeps track of users.
+pub struct Registry {
+    users: Vec<User>,
+}
+
+impl Registry {
+    pub fn rename(&mut self, user: &mut User, name: String) {
+        user.name = name;
+    }
+
+    pub fn add_all(&mut self, users: Vec<User>) {
+        self.users.extend(users);
+    }
+}
+
+/// Private, but at the crate root the whole crate can see it.
+#[allow(dead_code)]
+struct RootPrivate;
+
+/// Types with each kind of restricted visibility.
+#[allow(dead_code)]
+pub mod access {
+    pub(crate) struct CrateVisible;
+
+    pub mod inner {
+        pub(super) struct ParentVisible;
+
+        pub(in crate::access) struct AccessVisible;
+
+        struct Hidden;
+    }
+}
+
+/// A `Foo` for tests to start from.
+#[cfg(test)]
+pub fn sample_foo() -> Foo {
+    Foo { value: 42 }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_foo() {
-        let foo = Foo { value: 42 };
+        let foo = sample_foo();
         assert_eq!(foo.do_something(), "Foo does something with value: 42");
     }
 
@@ -90,6 +315,12 @@ mod tests {
         assert_eq!(baz.data, vec![1, 2, 3]);
     }
 
+    #[test]
+    fn test_generic_baz_impl() {
+        let baz = Baz::new(7);
+        assert_eq!(baz.describe(), "<Baz holding 7>");
+    }
+
     #[test]
     fn test_another_trait() {
         let num = 123;
@@ -22,7 +22,7 @@
 //!    ```
 
 use my_macros::{implements_trait, list_methods, list_trait_impls};
-use my_types::{Product, Serialize, User};
+use my_types::{BUILD_INFO, Product, Serialize, User};
 
 // Use Bronzite to discover traits implemented by User at compile time
 list_trait_impls!("my_types", User);
@@ -36,6 +36,9 @@ list_methods!("my_types", User);
 const USER_IS_SERIALIZABLE: bool = implements_trait!("my_types", User, "Serialize");
 const PRODUCT_IS_SERIALIZABLE: bool = implements_trait!("my_types", Product, "Serialize");
 
+// `BuildInfo` is generated into OUT_DIR by my-types' build script
+const BUILD_INFO_IS_DEBUG: bool = implements_trait!("my_types", BuildInfo, "Debug");
+
 fn main() {
     println!("=== Bronzite Compile-Time Reflection Demo ===\n");
 
@@ -60,6 +63,7 @@ fn main() {
         "  Product implements Serialize: {}",
         PRODUCT_IS_SERIALIZABLE
     );
+    println!("  BuildInfo implements Debug: {}", BUILD_INFO_IS_DEBUG);
     println!();
 
     // Actually use the types
@@ -71,6 +75,7 @@ fn main() {
     let product = Product::new("SKU-001".to_string(), "Widget".to_string(), 29.99);
     println!("  Product: {:?}", product);
     println!("  Serialized: {}", product.serialize());
+    println!("  Build: {:?}", BUILD_INFO);
 
     println!("\n=== Demo Complete ===");
 }
@@ -76,11 +76,7 @@ pub fn list_trait_impls(input: TokenStream) -> TokenStream {
             }
         }
         Err(e) => {
-            let msg = format!(
-                "Failed to connect to Bronzite daemon: {}. \
-                 Make sure bronzite-daemon is installed and the toolchain nightly-2025-08-20 is available.",
-                e
-            );
+            let msg = e.to_string();
             quote! { compile_error!(#msg) }.into()
         }
     }
@@ -164,7 +160,7 @@ pub fn generate_getters(input: TokenStream) -> TokenStream {
             }
         }
         Err(e) => {
-            let msg = format!("Failed to connect to Bronzite daemon: {}", e);
+            let msg = e.to_string();
             quote! { compile_error!(#msg) }.into()
         }
     }
@@ -216,7 +212,7 @@ pub fn implements_trait(input: TokenStream) -> TokenStream {
             }
         },
         Err(e) => {
-            let msg = format!("Failed to connect to Bronzite daemon: {}", e);
+            let msg = e.to_string();
             quote! { compile_error!(#msg) }.into()
         }
     }
@@ -275,7 +271,7 @@ pub fn list_methods(input: TokenStream) -> TokenStream {
             }
         },
         Err(e) => {
-            let msg = format!("Failed to connect to Bronzite daemon: {}", e);
+            let msg = e.to_string();
             quote! { compile_error!(#msg) }.into()
         }
     }
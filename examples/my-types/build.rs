@@ -0,0 +1,26 @@
+//! Generates `BuildInfo` into `OUT_DIR`, so the example crate has a type
+//! that only exists once the build script has run.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    let profile = env::var("PROFILE").unwrap_or_default();
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+
+    let source = format!(
+        r#"/// How this crate was built, generated by `build.rs`.
+#[derive(Debug, Clone, Copy)]
+pub struct BuildInfo {{
+    pub profile: &'static str,
+}}
+
+/// The build this crate came from.
+pub const BUILD_INFO: BuildInfo = BuildInfo {{ profile: {:?} }};
+"#,
+        profile
+    );
+    fs::write(Path::new(&out_dir).join("build_info.rs"), source).unwrap();
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -3,6 +3,9 @@
 //! This crate defines various types and traits that can be introspected
 //! at compile time using Bronzite.
 
+// `BuildInfo` and `BUILD_INFO`, written by build.rs
+include!(concat!(env!("OUT_DIR"), "/build_info.rs"));
+
 /// A trait for serializable entities.
 pub trait Serialize {
     fn serialize(&self) -> String;